@@ -0,0 +1,15 @@
+
+CREATE TABLE IF NOT EXISTS user_session(
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    user_id integer NOT NULL REFERENCES user_info(id) ON DELETE CASCADE,
+    ip_address varchar(45),
+    user_agent text,
+    device varchar(50),
+    os varchar(50),
+    created_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at timestamp with time zone NOT NULL,
+    revoked_at timestamp with time zone,
+    PRIMARY KEY(id)
+);
+CREATE INDEX IF NOT EXISTS idx_user_session_user_id ON public.user_session USING btree (user_id, revoked_at);
@@ -0,0 +1,15 @@
+
+CREATE TABLE IF NOT EXISTS impersonation_event(
+    id BIGSERIAL NOT NULL,
+    token_jti varchar(64) NOT NULL,
+    actor_id integer NOT NULL REFERENCES user_info(id),
+    target_user_id integer NOT NULL REFERENCES user_info(id),
+    event varchar(20) NOT NULL,
+    http_method varchar(10),
+    path text,
+    ip_address varchar(45),
+    created_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(id)
+);
+CREATE INDEX IF NOT EXISTS idx_impersonation_event_actor ON public.impersonation_event USING btree (actor_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_impersonation_event_target ON public.impersonation_event USING btree (target_user_id, created_at);
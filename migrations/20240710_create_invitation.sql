@@ -0,0 +1,16 @@
+
+CREATE TABLE IF NOT EXISTS invitation(
+    id SERIAL NOT NULL,
+    code_hash varchar(64) NOT NULL,
+    role varchar(20) NOT NULL DEFAULT 'user'::character varying,
+    max_uses integer NOT NULL DEFAULT 1,
+    use_count integer NOT NULL DEFAULT 0,
+    email varchar(100),
+    expires_at timestamp with time zone,
+    created_by integer REFERENCES user_info(id) ON DELETE SET NULL,
+    created_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    revoked_at timestamp with time zone,
+    PRIMARY KEY(id),
+    CHECK (max_uses > 0)
+);
+CREATE UNIQUE INDEX IF NOT EXISTS invitation_code_hash_key ON public.invitation USING btree (code_hash);
@@ -0,0 +1,19 @@
+
+ALTER TABLE user_info ADD COLUMN IF NOT EXISTS deletion_requested_at timestamp with time zone;
+ALTER TABLE user_info ADD COLUMN IF NOT EXISTS deletion_scheduled_for timestamp with time zone;
+ALTER TABLE user_info ADD COLUMN IF NOT EXISTS anonymized_at timestamp with time zone;
+CREATE INDEX IF NOT EXISTS idx_user_info_deletion_scheduled ON public.user_info USING btree (deletion_scheduled_for)
+    WHERE deletion_scheduled_for IS NOT NULL AND anonymized_at IS NULL;
+
+CREATE TABLE IF NOT EXISTS data_export(
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    user_id integer NOT NULL REFERENCES user_info(id) ON DELETE CASCADE,
+    status varchar(20) NOT NULL DEFAULT 'pending'::character varying,
+    file_path text,
+    error text,
+    created_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at timestamp with time zone,
+    expires_at timestamp with time zone,
+    PRIMARY KEY(id)
+);
+CREATE INDEX IF NOT EXISTS idx_data_export_user_id ON public.data_export USING btree (user_id, created_at);
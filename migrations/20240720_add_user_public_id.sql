@@ -0,0 +1,17 @@
+
+-- Zaman sıralı, tahmin edilemez UUIDv7 üretimi (RFC 9562)
+CREATE OR REPLACE FUNCTION uuid_generate_v7() RETURNS uuid AS $$
+    SELECT encode(
+        set_bit(
+            set_bit(
+                overlay(uuid_send(gen_random_uuid())
+                        placing substring(int8send(floor(extract(epoch from clock_timestamp()) * 1000)::bigint) from 3)
+                        from 1 for 6),
+                52, 1),
+            53, 1),
+        'hex')::uuid;
+$$ LANGUAGE sql VOLATILE;
+
+-- Volatile DEFAULT her mevcut satır için ayrı değer üretir; eski kayıtlar bu adımda doldurulur
+ALTER TABLE user_info ADD COLUMN IF NOT EXISTS public_id uuid NOT NULL DEFAULT uuid_generate_v7();
+CREATE UNIQUE INDEX IF NOT EXISTS user_info_public_id_key ON public.user_info USING btree (public_id);
@@ -0,0 +1,30 @@
+
+-- Kullanıcı satırları silinse/anonimleştirilse de kayıtlar korunur; bu yüzden yabancı anahtar yoktur
+CREATE TABLE IF NOT EXISTS audit_event(
+    id BIGSERIAL NOT NULL,
+    occurred_at timestamp with time zone NOT NULL,
+    actor_id integer,
+    target_user_id integer,
+    action varchar(64) NOT NULL,
+    ip_address varchar(45),
+    user_agent text,
+    diff jsonb NOT NULL DEFAULT '{}'::jsonb,
+    prev_hash char(64) NOT NULL,
+    hash char(64) NOT NULL,
+    PRIMARY KEY(id)
+);
+CREATE INDEX IF NOT EXISTS idx_audit_event_actor ON public.audit_event USING btree (actor_id, occurred_at);
+CREATE INDEX IF NOT EXISTS idx_audit_event_action ON public.audit_event USING btree (action, occurred_at);
+CREATE INDEX IF NOT EXISTS idx_audit_event_occurred_at ON public.audit_event USING btree (occurred_at);
+
+-- Yalnızca ekleme: güncelleme ve silme veritabanı seviyesinde engellenir
+CREATE OR REPLACE FUNCTION audit_event_append_only() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_event is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS audit_event_no_update ON audit_event;
+CREATE TRIGGER audit_event_no_update
+    BEFORE UPDATE OR DELETE ON audit_event
+    FOR EACH ROW EXECUTE FUNCTION audit_event_append_only();
@@ -0,0 +1,9 @@
+
+-- Şüpheli giriş tespiti için oturumun açıldığı konum (GeoIP veritabanından)
+ALTER TABLE user_session ADD COLUMN IF NOT EXISTS country varchar(2);
+ALTER TABLE user_session ADD COLUMN IF NOT EXISTS city varchar(128);
+ALTER TABLE user_session ADD COLUMN IF NOT EXISTS latitude double precision;
+ALTER TABLE user_session ADD COLUMN IF NOT EXISTS longitude double precision;
+
+-- "Bu ben değildim" bağlantısı kullanıldığında parola sıfırlanana kadar parola ile giriş engellenir
+ALTER TABLE user_info ADD COLUMN IF NOT EXISTS password_reset_required boolean NOT NULL DEFAULT false;
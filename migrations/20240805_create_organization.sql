@@ -0,0 +1,56 @@
+
+CREATE TABLE IF NOT EXISTS organization(
+    id SERIAL NOT NULL,
+    public_id uuid NOT NULL DEFAULT uuid_generate_v7(),
+    name varchar(100) NOT NULL,
+    slug varchar(120) NOT NULL,
+    -- Üyelerin ortak kullandığı token havuzu
+    token_balance numeric(12,2) NOT NULL DEFAULT 0,
+    created_by integer REFERENCES user_info(id) ON DELETE SET NULL,
+    created_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(id),
+    CHECK (token_balance >= 0)
+);
+CREATE UNIQUE INDEX IF NOT EXISTS organization_public_id_key ON public.organization USING btree (public_id);
+CREATE UNIQUE INDEX IF NOT EXISTS organization_slug_key ON public.organization USING btree (slug);
+
+CREATE TABLE IF NOT EXISTS organization_member(
+    organization_id integer NOT NULL REFERENCES organization(id) ON DELETE CASCADE,
+    user_id integer NOT NULL REFERENCES user_info(id) ON DELETE CASCADE,
+    role varchar(20) NOT NULL DEFAULT 'member'::character varying,
+    -- NULL: sınırsız; aksi halde üyenin havuzdan harcayabileceği toplam token
+    spending_cap numeric(12,2),
+    spent numeric(12,2) NOT NULL DEFAULT 0,
+    joined_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(organization_id, user_id),
+    CHECK (role IN ('owner', 'admin', 'member')),
+    CHECK (spending_cap IS NULL OR spending_cap >= 0)
+);
+CREATE INDEX IF NOT EXISTS idx_organization_member_user ON public.organization_member USING btree (user_id);
+
+CREATE TABLE IF NOT EXISTS organization_invitation(
+    id SERIAL NOT NULL,
+    organization_id integer NOT NULL REFERENCES organization(id) ON DELETE CASCADE,
+    email varchar(100) NOT NULL,
+    role varchar(20) NOT NULL DEFAULT 'member'::character varying,
+    token_hash varchar(64) NOT NULL,
+    invited_by integer REFERENCES user_info(id) ON DELETE SET NULL,
+    expires_at timestamp with time zone NOT NULL,
+    accepted_at timestamp with time zone,
+    created_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(id),
+    CHECK (role IN ('admin', 'member'))
+);
+CREATE UNIQUE INDEX IF NOT EXISTS organization_invitation_token_hash_key ON public.organization_invitation USING btree (token_hash);
+CREATE INDEX IF NOT EXISTS idx_organization_invitation_org ON public.organization_invitation USING btree (organization_id, accepted_at);
+
+-- Sohbetler bir organizasyona ait olabilir; chat tablosu henüz bu şemada yoksa atlanır
+DO $$
+BEGIN
+    IF to_regclass('public.chat') IS NOT NULL THEN
+        ALTER TABLE chat ADD COLUMN IF NOT EXISTS organization_id integer REFERENCES organization(id) ON DELETE SET NULL;
+        CREATE INDEX IF NOT EXISTS idx_chat_organization ON public.chat USING btree (organization_id);
+    END IF;
+END
+$$;
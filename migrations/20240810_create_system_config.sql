@@ -0,0 +1,21 @@
+
+-- Yöneticilerin çalışma anında değiştirebildiği ayarlar; bulunmayan anahtarlar için koddaki varsayılan kullanılır
+CREATE TABLE IF NOT EXISTS system_config(
+    key varchar(100) NOT NULL,
+    value jsonb NOT NULL,
+    description text,
+    updated_by integer REFERENCES user_info(id) ON DELETE SET NULL,
+    updated_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(key)
+);
+
+CREATE TABLE IF NOT EXISTS system_config_history(
+    id BIGSERIAL NOT NULL,
+    key varchar(100) NOT NULL,
+    old_value jsonb,
+    new_value jsonb NOT NULL,
+    changed_by integer REFERENCES user_info(id) ON DELETE SET NULL,
+    changed_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(id)
+);
+CREATE INDEX IF NOT EXISTS idx_system_config_history_key ON public.system_config_history USING btree (key, changed_at);
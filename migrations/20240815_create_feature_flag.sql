@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS feature_flag(
+    key varchar(100) NOT NULL,
+    description text,
+    -- 'boolean' ya da 'multivariate'
+    kind varchar(20) NOT NULL DEFAULT 'boolean',
+    -- Kapalı bayraklar kurallara bakılmadan varsayılan varyantı döner
+    enabled boolean NOT NULL DEFAULT false,
+    variants jsonb NOT NULL DEFAULT '["off", "on"]'::jsonb,
+    default_variant varchar(100) NOT NULL DEFAULT 'off',
+    -- Sırayla değerlendirilen hedefleme kuralları; ilk eşleşen kural varyantı belirler
+    rules jsonb NOT NULL DEFAULT '[]'::jsonb,
+    updated_by integer REFERENCES user_info(id) ON DELETE SET NULL,
+    created_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at timestamp with time zone NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(key),
+    CHECK (kind IN ('boolean', 'multivariate'))
+);
@@ -0,0 +1,18 @@
+-- Uyarı ve hata seviyesindeki log satırları; saklama süresi dolanlar arka plan işiyle silinir
+CREATE TABLE IF NOT EXISTS system_log(
+    id BIGSERIAL NOT NULL,
+    occurred_at timestamp with time zone NOT NULL,
+    -- 'warn' ya da 'error'
+    level varchar(5) NOT NULL,
+    target varchar(255) NOT NULL,
+    message text NOT NULL,
+    -- Olayın mesaj dışındaki alanları (ör. durum kodu, hata ayrıntısı)
+    fields jsonb NOT NULL DEFAULT '{}'::jsonb,
+    request_id varchar(128),
+    -- Kullanıcı silinse de log satırı korunur; bu yüzden yabancı anahtar yoktur
+    user_id integer,
+    PRIMARY KEY(id)
+);
+CREATE INDEX IF NOT EXISTS idx_system_log_occurred_at ON public.system_log USING btree (occurred_at);
+CREATE INDEX IF NOT EXISTS idx_system_log_level ON public.system_log USING btree (level, occurred_at);
+CREATE INDEX IF NOT EXISTS idx_system_log_request_id ON public.system_log USING btree (request_id);
@@ -0,0 +1,11 @@
+-- Kullanıcı silindiğinde (ör. süresi dolan misafir hesaplar) taklit kayıtları silmeyi engellemez;
+-- kayıt korunur, silinen tarafın id'si boşaltılır
+ALTER TABLE impersonation_event
+    ALTER COLUMN actor_id DROP NOT NULL,
+    ALTER COLUMN target_user_id DROP NOT NULL,
+    DROP CONSTRAINT IF EXISTS impersonation_event_actor_id_fkey,
+    DROP CONSTRAINT IF EXISTS impersonation_event_target_user_id_fkey,
+    ADD CONSTRAINT impersonation_event_actor_id_fkey
+        FOREIGN KEY (actor_id) REFERENCES user_info(id) ON DELETE SET NULL,
+    ADD CONSTRAINT impersonation_event_target_user_id_fkey
+        FOREIGN KEY (target_user_id) REFERENCES user_info(id) ON DELETE SET NULL;
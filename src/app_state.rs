@@ -1,9 +1,29 @@
 use crate::config::AppConfig;
 use crate::error::AppError;
+use crate::services::ai_integration::{ai_provider_from_config, AiProvider};
+use crate::services::email::{email_sender_from_config, EmailSender};
+use crate::services::feature_flag::FeatureFlagStore;
+use crate::services::security_notifier::{security_notifier_from_config, SecurityNotifier};
+use crate::models::system_config::RuntimeSettings;
+use crate::services::sms::{sms_sender_from_config, SmsSender};
+use crate::services::system_config::SystemConfigStore;
+use crate::utils::geoip::GeoIpResolver;
+use crate::utils::metrics::Metrics;
+use crate::utils::password::PasswordHashing;
+use crate::utils::password_policy::PasswordPolicy;
+use crate::utils::shutdown::Shutdown;
+use crate::utils::traced_redis::TracedConnection;
 use deadpool_redis::Pool as RedisPool;
+use sqlx::migrate::Migrator;
 use sqlx::postgres::PgPoolOptions;
 use sqlx::PgPool;
-use std::sync::Arc; // Add missing import
+use tracing::{error, info, warn};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Derlemeye gömülü migration'lar; hazır olma kontrolü de bekleyen migration'ları buradan hesaplar
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
 
 #[derive(Clone)]
 pub struct AppState {
@@ -11,28 +31,124 @@ pub struct AppState {
     pub redis_pool: RedisPool, // Redis
     pub jwt_secret: String,    // JWT
     pub config: AppConfig,     // App config
+    pub sms_sender: Arc<dyn SmsSender>,
+    pub email_sender: Arc<dyn EmailSender>,
+    pub password_policy: PasswordPolicy,
+    pub password_hashing: PasswordHashing,
+    pub geoip: Arc<GeoIpResolver>,
+    pub security_notifier: Arc<dyn SecurityNotifier>,
+    pub ai_provider: Arc<dyn AiProvider>,
+    pub system_config: Arc<SystemConfigStore>,
+    pub feature_flags: Arc<FeatureFlagStore>,
+    pub shutdown: Arc<Shutdown>,
+    pub metrics: Arc<Metrics>,
+
+}
 
+/// Açılışta bağımlılığa bağlanmayı üstel bekleme ile yeniden dener; son hatayı döner
+async fn with_retry<T, F, Fut>(what: &str, config: &AppConfig, mut connect: F) -> Result<T, AppError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, AppError>>,
+{
+    let mut delay = Duration::from_millis(config.startup_backoff_ms);
+    let mut attempt = 1;
+    loop {
+        match connect().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < config.startup_retries => {
+                warn!("{} unavailable (attempt {}/{}): {}; retrying in {:?}", what, attempt, config.startup_retries, e, delay);
+                actix_web::rt::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => {
+                error!("{} unavailable after {} attempts: {}", what, attempt, e);
+                return Err(e);
+            }
+        }
+    }
 }
 
 impl AppState {
     pub async fn new(config: AppConfig) -> Result<Self, AppError> {
-        let db_pool = PgPoolOptions::new()
-            .max_connections(5)
-            .acquire_timeout(std::time::Duration::from_secs(5))
-            .connect(&config.database_url)
-            .await?;
-        println!("Migration BAŞLIYOR");
-        sqlx::migrate!("./migrations").run(&db_pool).await.unwrap();
-        println!("Migration BİTTİ");
-
-        let redis_cfg = deadpool_redis::Config::from_url(&config.redis_url);
-        let redis_pool = redis_cfg.create_pool(Some(deadpool_redis::Runtime::Tokio1)).unwrap();
+        let cfg = &config;
+        let db_pool = with_retry("PostgreSQL", cfg, || async move {
+            PgPoolOptions::new()
+                .max_connections(cfg.db_max_connections)
+                .min_connections(cfg.db_min_connections)
+                .acquire_timeout(Duration::from_secs(cfg.db_acquire_timeout_secs))
+                .connect(&cfg.database_url)
+                .await
+                .map_err(|e| AppError::db_error(&e.to_string()))
+        })
+        .await?;
+
+        if config.run_migrations {
+            info!("Running database migrations");
+            MIGRATOR
+                .run(&db_pool)
+                .await
+                .map_err(|e| AppError::db_error(&format!("migrations failed: {}", e)))?;
+            info!("Database migrations finished");
+        } else {
+            info!("Skipping database migrations (RUN_MIGRATIONS=false)");
+        }
+
+        let mut redis_cfg = deadpool_redis::Config::from_url(&config.redis_url);
+        let redis_timeout = Some(Duration::from_secs(config.redis_timeout_secs));
+        redis_cfg.pool = Some(deadpool_redis::PoolConfig {
+            max_size: config.redis_pool_size,
+            timeouts: deadpool_redis::Timeouts {
+                wait: redis_timeout,
+                create: redis_timeout,
+                recycle: redis_timeout,
+            },
+            ..Default::default()
+        });
+        let redis_pool = redis_cfg
+            .create_pool(Some(deadpool_redis::Runtime::Tokio1))
+            .map_err(|e| AppError::RedisError(format!("invalid Redis configuration: {}", e)))?;
+        // Havuz bağlantıları tembel açtığı için erişilebilirlik burada doğrulanır
+        let pool = &redis_pool;
+        with_retry("Redis", &config, || async move {
+            let mut conn = pool.get().await?;
+            deadpool_redis::redis::cmd("PING").query_async::<_, String>(&mut conn).await?;
+            Ok(())
+        })
+        .await?;
+
+        let sms_sender = sms_sender_from_config(&config)?;
+        let email_sender = email_sender_from_config(&config)?;
+        let password_policy = PasswordPolicy::from_config(&config);
+        let password_hashing = PasswordHashing::from_config(&config)?;
+        let geoip = Arc::new(GeoIpResolver::from_config(&config)?);
+        let security_notifier = security_notifier_from_config(&config, email_sender.clone())?;
+        let ai_provider = ai_provider_from_config(&config)?;
+        let metrics = Arc::new(Metrics::new()?);
+        let system_config = Arc::new(SystemConfigStore::new(RuntimeSettings::defaults(&config)));
 
         Ok(AppState {
             db: db_pool,
             redis_pool,
             jwt_secret: config.jwt_secret.clone(),
             config, // Simplified field assignment
+            sms_sender,
+            email_sender,
+            password_policy,
+            password_hashing,
+            geoip,
+            security_notifier,
+            ai_provider,
+            system_config,
+            feature_flags: Arc::new(FeatureFlagStore::default()),
+            shutdown: Arc::new(Shutdown::default()),
+            metrics,
         })
     }
+
+    /// Havuzdan komutları izlenen (span açan) bir Redis bağlantısı alır
+    pub async fn redis(&self) -> Result<TracedConnection, AppError> {
+        Ok(TracedConnection::new(self.redis_pool.get().await?))
+    }
 }
@@ -1,20 +1,728 @@
+use std::{fmt, net::IpAddr, path::PathBuf};
+
+use ::config::{Config, Environment, File, Map, Source, Value};
+use clap::Parser;
 use dotenvy::dotenv;
 use serde::Deserialize;
-use std::env;
 
+/// Yapılandırma katmanları (sonraki öncekini ezer):
+/// varsayılanlar → TOML dosyası → ortam değişkenleri → komut satırı argümanları
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppConfig {
+    // Zorunlu alanlar: eksik olanların hepsi `validate` tarafından tek seferde raporlanır
+    #[serde(default)]
     pub database_url: String,
+    #[serde(default)]
     pub jwt_secret: String,
+    #[serde(default)]
     pub redis_url: String,
+    #[serde(default = "default_host")]
     pub host: String,
+    #[serde(default = "default_port")]
     pub port: u16,
-    pub admin_registration_code: String
+    /// HTTP worker sayısı; boşsa CPU çekirdeği sayısı
+    pub workers: Option<usize>,
+    #[serde(default = "default_db_max_connections")]
+    pub db_max_connections: u32,
+    #[serde(default)]
+    pub db_min_connections: u32,
+    #[serde(default = "default_timeout_secs")]
+    pub db_acquire_timeout_secs: u64,
+    #[serde(default = "default_redis_pool_size")]
+    pub redis_pool_size: usize,
+    /// Havuzdan bağlantı bekleme / yeni bağlantı kurma zaman aşımı
+    #[serde(default = "default_timeout_secs")]
+    pub redis_timeout_secs: u64,
+    /// Açılışta bekleyen migration'ları uygula; kapalıysa migration'lar ayrı bir adımda çalıştırılmalıdır
+    #[serde(default = "default_true")]
+    pub run_migrations: bool,
+    /// Açılışta Postgres/Redis bağlantısı için deneme sayısı
+    #[serde(default = "default_startup_retries")]
+    pub startup_retries: u32,
+    /// İlk yeniden deneme beklemesi; her denemede iki katına çıkar
+    #[serde(default = "default_startup_backoff_ms")]
+    pub startup_backoff_ms: u64,
+    /// Kapanışta süren isteklerin ve arka plan işlerinin tamamlanması için beklenen süre
+    #[serde(default = "default_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64,
+    #[serde(default = "default_access_token_ttl_secs")]
+    pub access_token_ttl_secs: usize,
+    #[serde(default = "default_refresh_token_ttl_secs")]
+    pub refresh_token_ttl_secs: usize,
+    #[serde(default = "default_mfa_token_ttl_secs")]
+    pub mfa_token_ttl_secs: usize,
+    #[serde(default = "default_reset_token_ttl_secs")]
+    pub reset_token_ttl_secs: usize,
+    /// Açıkken kayıt yalnızca geçerli bir davet koduyla yapılabilir; çalışma anında
+    /// `registration_open` ayarıyla değiştirilebilir
+    #[serde(default)]
+    pub invite_only_registration: bool,
+    /// Hiç yönetici yokken ilk yöneticinin kaydı için kullanılacak tek seferlik davet kodu
+    pub bootstrap_admin_invite_code: Option<String>,
+    #[serde(default = "default_jwt_issuer")]
+    pub jwt_issuer: String,
+    #[serde(default = "default_jwt_audience")]
+    pub jwt_audience: String,
+    #[serde(default = "default_guest_token_allowance")]
+    pub guest_token_allowance: u32,
+    #[serde(default = "default_guest_max_per_ip_per_hour")]
+    pub guest_max_per_ip_per_hour: u32,
+    #[serde(default = "default_guest_ttl_hours")]
+    pub guest_ttl_hours: i64,
+    /// Çalışma anı ayarlarında `default_model` tanımlanmamışsa kullanılır
+    #[serde(default = "default_model")]
+    pub default_model: String,
+    #[serde(default = "default_impersonation_minutes")]
+    pub impersonation_minutes: u32,
+    /// "log" (geliştirme) veya "http"
+    #[serde(default = "default_sms_provider")]
+    pub sms_provider: String,
+    pub sms_gateway_url: Option<String>,
+    pub sms_gateway_token: Option<String>,
+    pub sms_sender_id: Option<String>,
+    /// Ülke kodu olmadan girilen numaralar için kullanılır (ör. "90")
+    #[serde(default = "default_phone_country_code")]
+    pub default_phone_country_code: String,
+    /// "log" (geliştirme) veya "http"
+    #[serde(default = "default_email_provider")]
+    pub email_provider: String,
+    pub email_api_url: Option<String>,
+    pub email_api_token: Option<String>,
+    #[serde(default = "default_email_from")]
+    pub email_from: String,
+    /// E-postalardaki bağlantıların üretildiği istemci adresi
+    #[serde(default = "default_public_base_url")]
+    pub public_base_url: String,
+    #[serde(default = "default_password_min_length")]
+    pub password_min_length: usize,
+    #[serde(default = "default_true")]
+    pub password_require_lowercase: bool,
+    #[serde(default = "default_true")]
+    pub password_require_uppercase: bool,
+    #[serde(default = "default_true")]
+    pub password_require_digit: bool,
+    #[serde(default)]
+    pub password_require_symbol: bool,
+    /// 0-4 arası zxcvbn tarzı güç skoru alt sınırı
+    #[serde(default = "default_password_min_score")]
+    pub password_min_score: u8,
+    /// HIBP indiricisinin ürettiği `<PREFIX>.txt` dosyalarının bulunduğu dizin
+    pub breached_passwords_dir: Option<String>,
+    #[serde(default = "default_argon2_memory_kib")]
+    pub argon2_memory_kib: u32,
+    #[serde(default = "default_argon2_iterations")]
+    pub argon2_iterations: u32,
+    #[serde(default = "default_argon2_parallelism")]
+    pub argon2_parallelism: u32,
+    /// Tüm parola hash'lerine eklenen sunucu tarafı gizli değer (Argon2 secret)
+    pub password_pepper: Option<String>,
+    /// Dışa aktarılan kişisel veri dosyalarının yazıldığı dizin
+    #[serde(default = "default_export_dir")]
+    pub export_dir: String,
+    #[serde(default = "default_export_ttl_hours")]
+    pub export_ttl_hours: i64,
+    /// Hesap silme talebinden sonra verilerin anonimleştirilmesine kadar geçen süre
+    #[serde(default = "default_deletion_grace_days")]
+    pub deletion_grace_days: i64,
+    /// MaxMind GeoLite2/GeoIP2 City (`.mmdb`) dosyası; yoksa konum tabanlı kontroller atlanır
+    pub geoip_db_path: Option<String>,
+    /// İki giriş arasında bu hızın (km/sa) üzerindeki yer değişimi "imkânsız seyahat" sayılır
+    #[serde(default = "default_impossible_travel_kmh")]
+    pub impossible_travel_kmh: f64,
+    /// "log" (geliştirme) veya "email"
+    #[serde(default = "default_security_notifier")]
+    pub security_notifier: String,
+    /// Şüpheli giriş e-postasındaki "bu ben değildim" bağlantısının geçerlilik süresi
+    #[serde(default = "default_login_alert_link_ttl_hours")]
+    pub login_alert_link_ttl_hours: u64,
+    /// Çerez modunda access token'ın yazıldığı HttpOnly çerez
+    #[serde(default = "default_auth_cookie_name")]
+    pub auth_cookie_name: String,
+    /// Çerez modunda CSRF token'ının yazıldığı (JavaScript'in okuyabildiği) çerez
+    #[serde(default = "default_csrf_cookie_name")]
+    pub csrf_cookie_name: String,
+    #[serde(default = "default_true")]
+    pub cookie_secure: bool,
+    /// "strict", "lax" veya "none"
+    #[serde(default = "default_cookie_same_site")]
+    pub cookie_same_site: String,
+    pub cookie_domain: Option<String>,
+    /// Virgülle ayrılmış izinli origin listesi; çerez modunda tarayıcının kimlik bilgisi
+    /// göndermesi için gereklidir. Boşsa tüm origin'lere (kimlik bilgisi olmadan) izin verilir
+    pub cors_allowed_origins: Option<String>,
+    /// "echo" (geliştirme; son mesajı geri döner) veya "openai" (OpenAI uyumlu chat completions API'si)
+    #[serde(default = "default_ai_provider")]
+    pub ai_provider: String,
+    /// OpenAI uyumlu API'nin kök adresi (ör. "https://api.openai.com/v1")
+    pub ai_api_url: Option<String>,
+    pub ai_api_token: Option<String>,
+    #[serde(default = "default_ai_request_timeout_secs")]
+    pub ai_request_timeout_secs: u64,
+    /// Virgülle ayrılmış ters proxy adresleri. Yalnızca bağlantı bu adreslerden geldiğinde
+    /// `X-Forwarded-For` okunur; boşsa istemci IP'si her zaman bağlantının kendisinden alınır
+    #[serde(default)]
+    pub trusted_proxies: String,
+    /// `/metrics` için Bearer token; verilirse allowlist dışındaki adresler de bu token ile erişebilir
+    pub metrics_bearer_token: Option<String>,
+    /// `/metrics`'e token olmadan erişebilecek, virgülle ayrılmış IP listesi
+    #[serde(default = "default_metrics_allowed_ips")]
+    pub metrics_allowed_ips: String,
+    /// Hazır olma kontrolünde yoklanan yapay zekâ sağlayıcıları: virgülle ayrılmış
+    /// `ad=url` çiftleri (ör. `openai=https://status.openai.com/api/v2/status.json`)
+    #[serde(default)]
+    pub ai_provider_health_urls: String,
+    /// Log satırlarının biçimi: "text" (okunabilir) veya "json" (satır başına bir JSON nesnesi)
+    #[serde(default = "default_log_format")]
+    pub log_format: String,
+    /// `system_log` tablosundaki uyarı/hata kayıtlarının saklanacağı gün sayısı
+    #[serde(default = "default_system_log_retention_days")]
+    pub system_log_retention_days: i64,
+    /// Trace exporter'ı: "none", "otlp", "stdout" veya "file"
+    #[serde(default = "default_otel_exporter")]
+    pub otel_exporter: String,
+    /// OTLP/HTTP toplayıcı adresi (ör. http://localhost:4318)
+    pub otel_endpoint: Option<String>,
+    /// "file" exporter'ının span'leri JSON satırları olarak yazdığı dosya
+    #[serde(default = "default_otel_file_path")]
+    pub otel_file_path: String,
+    #[serde(default = "default_otel_service_name")]
+    pub otel_service_name: String,
+    /// Kök span'lerin örneklenme oranı (0.0-1.0); üst span'i olanlar üstün kararına uyar
+    #[serde(default = "default_otel_sample_ratio")]
+    pub otel_sample_ratio: f64,
+}
+
+fn default_host() -> String {
+    "0.0.0.0".to_string()
+}
+
+fn default_port() -> u16 {
+    3000
+}
+
+fn default_db_max_connections() -> u32 {
+    5
+}
+
+fn default_redis_pool_size() -> usize {
+    16
+}
+
+fn default_timeout_secs() -> u64 {
+    5
+}
+
+fn default_startup_retries() -> u32 {
+    5
+}
+
+fn default_startup_backoff_ms() -> u64 {
+    500
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+    30
+}
+
+fn default_access_token_ttl_secs() -> usize {
+    24 * 3600
+}
+
+fn default_refresh_token_ttl_secs() -> usize {
+    30 * 24 * 3600
+}
+
+fn default_mfa_token_ttl_secs() -> usize {
+    5 * 60
+}
+
+fn default_reset_token_ttl_secs() -> usize {
+    15 * 60
+}
+
+fn default_jwt_issuer() -> String {
+    "ann-ai-backend".to_string()
+}
+
+fn default_jwt_audience() -> String {
+    "ann-ai-client".to_string()
+}
+
+fn default_guest_token_allowance() -> u32 {
+    20
+}
+
+fn default_guest_max_per_ip_per_hour() -> u32 {
+    5
+}
+
+fn default_guest_ttl_hours() -> i64 {
+    72
+}
+
+fn default_model() -> String {
+    "gemini-1.5-flash".to_string()
+}
+
+fn default_impersonation_minutes() -> u32 {
+    30
+}
+
+fn default_sms_provider() -> String {
+    "log".to_string()
+}
+
+fn default_phone_country_code() -> String {
+    "90".to_string()
+}
+
+fn default_email_provider() -> String {
+    "log".to_string()
+}
+
+fn default_email_from() -> String {
+    "no-reply@ann-ai.local".to_string()
+}
+
+fn default_public_base_url() -> String {
+    "http://localhost:3000".to_string()
+}
+
+fn default_password_min_length() -> usize {
+    8
+}
+
+fn default_password_min_score() -> u8 {
+    2
+}
+
+fn default_argon2_memory_kib() -> u32 {
+    argon2::Params::DEFAULT_M_COST
+}
+
+fn default_argon2_iterations() -> u32 {
+    argon2::Params::DEFAULT_T_COST
+}
+
+fn default_argon2_parallelism() -> u32 {
+    argon2::Params::DEFAULT_P_COST
+}
+
+fn default_export_dir() -> String {
+    "./exports".to_string()
+}
+
+fn default_export_ttl_hours() -> i64 {
+    48
+}
+
+fn default_deletion_grace_days() -> i64 {
+    30
+}
+
+fn default_impossible_travel_kmh() -> f64 {
+    900.0
+}
+
+fn default_security_notifier() -> String {
+    "email".to_string()
+}
+
+fn default_login_alert_link_ttl_hours() -> u64 {
+    72
+}
+
+fn default_auth_cookie_name() -> String {
+    "ann_ai_session".to_string()
+}
+
+fn default_csrf_cookie_name() -> String {
+    "ann_ai_csrf".to_string()
+}
+
+fn default_cookie_same_site() -> String {
+    "strict".to_string()
+}
+
+fn default_ai_provider() -> String {
+    "echo".to_string()
+}
+
+fn default_ai_request_timeout_secs() -> u64 {
+    120
+}
+
+fn default_metrics_allowed_ips() -> String {
+    "127.0.0.1,::1".to_string()
+}
+
+fn default_log_format() -> String {
+    "text".to_string()
+}
+
+fn default_system_log_retention_days() -> i64 {
+    30
+}
+
+fn default_otel_exporter() -> String {
+    "none".to_string()
+}
+
+fn default_otel_file_path() -> String {
+    "traces.jsonl".to_string()
+}
+
+fn default_otel_service_name() -> String {
+    "ann-ai-backend".to_string()
+}
+
+fn default_otel_sample_ratio() -> f64 {
+    1.0
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Komut satırı argümanları; verilenler diğer tüm katmanları ezer
+#[derive(Debug, Parser)]
+#[command(name = "ann-ai-backend", version, about)]
+pub struct CliArgs {
+    /// TOML yapılandırma dosyası
+    #[arg(short, long, env = "APP_CONFIG_FILE", default_value = "config.toml")]
+    pub config: PathBuf,
+    #[arg(long)]
+    pub host: Option<String>,
+    #[arg(long)]
+    pub port: Option<u16>,
+    #[arg(long)]
+    pub workers: Option<usize>,
+    #[arg(long)]
+    pub database_url: Option<String>,
+    #[arg(long)]
+    pub redis_url: Option<String>,
+    #[arg(long)]
+    pub db_max_connections: Option<u32>,
+    #[arg(long)]
+    pub redis_pool_size: Option<usize>,
+    /// Virgülle ayrılmış izinli CORS origin listesi
+    #[arg(long)]
+    pub cors_allowed_origins: Option<String>,
+    /// Açılışta migration çalıştırma (ör. migration'lar ayrı bir job ile uygulanıyorsa)
+    #[arg(long)]
+    pub skip_migrations: bool,
+}
+
+/// Başlangıçta bulunan tüm yapılandırma hataları
+#[derive(Debug)]
+pub struct ConfigError {
+    pub problems: Vec<String>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "invalid configuration ({} problem(s)):", self.problems.len())?;
+        for problem in &self.problems {
+            writeln!(f, "  - {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<::config::ConfigError> for ConfigError {
+    fn from(e: ::config::ConfigError) -> Self {
+        Self { problems: vec![e.to_string()] }
+    }
+}
+
+/// Hata mesajlarında alanın ortam değişkeni karşılığı gösterilir
+fn env_name(field: &str) -> String {
+    field.to_uppercase()
 }
 
 impl AppConfig {
-    pub fn from_env() -> Result<Self, envy::Error> {
+    pub fn load() -> Result<Self, ConfigError> {
         dotenv().ok();
-        envy::from_env()
+        Self::load_from(CliArgs::parse())
+    }
+
+    pub fn load_from(cli: CliArgs) -> Result<Self, ConfigError> {
+        let settings = Config::builder()
+            .add_source(File::from(cli.config.clone()).required(false))
+            .add_source(Environment::default().try_parsing(true))
+            .set_override_option("host", cli.host)?
+            .set_override_option("port", cli.port.map(i64::from))?
+            .set_override_option("workers", cli.workers.map(|w| w as u64))?
+            .set_override_option("database_url", cli.database_url)?
+            .set_override_option("redis_url", cli.redis_url)?
+            .set_override_option("db_max_connections", cli.db_max_connections.map(i64::from))?
+            .set_override_option("redis_pool_size", cli.redis_pool_size.map(|s| s as u64))?
+            .set_override_option("cors_allowed_origins", cli.cors_allowed_origins)?
+            .set_override_option("run_migrations", cli.skip_migrations.then_some(false))?
+            .build()?;
+
+        // Tür hatası veren her alan kaldırılıp yeniden denenir; böylece ilk hatada durmadan
+        // tüm hatalı alanlar toplanır
+        let mut values: Map<String, Value> = settings.collect()?;
+        let mut problems = Vec::new();
+        let config = loop {
+            match AppConfig::deserialize(Value::from(values.clone())) {
+                Ok(config) => break Some(config),
+                Err(::config::ConfigError::Type { key: Some(key), .. }) if values.contains_key(&key) => {
+                    let raw = values.remove(&key).map(|v| v.to_string()).unwrap_or_default();
+                    problems.push(format!("{}: invalid value {:?}", env_name(&key), raw));
+                }
+                Err(e) => {
+                    problems.push(e.to_string());
+                    break None;
+                }
+            }
+        };
+
+        if let Some(config) = &config {
+            problems.extend(config.validate());
+        }
+        match config {
+            Some(config) if problems.is_empty() => Ok(config),
+            _ => Err(ConfigError { problems }),
+        }
+    }
+
+    /// Değerlerin anlamsal doğrulaması; bulunan tüm sorunları döner
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut require = |ok: bool, message: String| {
+            if !ok {
+                problems.push(message);
+            }
+        };
+
+        for (field, value) in [
+            ("database_url", &self.database_url),
+            ("redis_url", &self.redis_url),
+            ("jwt_secret", &self.jwt_secret),
+        ] {
+            require(!value.trim().is_empty(), format!("{} is required", env_name(field)));
+        }
+        require(
+            self.jwt_secret.is_empty() || self.jwt_secret.len() >= 32,
+            "JWT_SECRET must be at least 32 characters".to_string(),
+        );
+        require(self.port != 0, "PORT must be between 1 and 65535".to_string());
+        require(self.workers != Some(0), "WORKERS must be greater than 0".to_string());
+        require(self.db_max_connections > 0, "DB_MAX_CONNECTIONS must be greater than 0".to_string());
+        require(
+            self.db_min_connections <= self.db_max_connections,
+            "DB_MIN_CONNECTIONS cannot exceed DB_MAX_CONNECTIONS".to_string(),
+        );
+        require(self.db_acquire_timeout_secs > 0, "DB_ACQUIRE_TIMEOUT_SECS must be greater than 0".to_string());
+        require(self.redis_pool_size > 0, "REDIS_POOL_SIZE must be greater than 0".to_string());
+        require(self.redis_timeout_secs > 0, "REDIS_TIMEOUT_SECS must be greater than 0".to_string());
+        require(self.startup_retries > 0, "STARTUP_RETRIES must be greater than 0".to_string());
+        for (field, ttl) in [
+            ("access_token_ttl_secs", self.access_token_ttl_secs),
+            ("refresh_token_ttl_secs", self.refresh_token_ttl_secs),
+            ("mfa_token_ttl_secs", self.mfa_token_ttl_secs),
+            ("reset_token_ttl_secs", self.reset_token_ttl_secs),
+        ] {
+            require(ttl > 0, format!("{} must be greater than 0", env_name(field)));
+        }
+        require(self.impersonation_minutes > 0, "IMPERSONATION_MINUTES must be greater than 0".to_string());
+
+        match self.email_provider.as_str() {
+            "log" => {}
+            "http" => require(self.email_api_url.is_some(), "EMAIL_API_URL is required when EMAIL_PROVIDER=http".to_string()),
+            other => require(false, format!("EMAIL_PROVIDER must be \"log\" or \"http\", got {:?}", other)),
+        }
+        match self.sms_provider.as_str() {
+            "log" => {}
+            "http" => require(self.sms_gateway_url.is_some(), "SMS_GATEWAY_URL is required when SMS_PROVIDER=http".to_string()),
+            other => require(false, format!("SMS_PROVIDER must be \"log\" or \"http\", got {:?}", other)),
+        }
+        match self.ai_provider.as_str() {
+            "echo" => {}
+            "openai" => require(self.ai_api_url.is_some(), "AI_API_URL is required when AI_PROVIDER=openai".to_string()),
+            other => require(false, format!("AI_PROVIDER must be \"echo\" or \"openai\", got {:?}", other)),
+        }
+        require(self.ai_request_timeout_secs > 0, "AI_REQUEST_TIMEOUT_SECS must be greater than 0".to_string());
+        require(
+            matches!(self.security_notifier.as_str(), "log" | "email"),
+            format!("SECURITY_NOTIFIER must be \"log\" or \"email\", got {:?}", self.security_notifier),
+        );
+
+        let same_site = self.cookie_same_site.to_ascii_lowercase();
+        require(
+            matches!(same_site.as_str(), "strict" | "lax" | "none"),
+            format!("COOKIE_SAME_SITE must be strict, lax or none, got {:?}", self.cookie_same_site),
+        );
+        require(
+            same_site != "none" || self.cookie_secure,
+            "COOKIE_SAME_SITE=none requires COOKIE_SECURE=true".to_string(),
+        );
+
+        require(self.password_min_length > 0, "PASSWORD_MIN_LENGTH must be greater than 0".to_string());
+        require(self.password_min_score <= 4, "PASSWORD_MIN_SCORE must be between 0 and 4".to_string());
+        if let Err(e) = argon2::Params::new(self.argon2_memory_kib, self.argon2_iterations, self.argon2_parallelism, None) {
+            require(false, format!("invalid Argon2 parameters: {}", e));
+        }
+
+        let is_http_url = |url: &str| url.starts_with("http://") || url.starts_with("https://");
+        require(
+            is_http_url(&self.public_base_url),
+            format!("PUBLIC_BASE_URL must be an http(s) URL, got {:?}", self.public_base_url),
+        );
+        for origin in self.cors_origins() {
+            require(is_http_url(&origin), format!("CORS_ALLOWED_ORIGINS contains an invalid origin {:?}", origin));
+        }
+        require(
+            matches!(self.log_format.as_str(), "text" | "json"),
+            format!("LOG_FORMAT must be text or json, got {:?}", self.log_format),
+        );
+        require(
+            self.system_log_retention_days > 0,
+            "SYSTEM_LOG_RETENTION_DAYS must be greater than 0".to_string(),
+        );
+        match self.otel_exporter.as_str() {
+            "none" | "stdout" | "file" => {}
+            "otlp" => require(self.otel_endpoint.is_some(), "OTEL_ENDPOINT is required when OTEL_EXPORTER=otlp".to_string()),
+            other => require(false, format!("OTEL_EXPORTER must be none, otlp, stdout or file, got {:?}", other)),
+        }
+        require(
+            (0.0..=1.0).contains(&self.otel_sample_ratio),
+            "OTEL_SAMPLE_RATIO must be between 0.0 and 1.0".to_string(),
+        );
+        for ip in self.trusted_proxies.split(',').map(str::trim).filter(|ip| !ip.is_empty()) {
+            require(ip.parse::<IpAddr>().is_ok(), format!("TRUSTED_PROXIES contains an invalid address {:?}", ip));
+        }
+        for ip in self.metrics_allowed_ips.split(',').map(str::trim).filter(|ip| !ip.is_empty()) {
+            require(ip.parse::<IpAddr>().is_ok(), format!("METRICS_ALLOWED_IPS contains an invalid address {:?}", ip));
+        }
+        for entry in self.ai_provider_health_urls.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let valid = entry
+                .split_once('=')
+                .is_some_and(|(name, url)| !name.trim().is_empty() && is_http_url(url.trim()));
+            require(valid, format!("AI_PROVIDER_HEALTH_URLS entries must be name=http(s)-url, got {:?}", entry));
+        }
+
+        problems
+    }
+
+    pub fn cors_origins(&self) -> Vec<String> {
+        self.cors_allowed_origins
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|o| o.trim().to_string())
+            .filter(|o| !o.is_empty())
+            .collect()
+    }
+
+    pub fn metrics_allowed_ips(&self) -> Vec<IpAddr> {
+        self.metrics_allowed_ips
+            .split(',')
+            .filter_map(|ip| ip.trim().parse().ok())
+            .collect()
+    }
+
+    /// `(sağlayıcı adı, sağlık URL'si)` çiftleri
+    pub fn ai_provider_health_urls(&self) -> Vec<(String, String)> {
+        self.ai_provider_health_urls
+            .split(',')
+            .filter_map(|entry| entry.split_once('='))
+            .map(|(name, url)| (name.trim().to_string(), url.trim().to_string()))
+            .filter(|(name, url)| !name.is_empty() && !url.is_empty())
+            .collect()
+    }
+
+    pub fn trusted_proxies(&self) -> Vec<IpAddr> {
+        self.trusted_proxies
+            .split(',')
+            .filter_map(|ip| ip.trim().parse().ok())
+            .collect()
+    }
+
+    pub fn bind_address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(overrides: serde_json::Value) -> AppConfig {
+        let mut values = json!({
+            "database_url": "postgres://localhost/ann_ai",
+            "redis_url": "redis://localhost",
+            "jwt_secret": "0123456789abcdef0123456789abcdef",
+        });
+        if let (Some(values), Some(overrides)) = (values.as_object_mut(), overrides.as_object()) {
+            values.extend(overrides.clone());
+        }
+        serde_json::from_value(values).expect("test config should deserialize")
+    }
+
+    #[test]
+    fn defaults_with_required_fields_are_valid() {
+        assert_eq!(config(json!({})).validate(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn reports_every_missing_required_field_at_once() {
+        let problems = config(json!({ "database_url": "", "redis_url": " ", "jwt_secret": "" })).validate();
+        assert!(problems.contains(&"DATABASE_URL is required".to_string()), "{:?}", problems);
+        assert!(problems.contains(&"REDIS_URL is required".to_string()), "{:?}", problems);
+        assert!(problems.contains(&"JWT_SECRET is required".to_string()), "{:?}", problems);
+    }
+
+    #[test]
+    fn rejects_short_jwt_secret_and_inverted_pool_bounds() {
+        let problems = config(json!({
+            "jwt_secret": "too-short",
+            "db_max_connections": 2,
+            "db_min_connections": 5,
+        }))
+        .validate();
+        assert!(problems.contains(&"JWT_SECRET must be at least 32 characters".to_string()), "{:?}", problems);
+        assert!(problems.contains(&"DB_MIN_CONNECTIONS cannot exceed DB_MAX_CONNECTIONS".to_string()), "{:?}", problems);
+    }
+
+    #[test]
+    fn provider_urls_are_required_for_http_providers() {
+        let problems = config(json!({ "email_provider": "http", "sms_provider": "carrier-pigeon" })).validate();
+        assert!(problems.contains(&"EMAIL_API_URL is required when EMAIL_PROVIDER=http".to_string()), "{:?}", problems);
+        assert!(problems.iter().any(|p| p.starts_with("SMS_PROVIDER must be")), "{:?}", problems);
+    }
+
+    #[test]
+    fn same_site_none_requires_secure_cookies() {
+        let problems = config(json!({ "cookie_same_site": "None", "cookie_secure": false })).validate();
+        assert!(problems.contains(&"COOKIE_SAME_SITE=none requires COOKIE_SECURE=true".to_string()), "{:?}", problems);
+        assert!(config(json!({ "cookie_same_site": "none", "cookie_secure": true })).validate().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_urls_and_addresses() {
+        let problems = config(json!({
+            "public_base_url": "ftp://example.com",
+            "cors_allowed_origins": "https://app.example.com, example.org",
+            "trusted_proxies": "10.0.0.1, not-an-ip",
+            "metrics_allowed_ips": "127.0.0.1,::1",
+        }))
+        .validate();
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.starts_with("PUBLIC_BASE_URL")), "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("\"example.org\"")), "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("\"not-an-ip\"")), "{:?}", problems);
+    }
+
+    #[test]
+    fn otlp_exporter_requires_an_endpoint_and_ratio_in_range() {
+        let problems = config(json!({ "otel_exporter": "otlp", "otel_sample_ratio": 1.5 })).validate();
+        assert!(problems.contains(&"OTEL_ENDPOINT is required when OTEL_EXPORTER=otlp".to_string()), "{:?}", problems);
+        assert!(problems.contains(&"OTEL_SAMPLE_RATIO must be between 0.0 and 1.0".to_string()), "{:?}", problems);
     }
 }
@@ -1,15 +1,16 @@
 use actix_web::{
     body::BoxBody,
-    http::{self, header::ToStrError, StatusCode},
+    http::{header::ToStrError, StatusCode},
     HttpResponse, ResponseError,
 };
 use jsonwebtoken::errors::ErrorKind;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use thiserror::Error;
+use validator::{ValidationErrors, ValidationErrorsKind};
 use tracing::{debug, error};
-use deadpool::managed::PoolError;
-use deadpool_redis::redis::RedisError;
+use deadpool_redis::{redis::RedisError, PoolError};
 
 // Define the JwtTokenError trait
 pub trait JwtTokenError {
@@ -19,21 +20,128 @@ pub trait JwtTokenError {
     fn from_jwt_error(err: jsonwebtoken::errors::Error) -> Self;
 }
 
+/// İstemcilerin mesaj metnine bakmadan ayırt edebileceği kararlı hata kodları.
+/// Kodlar yayımlandıktan sonra değiştirilmemeli; yeni durumlar için yeni kod eklenir.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    BadRequest,
+    MalformedRequest,
+    ValidationFailed,
+    Unauthorized,
+    InvalidCredentials,
+    TokenMissing,
+    TokenInvalid,
+    TokenExpired,
+    TokenRevoked,
+    SessionRevoked,
+    InvalidInvitation,
+    LinkInvalid,
+    LinkUsed,
+    InvalidVerificationCode,
+    VerificationCodeRecentlySent,
+    Forbidden,
+    AdminRequired,
+    ImpersonationForbidden,
+    NotOrganizationMember,
+    NotFound,
+    Conflict,
+    EmailTaken,
+    UsernameTaken,
+    AlreadyMember,
+    LastOwner,
+    SpendingCapReached,
+    InsufficientTokens,
+    ExportInProgress,
+    UnprocessableEntity,
+    RateLimited,
+    Internal,
+    Database,
+    Cache,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::BadRequest => "request.bad_request",
+            ErrorCode::MalformedRequest => "request.malformed",
+            ErrorCode::ValidationFailed => "validation.failed",
+            ErrorCode::Unauthorized => "auth.unauthorized",
+            ErrorCode::InvalidCredentials => "auth.invalid_credentials",
+            ErrorCode::TokenMissing => "auth.token_missing",
+            ErrorCode::TokenInvalid => "auth.token_invalid",
+            ErrorCode::TokenExpired => "auth.token_expired",
+            ErrorCode::TokenRevoked => "auth.token_revoked",
+            ErrorCode::SessionRevoked => "auth.session_revoked",
+            ErrorCode::InvalidInvitation => "auth.invalid_invitation",
+            ErrorCode::LinkInvalid => "auth.link_invalid",
+            ErrorCode::LinkUsed => "auth.link_used",
+            ErrorCode::InvalidVerificationCode => "auth.invalid_verification_code",
+            ErrorCode::VerificationCodeRecentlySent => "auth.verification_code_recently_sent",
+            ErrorCode::Forbidden => "auth.forbidden",
+            ErrorCode::AdminRequired => "auth.admin_required",
+            ErrorCode::ImpersonationForbidden => "auth.impersonation_forbidden",
+            ErrorCode::NotOrganizationMember => "org.not_member",
+            ErrorCode::NotFound => "resource.not_found",
+            ErrorCode::Conflict => "resource.conflict",
+            ErrorCode::EmailTaken => "user.email_taken",
+            ErrorCode::UsernameTaken => "user.username_taken",
+            ErrorCode::AlreadyMember => "org.already_member",
+            ErrorCode::LastOwner => "org.last_owner",
+            ErrorCode::SpendingCapReached => "org.spending_cap_reached",
+            ErrorCode::InsufficientTokens => "billing.insufficient_tokens",
+            ErrorCode::ExportInProgress => "user.export_in_progress",
+            ErrorCode::UnprocessableEntity => "request.unprocessable",
+            ErrorCode::RateLimited => "request.rate_limited",
+            ErrorCode::Internal => "internal.error",
+            ErrorCode::Database => "internal.database",
+            ErrorCode::Cache => "internal.cache",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Tek bir alan için doğrulama hatası
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FieldError {
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Kuralın parametreleri (ör. `min`, `max`); gönderilen değer geri yansıtılmaz
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty")]
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+
+/// RFC 7807 `application/problem+json` hata gövdesi
 #[derive(Debug, Serialize, Deserialize)]
-pub struct ErrorResponse {
-    pub status_code: u16,
-    pub error: String,
-    pub message: String,
-    //#[serde(skip_serializing_if = "Option::is_none")]
-    // pub details: Option<String>,
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    /// `ErrorCode` değeri; istemciler dallanmayı buna göre yapmalıdır
+    pub code: String,
+    /// Alan yolu ("email", "members[0].role") başına doğrulama hataları
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<BTreeMap<String, Vec<FieldError>>>,
+    /// Destek taleplerinde logları ve trace'leri eşleştirmek için
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
 }
 
-impl fmt::Display for ErrorResponse {
+impl fmt::Display for ProblemDetails {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "[{}] {}: {}", self.status_code, self.error, self.message)
+        write!(f, "[{}] {}: {}", self.status, self.code, self.detail)
     }
 }
 
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Bad Request: {0}")]
@@ -54,6 +162,9 @@ pub enum AppError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    #[error("Too Many Requests: {0}")]
+    TooManyRequests(String),
+
     #[error("Internal Server Error: {0}")]
     InternalServerError(String),
 
@@ -79,21 +190,29 @@ pub enum AppError {
     RedisCmdError(#[from] redis::RedisError),
 
     #[error(transparent)]
-    ToStrError(#[from] ToStrError)
+    ToStrError(#[from] ToStrError),
+
+    /// Alan bazında ayrıntıları yanıtta `errors` olarak döner
+    #[error("Validation Error: {0}")]
+    InvalidFields(#[from] ValidationErrors),
+
+    /// Başka bir hataya varsayılanı yerine belirli bir kod iliştirir; bkz. `AppError::with_code`
+    #[error("{inner}")]
+    Coded { code: ErrorCode, inner: Box<AppError> },
 }
 
 // Implement JwtTokenError for AppError
 impl JwtTokenError for AppError {
     fn token_invalid() -> Self {
-        Self::Unauthorized("Invalid authentication token".to_string())
+        Self::Unauthorized("Invalid authentication token".to_string()).with_code(ErrorCode::TokenInvalid)
     }
 
     fn token_expired() -> Self {
-        Self::Unauthorized("Authentication token expired".to_string())
+        Self::Unauthorized("Authentication token expired".to_string()).with_code(ErrorCode::TokenExpired)
     }
 
     fn token_missing() -> Self {
-        Self::Unauthorized("Authentication token is missing".to_string())
+        Self::Unauthorized("Authentication token is missing".to_string()).with_code(ErrorCode::TokenMissing)
     }
 
     fn from_jwt_error(err: jsonwebtoken::errors::Error) -> Self {
@@ -101,12 +220,18 @@ impl JwtTokenError for AppError {
             ErrorKind::ExpiredSignature => Self::token_expired(),
             ErrorKind::InvalidToken => Self::token_invalid(),
             ErrorKind::InvalidSignature => {
-                Self::Unauthorized("Invalid token signature".to_string())
+                Self::Unauthorized("Invalid token signature".to_string()).with_code(ErrorCode::TokenInvalid)
+            }
+            ErrorKind::InvalidIssuer => {
+                Self::Unauthorized("Invalid token issuer".to_string()).with_code(ErrorCode::TokenInvalid)
+            }
+            ErrorKind::InvalidAudience => {
+                Self::Unauthorized("Invalid token audience".to_string()).with_code(ErrorCode::TokenInvalid)
             }
-            ErrorKind::InvalidIssuer => Self::Unauthorized("Invalid token issuer".to_string()),
-            ErrorKind::InvalidAudience => Self::Unauthorized("Invalid token audience".to_string()),
-            ErrorKind::InvalidSubject => Self::Unauthorized("Invalid token subject".to_string()),
-            _ => Self::Unauthorized(format!("Token error: {}", err)),
+            ErrorKind::InvalidSubject => {
+                Self::Unauthorized("Invalid token subject".to_string()).with_code(ErrorCode::TokenInvalid)
+            }
+            _ => Self::Unauthorized(format!("Token error: {}", err)).with_code(ErrorCode::TokenInvalid),
         }
     }
 }
@@ -120,11 +245,10 @@ impl ResponseError for AppError {
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
             AppError::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
             AppError::Conflict(_) => StatusCode::CONFLICT,
-            AppError::ValidationError(_) => StatusCode::BAD_REQUEST,
-            AppError::SqlxError(e) => match e {
-                sqlx::Error::RowNotFound => StatusCode::NOT_FOUND,
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            },
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::ValidationError(_) | AppError::InvalidFields(_) => StatusCode::BAD_REQUEST,
+            AppError::Coded { inner, .. } => inner.status_code(),
+            AppError::SqlxError(sqlx::Error::RowNotFound) => StatusCode::NOT_FOUND,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -132,32 +256,113 @@ impl ResponseError for AppError {
     fn error_response(&self) -> HttpResponse<BoxBody> {
         let status = self.status_code();
 
-        // let details = match self {
-        //     AppError::SqlxError(e) => Some(e.to_string()),
-        //     AppError::JwtError(e) => Some(e.to_string()),
-        //     AppError::RedisError(e) => Some(e.to_string()),
-        //     AppError::RedisCmdError(e) => Some(e.to_string()),
-        //     _ => None,
-        // };
-
         if status.is_server_error() {
             error!(?self, status = status.as_u16(), "Server error occurred");
         } else {
             debug!(?self, status = status.as_u16(), "Client error occurred");
         }
 
-        let response = ErrorResponse {
-            status_code: status.as_u16(),
-            error: status.canonical_reason().unwrap_or("Unknown").to_string(),
-            message: self.to_string(),
-            //details: if cfg!(debug_assertions) { details } else { None },
+        let errors = self.field_errors().map(flatten_validation_errors);
+        let detail = match self.without_code() {
+            AppError::InvalidFields(_) => "One or more fields are invalid".to_string(),
+            other => other.to_string(),
+        };
+        let problem = ProblemDetails {
+            problem_type: "about:blank".to_string(),
+            title: status.canonical_reason().unwrap_or("Unknown").to_string(),
+            status: status.as_u16(),
+            detail,
+            code: self.code().to_string(),
+            errors,
+            trace_id: crate::utils::telemetry::current_trace_id(),
         };
 
-        HttpResponse::build(status).json(response)
+        HttpResponse::build(status)
+            .content_type(PROBLEM_JSON)
+            .json(problem)
     }
 }
 
+/// İç içe doğrulama hatalarını alan yoluna göre düzleştirir: "profile.name", "members[0].role"
+fn flatten_validation_errors(errors: &ValidationErrors) -> BTreeMap<String, Vec<FieldError>> {
+    fn walk(prefix: &str, errors: &ValidationErrors, out: &mut BTreeMap<String, Vec<FieldError>>) {
+        for (field, kind) in errors.errors() {
+            let path = if prefix.is_empty() { field.to_string() } else { format!("{}.{}", prefix, field) };
+            match kind {
+                ValidationErrorsKind::Field(field_errors) => {
+                    let entries = out.entry(path).or_default();
+                    entries.extend(field_errors.iter().map(|e| FieldError {
+                        code: e.code.to_string(),
+                        message: e.message.as_ref().map(|m| m.to_string()),
+                        params: e
+                            .params
+                            .iter()
+                            .filter(|(name, _)| *name != "value")
+                            .map(|(name, value)| (name.to_string(), value.clone()))
+                            .collect(),
+                    }));
+                }
+                ValidationErrorsKind::Struct(nested) => walk(&path, nested, out),
+                ValidationErrorsKind::List(items) => {
+                    for (index, nested) in items {
+                        walk(&format!("{}[{}]", path, index), nested, out);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    walk("", errors, &mut out);
+    out
+}
+
 impl AppError {
+    /// Hataya varsayılan kod yerine daha belirli bir kod iliştirir; durum kodu ve mesaj değişmez
+    pub fn with_code(self, code: ErrorCode) -> Self {
+        match self {
+            AppError::Coded { inner, .. } => AppError::Coded { code, inner },
+            other => AppError::Coded { code, inner: Box::new(other) },
+        }
+    }
+
+    /// İliştirilmiş kod, yoksa varyantın varsayılan kodu
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::Coded { code, .. } => *code,
+            AppError::BadRequest(_) => ErrorCode::BadRequest,
+            AppError::Unauthorized(_) => ErrorCode::Unauthorized,
+            AppError::Forbidden(_) => ErrorCode::Forbidden,
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::UnprocessableEntity(_) => ErrorCode::UnprocessableEntity,
+            AppError::Conflict(_) => ErrorCode::Conflict,
+            AppError::TooManyRequests(_) => ErrorCode::RateLimited,
+            AppError::ValidationError(_) | AppError::InvalidFields(_) => ErrorCode::ValidationFailed,
+            AppError::SqlxError(sqlx::Error::RowNotFound) => ErrorCode::NotFound,
+            AppError::SqlxError(_) | AppError::DbError(_) => ErrorCode::Database,
+            AppError::RedisError(_) | AppError::RedisCmdError(_) => ErrorCode::Cache,
+            AppError::InternalServerError(_)
+            | AppError::Argon2Error(_)
+            | AppError::JwtError(_)
+            | AppError::ToStrError(_) => ErrorCode::Internal,
+        }
+    }
+
+    /// `with_code` ile sarılmışsa asıl hata
+    pub fn without_code(&self) -> &AppError {
+        match self {
+            AppError::Coded { inner, .. } => inner.without_code(),
+            other => other,
+        }
+    }
+
+    fn field_errors(&self) -> Option<&ValidationErrors> {
+        match self.without_code() {
+            AppError::InvalidFields(errors) => Some(errors),
+            _ => None,
+        }
+    }
+
     pub fn not_found(resource: &str) -> Self {
         Self::NotFound(format!("Resource not found: {}", resource))
     }
@@ -191,9 +396,9 @@ impl AppError {
     }
 }
 
-// Implementation for the Redis pool error
-impl From<PoolError<RedisError>> for AppError {
-    fn from(err: PoolError<RedisError>) -> Self {
+// Implementation for the Redis pool error; deadpool-redis kendi deadpool sürümünün hata tipini döner
+impl From<PoolError> for AppError {
+    fn from(err: PoolError) -> Self {
         AppError::internal_error(&format!("Redis pool error: {}", err))
     }
 }
@@ -203,4 +408,82 @@ impl From<RedisError> for AppError {
     fn from(err: RedisError) -> Self {
         AppError::internal_error(&format!("Redis error: {}", err))
     }
-}
\ No newline at end of file
+}
+impl From<prometheus::Error> for AppError {
+    fn from(err: prometheus::Error) -> Self {
+        AppError::internal_error(&format!("Metrics error: {}", err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::Validate;
+
+    #[derive(Validate)]
+    struct Member {
+        #[validate(length(min = 1, message = "role is required"))]
+        role: String,
+    }
+
+    #[derive(Validate)]
+    struct Profile {
+        #[validate(length(min = 2, max = 5))]
+        name: String,
+    }
+
+    #[derive(Validate)]
+    struct Request {
+        #[validate(email, length(max = 10))]
+        email: String,
+        #[validate(nested)]
+        profile: Profile,
+        #[validate(nested)]
+        members: Vec<Member>,
+    }
+
+    fn flatten(request: Request) -> BTreeMap<String, Vec<FieldError>> {
+        flatten_validation_errors(&request.validate().expect_err("request should be invalid"))
+    }
+
+    fn valid_request() -> Request {
+        Request {
+            email: "a@b.co".to_string(),
+            profile: Profile { name: "Ann".to_string() },
+            members: vec![Member { role: "owner".to_string() }],
+        }
+    }
+
+    #[test]
+    fn collects_every_error_for_a_field() {
+        let errors = flatten(Request { email: "not-an-email-address".to_string(), ..valid_request() });
+
+        assert_eq!(errors.keys().collect::<Vec<_>>(), ["email"]);
+        let mut codes: Vec<&str> = errors["email"].iter().map(|e| e.code.as_str()).collect();
+        codes.sort();
+        assert_eq!(codes, ["email", "length"]);
+    }
+
+    #[test]
+    fn nested_structs_and_lists_use_dotted_and_indexed_paths() {
+        let errors = flatten(Request {
+            profile: Profile { name: "A".to_string() },
+            members: vec![Member { role: "owner".to_string() }, Member { role: String::new() }],
+            ..valid_request()
+        });
+
+        assert_eq!(errors.keys().collect::<Vec<_>>(), ["members[1].role", "profile.name"]);
+        assert_eq!(errors["members[1].role"][0].message.as_deref(), Some("role is required"));
+        assert_eq!(errors["profile.name"][0].code, "length");
+    }
+
+    #[test]
+    fn rule_params_are_kept_but_the_submitted_value_is_not() {
+        let errors = flatten(Request { profile: Profile { name: "secret-value".to_string() }, ..valid_request() });
+
+        let params = &errors["profile.name"][0].params;
+        assert_eq!(params.get("min"), Some(&serde_json::json!(2)));
+        assert_eq!(params.get("max"), Some(&serde_json::json!(5)));
+        assert!(!params.contains_key("value"));
+    }
+}
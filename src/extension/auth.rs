@@ -1,66 +1,201 @@
 use actix_web::{
-dev::{Service, ServiceRequest, ServiceResponse, Transform}, 
 web::Data, 
-Error, FromRequest, HttpMessage
+FromRequest, HttpMessage
 };
-use futures::future::{LocalBoxFuture, Ready};
-use std::{future::Future, rc::Rc};
-use tracing::{debug, error, info};
+use futures::future::LocalBoxFuture;
 use deadpool_redis::redis::AsyncCommands; // Redis komutları için
 
 use crate::{
-app_state::AppState, error::AppError, models::dto::auth::Claims, utils::{jwt, sha256::sha256_hash}
+app_state::AppState, error::{AppError, ErrorCode, JwtTokenError},
+models::{dto::auth::{Claims, TokenType}, impersonation::ImpersonationEventKind, organization::OrganizationRole, user::UserRole},
+repositories::{impersonation::{self, NewImpersonationEvent}, organization},
+services::auth_cache,
+utils::{auth_cookie::{self, TokenSource}, client_info::ClientInfo, jwt, metrics::AuthEvent, rate_limit, traced_redis::TracedConnection}
 };
+use uuid::Uuid;
+
+/// Token'daki `org` claim'inin doğrulanmış hali
+#[derive(Debug, Clone)]
+pub struct ActiveOrganization {
+    pub public_id: Uuid,
+    pub role: OrganizationRole,
+}
+
 /// Authenticated user details extracted from validated JWT
 #[derive(Debug, Clone)]
 pub struct AuthenticatedUser {
+    /// Dahili kullanıcı id'si; yalnızca sunucu içinde (veritabanı sorgularında) kullanılır
     pub user_id: i32,
+    /// Token'daki `sub`; istemciye dönen kimlik
+    pub public_id: Uuid,
+    /// Taklit oturumlarında yöneticinin dahili id'si
+    pub actor_user_id: Option<i32>,
     pub role: String,
     pub claims: Claims,
-    pub token: String
+    pub session_id: Option<Uuid>,
+    /// İsteği yapan istemcinin IP / User-Agent bilgisi; denetim kayıtlarında kullanılır
+    pub client: ClientInfo,
+    pub token_source: TokenSource,
+    pub organization: Option<ActiveOrganization>,
+}
+
+impl AuthenticatedUser {
+    pub fn is_admin(&self) -> bool {
+        self.role == UserRole::Admin.to_string()
+    }
+
+    pub fn require_admin(&self) -> Result<(), AppError> {
+        if self.is_admin() {
+            Ok(())
+        } else {
+            Err(AppError::forbidden("admin role required").with_code(ErrorCode::AdminRequired))
+        }
+    }
+
+    /// Token bir yönetici tarafından taklit amacıyla üretildiyse yöneticinin id'si
+    pub fn actor_id(&self) -> Option<i32> {
+        self.actor_user_id
+    }
+
+    pub fn is_impersonated(&self) -> bool {
+        self.claims.act.is_some()
+    }
+
+    /// Parola değişikliği, ödeme, veri silme gibi hassas işlemleri taklit oturumlarında engeller
+    pub fn deny_if_impersonated(&self, action: &str) -> Result<(), AppError> {
+        if self.is_impersonated() {
+            return Err(AppError::forbidden(&format!("{} is not allowed while impersonating", action))
+                .with_code(ErrorCode::ImpersonationForbidden));
+        }
+        Ok(())
+    }
 }
 
 impl FromRequest for AuthenticatedUser {
     type Error = AppError;
     type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
     fn from_request(req: &actix_web::HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
-        let token_res = req
-            .headers()
-            .get("Authorization")
-            .ok_or(AppError::BadRequest(String::from("Authorization header was not passed.")))
-            .and_then(|header_value| {
-                header_value
-                    .to_str()
-                    .map(|v| v[7..].to_string())
-                    .map_err(AppError::from)
-            });
+        // Aynı istekte birden fazla extractor kullanıcıyı isterse doğrulama (ve hız sınırı sayacı) bir kez çalışır
+        if let Some(user) = req.extensions().get::<AuthenticatedUser>().cloned() {
+            return Box::pin(async move { Ok(user) });
+        }
+        let http_req = req.clone();
         let state_res = req.app_data::<Data<AppState>>()
             .ok_or(AppError::InternalServerError("AppState is missing in app.".to_string()))
-            .map(|v| {
-                v.clone()
-            });
-        Box::pin(async {
-            let token = token_res?;
+            .cloned();
+        // Token başlıktan (Bearer) ya da çerez modunda oturum çerezinden okunur
+        let token_res = state_res
+            .as_ref()
+            .ok()
+            .map(|state| auth_cookie::token_from_request(req, &state.config));
+        let csrf_header = req
+            .headers()
+            .get(auth_cookie::CSRF_HEADER)
+            .and_then(|h| h.to_str().ok())
+            .map(str::to_string);
+        let http_method = req.method().clone();
+        let method = http_method.to_string();
+        let path = req.path().to_string();
+        let client = ClientInfo::from_request(req);
+        Box::pin(async move {
             let state = state_res?;
+            let (token, token_source) = token_res
+                .ok_or_else(|| AppError::InternalServerError("AppState is missing in app.".to_string()))??;
             let mut redis_con = match state.redis_pool.get().await {
-                Ok(conn) => conn,
+                Ok(conn) => TracedConnection::new(conn),
                 Err(e) => return Err(AppError::RedisError(e.to_string())),
             };
            
-            let claims = jwt::decode_jwt(&token, &state.jwt_secret)?;
-            let token_hash = sha256_hash(&token);
-            let blacklist_key = format!("blacklisted_jwt:{}", token_hash); // "token" yerine "jwt" kullan
+            let claims = jwt::decode_jwt(&token, TokenType::Access, &state.config)?;
+            // Tarayıcı çerezi otomatik eklediği için CSRF doğrulaması yalnızca çerezle gelen token'larda gerekir
+            if token_source == TokenSource::Cookie {
+                auth_cookie::verify_csrf(&http_method, csrf_header.as_deref(), &claims.jti, &state.jwt_secret)?;
+            }
+            let blacklist_key = jwt::revoked_jti_key(&claims.jti);
             let is_blacklisted: bool = redis_con.exists(blacklist_key).await?;
             if is_blacklisted {
-                return Err(AppError::Unauthorized("Token geçersiz kılındı (kara listede).".to_string()));
+                state.metrics.auth_event(AuthEvent::BlacklistedToken);
+                return Err(AppError::Unauthorized("Token geçersiz kılındı (kara listede).".to_string()).with_code(ErrorCode::TokenRevoked));
+            }
+
+            // Token'daki dış kimlik dahili id'ye çevrilir; silinmiş/pasif hesapların token'ları reddedilir
+            let user_id = auth_cache::internal_id(&mut redis_con, &state.db, claims.sub)
+                .await?
+                .ok_or_else(AppError::token_invalid)?;
+
+            // Rol başına dakikalık istek sınırı çalışma anı ayarlarından okunur; 0 sınırsızdır
+            let rate_limit = state.system_config.current().rate_limit_for_role(&claims.role);
+            if rate_limit > 0 {
+                rate_limit::enforce(
+                    &mut redis_con,
+                    &format!("api_rate:{}", user_id),
+                    rate_limit,
+                    60,
+                    "Too many requests, please slow down.",
+                )
+                .await?;
+            }
+
+            // Oturuma bağlı token'larda oturumun iptal edilmediğini kontrol et ve last_seen güncelle
+            // (doğrulanan oturum kısa süre önbellekten kabul edilir)
+            if let Some(session_id) = claims.sid {
+                if !auth_cache::touch_session(&mut redis_con, &state.db, session_id, user_id).await? {
+                    return Err(AppError::Unauthorized("Oturum sonlandırılmış.".to_string()).with_code(ErrorCode::SessionRevoked));
+                }
             }
-            
-            Ok(Self {
+
+            // Aktif organizasyondan çıkarılan kullanıcının token'ı o organizasyon adına kullanılamaz
+            let active_organization = match claims.org {
+                Some(org_public_id) => {
+                    let role = organization::find_active_membership(&state.db, org_public_id, user_id)
+                        .await?
+                        .ok_or_else(|| AppError::Unauthorized("No longer a member of the active organization".to_string())
+                            .with_code(ErrorCode::NotOrganizationMember))?;
+                    Some(ActiveOrganization {
+                        public_id: org_public_id,
+                        role,
+                    })
+                }
+                None => None,
+            };
+
+            // Taklit oturumunda yapılan her istek denetim kaydına yazılır
+            let mut actor_user_id = None;
+            if let Some(act) = &claims.act {
+                let actor_id = auth_cache::internal_id(&mut redis_con, &state.db, act.sub)
+                    .await?
+                    .ok_or_else(AppError::token_invalid)?;
+                actor_user_id = Some(actor_id);
+                impersonation::record(
+                    &state.db,
+                    &NewImpersonationEvent {
+                        token_jti: &claims.jti,
+                        actor_id,
+                        target_user_id: user_id,
+                        event: ImpersonationEventKind::Action,
+                        http_method: Some(&method),
+                        path: Some(&path),
+                        ip_address: client.ip_address.as_deref(),
+                    },
+                )
+                .await?;
+            }
+
+            let user = Self {
                 role: claims.role.clone(),
                 claims: claims.clone(),
-                user_id: claims.user_id,
-                token: token
-            })
+                user_id,
+                public_id: claims.sub,
+                actor_user_id,
+                session_id: claims.sid,
+                client,
+                token_source,
+                organization: active_organization,
+            };
+            // Kök istek span'ine yazılır; bu noktadan sonraki tüm log satırları kullanıcıyı taşır
+            tracing::Span::current().record("user_id", user_id);
+            http_req.extensions_mut().insert(user.clone());
+            Ok(user)
         })
     }
 }
@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use actix_web::{dev::Payload, web::Data, FromRequest, HttpRequest};
+use futures::future::LocalBoxFuture;
+use serde_json::Value;
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::feature_flag::{FeatureFlag, FlagContext},
+    services::feature_flag,
+    utils::auth_cookie,
+};
+
+/// İsteği yapan kullanıcı için değerlendirilmiş özellik bayrakları. Kimlik bilgisi
+/// gönderilmemişse anonim bağlamla değerlendirilir; gönderilmiş ama geçersizse istek reddedilir.
+pub struct FeatureFlags {
+    flags: Arc<Vec<FeatureFlag>>,
+    context: FlagContext,
+}
+
+impl FeatureFlags {
+    pub fn all(&self) -> BTreeMap<String, Value> {
+        feature_flag::evaluate_all(&self.flags, &self.context)
+    }
+}
+
+impl FromRequest for FeatureFlags {
+    type Error = AppError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let state = match req.app_data::<Data<AppState>>() {
+            Some(state) => state.clone(),
+            None => {
+                return Box::pin(async { Err(AppError::InternalServerError("AppState is missing in app.".to_string())) })
+            }
+        };
+        let auth = auth_cookie::token_from_request(req, &state.config)
+            .is_ok()
+            .then(|| AuthenticatedUser::from_request(req, payload));
+
+        Box::pin(async move {
+            let context = match auth {
+                Some(auth) => {
+                    let user = auth.await?;
+                    FlagContext {
+                        user_id: Some(user.public_id),
+                        role: Some(user.role),
+                        organization_id: user.organization.map(|o| o.public_id),
+                    }
+                }
+                None => FlagContext::default(),
+            };
+
+            Ok(Self {
+                flags: state.feature_flags.current(),
+                context,
+            })
+        })
+    }
+}
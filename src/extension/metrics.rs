@@ -0,0 +1,67 @@
+use std::time::Instant;
+
+use actix_web::{
+    body::MessageBody,
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    web::Data,
+    Error,
+};
+use futures::future::{ready, LocalBoxFuture, Ready};
+
+use crate::app_state::AppState;
+
+/// Her isteğin sayısını ve süresini eşleşen route kalıbı ve durum koduna göre kaydeder
+pub struct RequestMetrics;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestMetrics
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestMetricsMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestMetricsMiddleware { service }))
+    }
+}
+
+pub struct RequestMetricsMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestMetricsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let started = Instant::now();
+        let state = req.app_data::<Data<AppState>>().cloned();
+        let method = req.method().to_string();
+        // Eşleşmeyen yollar (404 taramaları) tek etikette toplanır
+        let route = req.match_pattern().unwrap_or_else(|| "unmatched".to_string());
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let result = fut.await;
+            if let Some(state) = state {
+                let status = match &result {
+                    Ok(res) => res.status().as_u16(),
+                    Err(e) => e.as_response_error().status_code().as_u16(),
+                };
+                state.metrics.observe_http(&method, &route, status, started.elapsed());
+            }
+            result
+        })
+    }
+}
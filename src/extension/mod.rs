@@ -1,2 +1,4 @@
 pub mod auth;
-pub mod logger;
\ No newline at end of file
+pub mod feature_flags;
+pub mod metrics;
+pub mod request_id;
@@ -0,0 +1,161 @@
+use std::time::Instant;
+
+use actix_web::{
+    body::MessageBody,
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{HeaderMap, HeaderName, HeaderValue},
+    Error, HttpMessage,
+};
+use futures::future::{ready, LocalBoxFuture, Ready};
+use opentelemetry::propagation::Extractor;
+use tracing::{field::Empty, info, Span};
+use tracing_actix_web::{DefaultRootSpanBuilder, RootSpanBuilder};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// İsteğin kimliği; gelen `X-Request-Id` geçerliyse o, değilse yeni bir UUID v7
+#[derive(Debug, Clone)]
+pub struct RequestId {
+    pub id: String,
+    pub started: Instant,
+}
+
+impl RequestId {
+    fn from_request(req: &ServiceRequest) -> Self {
+        let id = req
+            .headers()
+            .get(&REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| is_valid(value))
+            .map(str::to_string)
+            .unwrap_or_else(|| Uuid::now_v7().to_string());
+        Self { id, started: Instant::now() }
+    }
+}
+
+/// Log satırlarına taşındığı için yalnızca kısa, görünür ASCII kimlikler kabul edilir
+fn is_valid(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && id.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// İstek kimliğini belirler, istek uzantılarına koyar ve yanıtta `X-Request-Id` olarak döner.
+/// Kök span kimliği buradan okuduğu için `TracingLogger`'dan sonra (dışta) sarılmalıdır.
+pub struct RequestIdentifier;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestIdentifier
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestIdentifierMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdentifierMiddleware { service }))
+    }
+}
+
+pub struct RequestIdentifierMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdentifierMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let request_id = RequestId::from_request(&req);
+        let header = HeaderValue::from_str(&request_id.id).ok();
+        req.extensions_mut().insert(request_id);
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let mut res = fut.await?;
+            if let Some(header) = header {
+                res.headers_mut().insert(REQUEST_ID_HEADER, header);
+            }
+            Ok(res)
+        })
+    }
+}
+
+/// actix başlıklarını OpenTelemetry yayıcısına açar; `opentelemetry_http::HeaderExtractor`
+/// farklı bir `http` sürümünün `HeaderMap`'ini beklediği için kullanılamaz
+struct ActixHeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for ActixHeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(HeaderName::as_str).collect()
+    }
+}
+
+/// `TracingLogger` için kök span: her log satırı istek kimliği, route ve (kimlik doğrulandıktan
+/// sonra) kullanıcı id'sini taşır; istek bitince durum kodu ve süreyle tek bir satır yazılır.
+pub struct RequestSpan;
+
+impl RootSpanBuilder for RequestSpan {
+    fn on_request_start(request: &ServiceRequest) -> Span {
+        let request_id = request
+            .extensions()
+            .get::<RequestId>()
+            .map(|request_id| request_id.id.clone())
+            .unwrap_or_default();
+        let route = request.match_pattern().unwrap_or_else(|| "unmatched".to_string());
+        let span = tracing::info_span!(
+            "http_request",
+            otel.name = %format!("{} {}", request.method(), route),
+            otel.kind = "server",
+            otel.status_code = Empty,
+            http.method = %request.method(),
+            http.route = %route,
+            http.target = %request.uri(),
+            http.status_code = Empty,
+            request_id = %request_id,
+            user_id = Empty,
+            exception.message = Empty,
+        );
+        // Gelen `traceparent` başlığı üst span olarak kullanılır
+        let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.extract(&ActixHeaderExtractor(request.headers()))
+        });
+        span.set_parent(parent);
+        span
+    }
+
+    fn on_request_end<B: MessageBody>(span: Span, outcome: &Result<ServiceResponse<B>, Error>) {
+        DefaultRootSpanBuilder::on_request_end(span.clone(), outcome);
+
+        let _entered = span.enter();
+        match outcome {
+            Ok(res) => {
+                let latency_ms = res
+                    .request()
+                    .extensions()
+                    .get::<RequestId>()
+                    .map(|request_id| request_id.started.elapsed().as_secs_f64() * 1000.0)
+                    .unwrap_or_default();
+                info!(status = res.status().as_u16(), latency_ms, "request completed");
+            }
+            Err(e) => {
+                info!(status = e.as_response_error().status_code().as_u16(), "request failed");
+            }
+        }
+    }
+}
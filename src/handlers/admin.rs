@@ -0,0 +1,326 @@
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+use bigdecimal::ToPrimitive;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::dto::{
+        auth::CreateInvitationRequest, feature_flag::UpsertFeatureFlagRequest, organization::CreditOrganizationRequest,
+        system_config::UpdateSettingRequest,
+    },
+    repositories::{audit_event::AuditEventFilter, system_log::SystemLogFilter},
+    services::{
+        audit as audit_service, feature_flag as feature_flag_service, impersonation as impersonation_service,
+        invitation as invitation_service, organization as organization_service, system_config as system_config_service,
+        system_log as system_log_service,
+    },
+    utils::client_info::ClientInfo,
+};
+
+#[post("/impersonate/{user_id}")]
+pub async fn start_impersonation(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    http_req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let client = ClientInfo::from_request(&http_req);
+    let impersonation = impersonation_service::start_impersonation(&app_state, &auth_user, path.into_inner(), &client).await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "status": "success",
+        "message": "Impersonation started",
+        "data": {
+            "impersonation": impersonation
+        }
+    })))
+}
+
+#[post("/impersonate/stop")]
+pub async fn stop_impersonation(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    http_req: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let client = ClientInfo::from_request(&http_req);
+    impersonation_service::stop_impersonation(&app_state, &auth_user, &client).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[get("/impersonations/{jti}/events")]
+pub async fn impersonation_events(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let events = impersonation_service::list_events(&app_state, &auth_user, &path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "events": events
+        }
+    })))
+}
+
+#[post("/invitations")]
+pub async fn create_invitation(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    req: web::Json<CreateInvitationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let created = invitation_service::create_invitation(&app_state, &auth_user, req.into_inner()).await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "status": "success",
+        "message": "Invitation created",
+        "data": created
+    })))
+}
+
+#[get("/invitations")]
+pub async fn list_invitations(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    let invitations = invitation_service::list_invitations(&app_state, &auth_user).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "invitations": invitations
+        }
+    })))
+}
+
+#[delete("/invitations/{id}")]
+pub async fn revoke_invitation(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    invitation_service::revoke_invitation(&app_state, &auth_user, path.into_inner()).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditLogQuery {
+    pub actor: Option<Uuid>,
+    pub action: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    #[serde(default = "default_audit_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_audit_limit() -> i64 {
+    100
+}
+
+#[get("/audit")]
+pub async fn audit_events(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    query: web::Query<AuditLogQuery>,
+) -> Result<HttpResponse, AppError> {
+    let query = query.into_inner();
+    let filter = AuditEventFilter {
+        actor_public_id: query.actor,
+        action: query.action.filter(|a| !a.is_empty()),
+        from: query.from,
+        to: query.to,
+        limit: query.limit,
+        offset: query.offset,
+    };
+    let events = audit_service::list_events(&app_state, &auth_user, filter).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "events": events
+        }
+    })))
+}
+
+#[get("/audit/verify")]
+pub async fn verify_audit_chain(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    let verification = audit_service::verify_chain(&app_state, &auth_user).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": verification
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SystemLogQuery {
+    pub level: Option<String>,
+    pub target: Option<String>,
+    pub request_id: Option<String>,
+    pub user: Option<Uuid>,
+    /// Mesajda büyük/küçük harf duyarsız arama
+    pub q: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    #[serde(default = "default_audit_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[get("/logs")]
+pub async fn system_logs(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    query: web::Query<SystemLogQuery>,
+) -> Result<HttpResponse, AppError> {
+    let query = query.into_inner();
+    let filter = SystemLogFilter {
+        level: query.level.map(|l| l.to_ascii_lowercase()).filter(|l| !l.is_empty()),
+        target: query.target.filter(|t| !t.is_empty()),
+        request_id: query.request_id.filter(|r| !r.is_empty()),
+        user_public_id: query.user,
+        search: query.q.filter(|q| !q.is_empty()),
+        from: query.from,
+        to: query.to,
+        limit: query.limit,
+        offset: query.offset,
+    };
+    let logs = system_log_service::list_logs(&app_state, &auth_user, filter).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "logs": logs
+        }
+    })))
+}
+
+#[post("/organizations/{id}/tokens")]
+pub async fn credit_organization(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<Uuid>,
+    req: web::Json<CreditOrganizationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let balance = organization_service::credit_organization(&app_state, &auth_user, path.into_inner(), req.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Organization credited",
+        "data": {
+            "token_balance": balance.to_f64()
+        }
+    })))
+}
+
+#[get("/settings")]
+pub async fn list_settings(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    let settings = system_config_service::list_settings(&app_state, &auth_user).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": settings
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SettingsHistoryQuery {
+    pub key: Option<String>,
+    #[serde(default = "default_audit_limit")]
+    pub limit: i64,
+}
+
+#[get("/settings/history")]
+pub async fn settings_history(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    query: web::Query<SettingsHistoryQuery>,
+) -> Result<HttpResponse, AppError> {
+    let query = query.into_inner();
+    let changes = system_config_service::list_history(&app_state, &auth_user, query.key.as_deref(), query.limit).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "changes": changes
+        }
+    })))
+}
+
+#[put("/settings/{key}")]
+pub async fn update_setting(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<String>,
+    req: web::Json<UpdateSettingRequest>,
+) -> Result<HttpResponse, AppError> {
+    let settings = system_config_service::update_setting(&app_state, &auth_user, &path.into_inner(), req.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Setting updated",
+        "data": {
+            "settings": settings
+        }
+    })))
+}
+
+#[get("/flags")]
+pub async fn list_flags(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    let flags = feature_flag_service::list_flags(&app_state, &auth_user).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "flags": flags
+        }
+    })))
+}
+
+#[put("/flags/{key}")]
+pub async fn upsert_flag(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<String>,
+    req: web::Json<UpsertFeatureFlagRequest>,
+) -> Result<HttpResponse, AppError> {
+    let flag = feature_flag_service::upsert_flag(&app_state, &auth_user, &path.into_inner(), req.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Feature flag saved",
+        "data": {
+            "flag": flag
+        }
+    })))
+}
+
+#[delete("/flags/{key}")]
+pub async fn delete_flag(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    feature_flag_service::delete_flag(&app_state, &auth_user, &path.into_inner()).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -1,28 +1,25 @@
-use std::sync::Arc;
-
-use actix_web::{post, web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
 use actix_web::get;
-use deadpool_redis::redis::AsyncCommands;
 use serde_json::json;
 use crate::extension::auth::AuthenticatedUser;
-use crate::utils::get_token_from_header::get_token_from_header;
+use crate::utils::auth_cookie::{self, TokenSource};
+use crate::utils::client_info::ClientInfo;
 use crate::{
     app_state::AppState, 
     error::AppError, 
-    models::{
-        dto::auth::{AuthResponse, LoginRequest, RegisterRequest}, 
-        user::UserRole
-    }, 
-    services::auth as auth_service
+    models::dto::auth::{DisownLoginRequest, ForgotPasswordRequest, LoginRequest, MagicLinkConsumeRequest, MagicLinkRequest, RegisterRequest, ResetPasswordRequest}, 
+    services::{auth as auth_service, guest as guest_service, login_risk as login_risk_service, magic_link as magic_link_service, password as password_service}
 };
 
 
 #[post("/register")]
 pub async fn register(
     app_state: web::Data<AppState>,
+    http_req: HttpRequest,
     req: web::Json<RegisterRequest>,
 ) -> Result<HttpResponse, AppError> {
-    let user = auth_service::register_user(&app_state, req.into_inner()).await?;
+    let client = ClientInfo::from_request(&http_req);
+    let user = auth_service::register_user(&app_state, req.into_inner(), &client).await?;
 
     Ok(HttpResponse::Created().json(json!({
         "status": "success",
@@ -35,11 +32,50 @@ pub async fn register(
 #[post("/login")]
 pub async fn login(
     app_state: web::Data<AppState>,
+    http_req: HttpRequest,
     req: web::Json<LoginRequest>,
 ) -> Result<HttpResponse, AppError> {
-    let user = auth_service::login_user(&app_state, req.into_inner()).await?;
-    
-    Ok(HttpResponse::Ok().json(json!({
+    let client = ClientInfo::from_request(&http_req);
+    let mut user = auth_service::login_user(&app_state, req.into_inner(), &client).await?;
+    let mut response = HttpResponse::Ok();
+    auth_cookie::apply_session_cookies(&http_req, &app_state.config, &mut response, &mut user)?;
+
+    Ok(response.json(json!({
+        "status": "success",
+        "message": "Login successful",
+        "data": {
+            "user": user
+        }
+    })))
+}
+
+#[post("/magic-link")]
+pub async fn request_magic_link(
+    app_state: web::Data<AppState>,
+    http_req: HttpRequest,
+    req: web::Json<MagicLinkRequest>,
+) -> Result<HttpResponse, AppError> {
+    let client = ClientInfo::from_request(&http_req);
+    magic_link_service::request_magic_link(&app_state, req.into_inner(), &client).await?;
+
+    Ok(HttpResponse::Accepted().json(json!({
+        "status": "success",
+        "message": "If the email is registered, a sign-in link has been sent"
+    })))
+}
+
+#[post("/magic-link/consume")]
+pub async fn consume_magic_link(
+    app_state: web::Data<AppState>,
+    http_req: HttpRequest,
+    req: web::Json<MagicLinkConsumeRequest>,
+) -> Result<HttpResponse, AppError> {
+    let client = ClientInfo::from_request(&http_req);
+    let mut user = magic_link_service::consume_magic_link(&app_state, req.into_inner(), &client).await?;
+    let mut response = HttpResponse::Ok();
+    auth_cookie::apply_session_cookies(&http_req, &app_state.config, &mut response, &mut user)?;
+
+    Ok(response.json(json!({
         "status": "success",
         "message": "Login successful",
         "data": {
@@ -48,15 +84,106 @@ pub async fn login(
     })))
 }
 
+#[post("/password/forgot")]
+pub async fn forgot_password(
+    app_state: web::Data<AppState>,
+    http_req: HttpRequest,
+    req: web::Json<ForgotPasswordRequest>,
+) -> Result<HttpResponse, AppError> {
+    let client = ClientInfo::from_request(&http_req);
+    password_service::request_password_reset(&app_state, req.into_inner(), &client).await?;
+
+    Ok(HttpResponse::Accepted().json(json!({
+        "status": "success",
+        "message": "If the email is registered, a password reset link has been sent"
+    })))
+}
+
+#[post("/password/reset")]
+pub async fn reset_password(
+    app_state: web::Data<AppState>,
+    http_req: HttpRequest,
+    req: web::Json<ResetPasswordRequest>,
+) -> Result<HttpResponse, AppError> {
+    let client = ClientInfo::from_request(&http_req);
+    password_service::reset_password(&app_state, req.into_inner(), &client).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Password has been reset"
+    })))
+}
+
+/// Şüpheli giriş e-postasındaki bağlantı istemcide bir onay sayfası açar; kullanıcı onaylayınca
+/// istemci token'ı buraya gönderir
+#[post("/not-me")]
+pub async fn disown_login(
+    app_state: web::Data<AppState>,
+    http_req: HttpRequest,
+    req: web::Json<DisownLoginRequest>,
+) -> Result<HttpResponse, AppError> {
+    let client = ClientInfo::from_request(&http_req);
+    login_risk_service::disown_login(&app_state, req.into_inner(), &client).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "All sessions have been signed out and a password reset link has been sent"
+    })))
+}
+
+#[post("/guest")]
+pub async fn guest(
+    app_state: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let client = ClientInfo::from_request(&http_req);
+    let mut user = guest_service::create_guest(&app_state, &client).await?;
+    let mut response = HttpResponse::Created();
+    auth_cookie::apply_session_cookies(&http_req, &app_state.config, &mut response, &mut user)?;
+
+    Ok(response.json(json!({
+        "status": "success",
+        "message": "Guest session created",
+        "data": {
+            "user": user
+        }
+    })))
+}
+
+#[post("/guest/upgrade")]
+pub async fn upgrade_guest(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    http_req: HttpRequest,
+    req: web::Json<RegisterRequest>,
+) -> Result<HttpResponse, AppError> {
+    let client = ClientInfo::from_request(&http_req);
+    let mut user = guest_service::upgrade_guest(&app_state, &auth_user, req.into_inner(), &client).await?;
+    let mut response = HttpResponse::Ok();
+    auth_cookie::apply_session_cookies(&http_req, &app_state.config, &mut response, &mut user)?;
+
+    Ok(response.json(json!({
+        "status": "success",
+        "message": "Guest account upgraded successfully",
+        "data": {
+            "user": user
+        }
+    })))
+}
+
 #[post("/logout")]
 pub async fn logout(
     app_state: web::Data<AppState>,
     auth_user: AuthenticatedUser,
 ) -> Result<HttpResponse, AppError> {
 
-    auth_service::logout_user(&app_state, &auth_user.claims, &auth_user.token).await?;
-    
-    Ok(HttpResponse::NoContent().finish())
+    auth_service::logout_user(&app_state, &auth_user).await?;
+
+    let mut response = HttpResponse::NoContent();
+    if auth_user.token_source == TokenSource::Cookie {
+        auth_cookie::clear_session_cookies(&app_state.config, &mut response);
+    }
+    Ok(response.finish())
 }
 
 #[get("/test-auth")]
@@ -66,7 +193,7 @@ pub async fn test_auth(
     HttpResponse::Ok().json(json!({
         "status": "success",
         "message": "Authentication successful",
-        "user_id": auth_user.user_id,
+        "user_id": auth_user.public_id,
         "role": auth_user.role
     }))
 }
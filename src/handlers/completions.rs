@@ -0,0 +1,27 @@
+use actix_web::{post, web, HttpResponse};
+use serde_json::json;
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::dto::completion::CompletionRequest,
+    services::completion as completion_service,
+};
+
+/// Tamamlama ister; kullanılan token'lar aktif organizasyonun havuzundan ya da kişisel bakiyeden düşülür
+#[post("/completions")]
+pub async fn create_completion(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    req: web::Json<CompletionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let completion = completion_service::complete(&app_state, &auth_user, req.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "completion": completion
+        }
+    })))
+}
@@ -0,0 +1,15 @@
+use actix_web::{get, HttpResponse};
+use serde_json::json;
+
+use crate::{error::AppError, extension::feature_flags::FeatureFlags};
+
+/// Oturum açmış kullanıcılar için hedeflenmiş, anonim istekler için varsayılan değerler döner
+#[get("/flags")]
+pub async fn evaluated_flags(flags: FeatureFlags) -> Result<HttpResponse, AppError> {
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "flags": flags.all()
+        }
+    })))
+}
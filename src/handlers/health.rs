@@ -0,0 +1,20 @@
+use actix_web::{web, HttpResponse};
+use serde_json::json;
+
+use crate::{app_state::AppState, services::health as health_service};
+
+/// Süreç ayakta ve istek alabiliyor; bağımlılıklara bakılmaz, böylece veritabanı
+/// kesintisinde orkestratör konteyneri gereksiz yere yeniden başlatmaz
+pub async fn live() -> HttpResponse {
+    HttpResponse::Ok().json(json!({ "status": "ok" }))
+}
+
+/// Kritik bir bağımlılık çalışmıyorsa 503 döner; yük dengeleyici trafiği bu örnekten çeker
+pub async fn ready(app_state: web::Data<AppState>) -> HttpResponse {
+    let report = health_service::readiness(&app_state).await;
+    if report.is_ready() {
+        HttpResponse::Ok().json(report)
+    } else {
+        HttpResponse::ServiceUnavailable().json(report)
+    }
+}
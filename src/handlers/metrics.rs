@@ -0,0 +1,29 @@
+use std::net::IpAddr;
+
+use actix_web::{http::header, web, HttpRequest, HttpResponse};
+
+use crate::{app_state::AppState, error::AppError, utils::otp::constant_time_eq};
+
+/// Prometheus metin formatında metrikler. Bağlantının doğrudan geldiği adres allowlist'teyse
+/// ya da yapılandırılmış Bearer token gönderilmişse erişilebilir; proxy başlıklarına güvenilmez.
+pub async fn metrics(app_state: web::Data<AppState>, req: HttpRequest) -> Result<HttpResponse, AppError> {
+    let peer_allowed = req
+        .peer_addr()
+        .map(|addr| addr.ip())
+        .is_some_and(|ip: IpAddr| app_state.config.metrics_allowed_ips().contains(&ip.to_canonical()));
+    let token_allowed = app_state.config.metrics_bearer_token.as_deref().is_some_and(|expected| {
+        req.headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| constant_time_eq(token.as_bytes(), expected.as_bytes()))
+    });
+    if !peer_allowed && !token_allowed {
+        return Err(AppError::forbidden("metrics are not available from this address"));
+    }
+
+    let body = app_state.metrics.render(&app_state.db, &app_state.redis_pool)?;
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4; charset=utf-8")
+        .body(body))
+}
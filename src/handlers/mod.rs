@@ -1,10 +1,24 @@
-use actix_web::{web::{self, ServiceConfig}, HttpResponse};
+use actix_web::web::{self, ServiceConfig};
+
+use crate::error::{AppError, ErrorCode};
 
 // Modülleri içe aktaralım
+pub mod admin;
 pub mod auth;
+pub mod completions;
+pub mod flags;
+pub mod health;
+pub mod metrics;
+pub mod organizations;
+pub mod settings;
 pub mod users;  // users modülünü ekleyin
 
 pub fn configure(cfg: &mut ServiceConfig) {
+    // Çözümlenemeyen gövde/sorgu/yol parametreleri de problem+json olarak döner
+    cfg.app_data(web::JsonConfig::default().error_handler(|err, _| malformed_request(err.to_string())))
+        .app_data(web::QueryConfig::default().error_handler(|err, _| malformed_request(err.to_string())))
+        .app_data(web::PathConfig::default().error_handler(|err, _| malformed_request(err.to_string())));
+
     cfg.service(
         web::scope("/api")
             .service(
@@ -13,12 +27,84 @@ pub fn configure(cfg: &mut ServiceConfig) {
                     .service(auth::login)
                     .service(auth::register)
                     .service(auth::logout)
+                    .service(auth::guest)
+                    .service(auth::upgrade_guest)
+                    .service(auth::request_magic_link)
+                    .service(auth::consume_magic_link)
+                    .service(auth::forgot_password)
+                    .service(auth::reset_password)
+                    .service(auth::disown_login)
                     .service(auth::test_auth)
             )
+            .service(
+                web::scope("/users")
+                    .service(users::list_sessions)
+                    .service(users::revoke_session)
+                    .service(users::request_phone_verification)
+                    .service(users::confirm_phone_verification)
+                    .service(users::change_password)
+                    .service(users::request_export)
+                    .service(users::get_export)
+                    .service(users::download_export)
+                    .service(users::deletion_status)
+                    .service(users::request_deletion)
+                    .service(users::cancel_deletion)
+            )
+            .service(
+                web::scope("/admin")
+                    // "/impersonate/stop", "/impersonate/{user_id}" kalıbından önce kayıtlı olmalı
+                    .service(admin::stop_impersonation)
+                    .service(admin::start_impersonation)
+                    .service(admin::impersonation_events)
+                    .service(admin::create_invitation)
+                    .service(admin::list_invitations)
+                    .service(admin::revoke_invitation)
+                    .service(admin::verify_audit_chain)
+                    .service(admin::audit_events)
+                    .service(admin::system_logs)
+                    .service(admin::credit_organization)
+                    .service(admin::settings_history)
+                    .service(admin::list_settings)
+                    .service(admin::update_setting)
+                    .service(admin::list_flags)
+                    .service(admin::upsert_flag)
+                    .service(admin::delete_flag)
+            )
+            .service(settings::public_settings)
+            .service(flags::evaluated_flags)
+            .service(
+                web::scope("/organizations")
+                    // Sabit yollar "/{id}" kalıbından önce kayıtlı olmalı
+                    .service(organizations::switch_organization)
+                    .service(organizations::accept_invitation)
+                    .service(organizations::create_organization)
+                    .service(organizations::list_organizations)
+                    .service(organizations::get_organization)
+                    .service(organizations::list_members)
+                    .service(organizations::update_member)
+                    .service(organizations::remove_member)
+                    .service(organizations::invite_member)
+                    .service(organizations::list_invitations)
+            )
+            .service(completions::create_completion)
 
 
             
     );
 
+    // Konteyner probları; "/health" eski istemciler için canlılık kontrolüne yönlendirilir
+    cfg.service(
+        web::scope("/health")
+            .route("", web::get().to(health::live))
+            .route("/live", web::get().to(health::live))
+            .route("/ready", web::get().to(health::ready)),
+    );
+    cfg.route("/metrics", web::get().to(metrics::metrics));
+
+
+
+}
 
-}
\ No newline at end of file
+fn malformed_request(reason: String) -> actix_web::Error {
+    AppError::BadRequest(reason).with_code(ErrorCode::MalformedRequest).into()
+}
@@ -0,0 +1,182 @@
+use actix_web::{delete, get, patch, post, web, HttpRequest, HttpResponse};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::dto::organization::{
+        AcceptOrganizationInvitationRequest, CreateOrganizationRequest, InviteMemberRequest, SwitchOrganizationRequest,
+        UpdateMemberRequest,
+    },
+    services::organization as organization_service,
+    utils::auth_cookie,
+};
+
+#[post("")]
+pub async fn create_organization(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    req: web::Json<CreateOrganizationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let organization = organization_service::create_organization(&app_state, &auth_user, req.into_inner()).await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "status": "success",
+        "message": "Organization created",
+        "data": {
+            "organization": organization
+        }
+    })))
+}
+
+#[get("")]
+pub async fn list_organizations(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    let organizations = organization_service::list_organizations(&app_state, &auth_user).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "organizations": organizations,
+            "active_organization_id": auth_user.organization.as_ref().map(|o| o.public_id),
+            "active_organization_role": auth_user.organization.as_ref().map(|o| o.role)
+        }
+    })))
+}
+
+#[post("/switch")]
+pub async fn switch_organization(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    http_req: HttpRequest,
+    req: web::Json<SwitchOrganizationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut user = organization_service::switch_organization(&app_state, &auth_user, req.into_inner()).await?;
+    let mut response = HttpResponse::Ok();
+    auth_cookie::apply_session_cookies(&http_req, &app_state.config, &mut response, &mut user)?;
+
+    Ok(response.json(json!({
+        "status": "success",
+        "message": "Active organization changed",
+        "data": {
+            "user": user
+        }
+    })))
+}
+
+#[post("/invitations/accept")]
+pub async fn accept_invitation(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    req: web::Json<AcceptOrganizationInvitationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let membership = organization_service::accept_invitation(&app_state, &auth_user, req.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Joined organization",
+        "data": {
+            "organization": membership
+        }
+    })))
+}
+
+#[get("/{id}")]
+pub async fn get_organization(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let organization = organization_service::get_organization(&app_state, &auth_user, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "organization": organization
+        }
+    })))
+}
+
+#[get("/{id}/members")]
+pub async fn list_members(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let members = organization_service::list_members(&app_state, &auth_user, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "members": members
+        }
+    })))
+}
+
+#[patch("/{id}/members/{user_id}")]
+pub async fn update_member(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<(Uuid, Uuid)>,
+    req: web::Json<UpdateMemberRequest>,
+) -> Result<HttpResponse, AppError> {
+    let (organization_id, user_id) = path.into_inner();
+    let member = organization_service::update_member(&app_state, &auth_user, organization_id, user_id, req.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "member": member
+        }
+    })))
+}
+
+#[delete("/{id}/members/{user_id}")]
+pub async fn remove_member(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, AppError> {
+    let (organization_id, user_id) = path.into_inner();
+    organization_service::remove_member(&app_state, &auth_user, organization_id, user_id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[post("/{id}/invitations")]
+pub async fn invite_member(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<Uuid>,
+    req: web::Json<InviteMemberRequest>,
+) -> Result<HttpResponse, AppError> {
+    let invitation = organization_service::invite_member(&app_state, &auth_user, path.into_inner(), req.into_inner()).await?;
+
+    Ok(HttpResponse::Created().json(json!({
+        "status": "success",
+        "message": "Invitation sent",
+        "data": {
+            "invitation": invitation
+        }
+    })))
+}
+
+#[get("/{id}/invitations")]
+pub async fn list_invitations(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let invitations = organization_service::list_invitations(&app_state, &auth_user, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "invitations": invitations
+        }
+    })))
+}
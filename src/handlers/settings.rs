@@ -0,0 +1,15 @@
+use actix_web::{get, web, HttpResponse};
+use serde_json::json;
+
+use crate::{app_state::AppState, error::AppError, services::system_config as system_config_service};
+
+/// Kimlik doğrulaması gerektirmez; istemciler açılışta kayıt durumunu ve bakım duyurusunu buradan okur
+#[get("/settings")]
+pub async fn public_settings(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let settings = system_config_service::public_settings(&app_state);
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": settings
+    })))
+}
@@ -0,0 +1,185 @@
+use actix_web::{delete, get, http::header, post, web, HttpResponse};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::dto::user::{ChangePasswordRequest, DeleteAccountRequest, PhoneVerificationConfirm, PhoneVerificationRequest},
+    services::{gdpr as gdpr_service, password as password_service, phone as phone_service, session as session_service},
+};
+
+#[get("/me/sessions")]
+pub async fn list_sessions(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    let sessions = session_service::list_sessions(&app_state, &auth_user).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "sessions": sessions
+        }
+    })))
+}
+
+#[delete("/me/sessions/{id}")]
+pub async fn revoke_session(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    session_service::revoke_session(&app_state, &auth_user, path.into_inner()).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[post("/me/phone/verify")]
+pub async fn request_phone_verification(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    req: web::Json<PhoneVerificationRequest>,
+) -> Result<HttpResponse, AppError> {
+    let masked = phone_service::request_verification(&app_state, &auth_user, req.into_inner()).await?;
+
+    Ok(HttpResponse::Accepted().json(json!({
+        "status": "success",
+        "message": "Verification code sent",
+        "data": {
+            "phone_number": masked
+        }
+    })))
+}
+
+#[post("/me/phone/verify/confirm")]
+pub async fn confirm_phone_verification(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    req: web::Json<PhoneVerificationConfirm>,
+) -> Result<HttpResponse, AppError> {
+    let masked = phone_service::confirm_verification(&app_state, &auth_user, req.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Phone number verified",
+        "data": {
+            "phone_number": masked,
+            "phone_verified": true
+        }
+    })))
+}
+
+#[post("/me/password")]
+pub async fn change_password(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    req: web::Json<ChangePasswordRequest>,
+) -> Result<HttpResponse, AppError> {
+    password_service::change_password(&app_state, &auth_user, req.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Password changed successfully"
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SignedDownloadQuery {
+    pub expires: i64,
+    pub sig: String,
+}
+
+#[post("/me/export")]
+pub async fn request_export(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    let export = gdpr_service::request_export(&app_state, &auth_user).await?;
+
+    Ok(HttpResponse::Accepted().json(json!({
+        "status": "success",
+        "message": "Data export is being prepared",
+        "data": {
+            "export": export
+        }
+    })))
+}
+
+#[get("/me/export/{id}")]
+pub async fn get_export(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let export = gdpr_service::get_export(&app_state, &auth_user, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "export": export
+        }
+    })))
+}
+
+/// İmzalı bağlantıyla erişilir; Authorization başlığı gerektirmez
+#[get("/exports/{id}/download")]
+pub async fn download_export(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<SignedDownloadQuery>,
+) -> Result<HttpResponse, AppError> {
+    let (export, content) = gdpr_service::download_export(&app_state, path.into_inner(), query.expires, &query.sig).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"ann-ai-export-{}.json\"", export.id),
+        ))
+        .body(content))
+}
+
+#[get("/me/deletion")]
+pub async fn deletion_status(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    let scheduled_for = gdpr_service::deletion_status(&app_state, &auth_user).await?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "scheduled_for": scheduled_for
+        }
+    })))
+}
+
+#[post("/me/deletion")]
+pub async fn request_deletion(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+    req: web::Json<DeleteAccountRequest>,
+) -> Result<HttpResponse, AppError> {
+    let scheduled_for = gdpr_service::request_deletion(&app_state, &auth_user, req.into_inner()).await?;
+
+    Ok(HttpResponse::Accepted().json(json!({
+        "status": "success",
+        "message": "Account deletion scheduled",
+        "data": {
+            "scheduled_for": scheduled_for
+        }
+    })))
+}
+
+#[delete("/me/deletion")]
+pub async fn cancel_deletion(
+    app_state: web::Data<AppState>,
+    auth_user: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    gdpr_service::cancel_deletion(&app_state, &auth_user).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -10,9 +10,12 @@ mod utils;
 mod extension;
 mod result;
 
-use actix_web::{middleware, web::{self, Data}, App, HttpResponse, HttpServer};
+use std::time::Duration;
+
+use actix_web::{dev::ServerHandle, web::Data, App, HttpServer};
 use actix_cors::Cors;
-use tracing::{info, error};
+use tracing::{error, info, warn};
+use tracing_actix_web::TracingLogger;
 
 use crate::app_state::AppState;
 
@@ -26,47 +29,105 @@ async fn main() -> std::io::Result<()> {
 }
 
 async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
-    // Loglama sistemini yapılandır
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .with_target(true)
-        .init();
-    
-    info!("Uygulama başlatılıyor...");
-
-    let config = config::AppConfig::from_env()?;
-    info!("Yapılandırma yüklendi");
+    // Exporter seçimi yapılandırmadan geldiği için loglama yapılandırma yüklendikten sonra kurulur
+    let config = config::AppConfig::load()?;
+    let system_log_receiver = utils::telemetry::init(&config)?;
+    info!("Uygulama başlatılıyor... Yapılandırma yüklendi");
     
-    println!("AppState oluşturuluyor... Veritabanı bağlantısı kuruluyor...");
+    info!("AppState oluşturuluyor... Veritabanı bağlantısı kuruluyor...");
     let app_state = Data::new(AppState::new(config).await?);
     info!("AppState başarıyla oluşturuldu");
+
+    services::system_log::spawn_writer(app_state.clone(), system_log_receiver);
+    services::config_sync::reload_all(&app_state).await?;
+    services::config_sync::spawn_listener(app_state.clone());
+    services::invitation::ensure_bootstrap_invitation(&app_state).await?;
+    services::guest::spawn_guest_cleanup(app_state.clone());
+    services::gdpr::spawn_gdpr_worker(app_state.clone());
     
-    let bind_address = format!("{}:{}", "0.0.0.0", 3000);
+    let bind_address = app_state.config.bind_address();
+    let workers = app_state.config.workers.unwrap_or_else(num_cpus::get);
     info!("Sunucu {} adresinde başlatılıyor", bind_address);
 
-    HttpServer::new(move || {
-        let cors = Cors::default()
-            .allow_any_origin()
-            .allow_any_method()
-            .allow_any_header()
-            .max_age(3600);
-            
+    let cors_origins = app_state.config.cors_origins();
+    let shutdown_timeout = app_state.config.shutdown_timeout_secs;
+    let server_state = app_state.clone();
+
+    let server = HttpServer::new(move || {
+        // Çerez modunda tarayıcının kimlik bilgisi göndermesi için origin'ler açıkça listelenmelidir
+        let cors = if cors_origins.is_empty() {
+            Cors::default().allow_any_origin()
+        } else {
+            cors_origins
+                .iter()
+                .fold(Cors::default(), |cors, origin| cors.allowed_origin(origin))
+                .supports_credentials()
+        }
+        .allow_any_method()
+        .allow_any_header()
+        .max_age(3600);
+
+
         App::new()
-            .app_data(app_state.clone())
-            .wrap(middleware::Logger::default())
+            .app_data(server_state.clone())
             .wrap(cors)
+            .wrap(extension::metrics::RequestMetrics)
+            // İstek başına span; istek kimliği, route, kullanıcı ve süre her log satırına eklenir
+            .wrap(TracingLogger::<extension::request_id::RequestSpan>::new())
+            // Kök span kimliği okuyabilsin diye en dışta
+            .wrap(extension::request_id::RequestIdentifier)
             // handlers/mod.rs'deki configure fonksiyonunu kullan
             .configure(handlers::configure)
-            // Sağlık kontrolü ana dosyada olsun
-            .route("/health", web::get().to(|| async { 
-                HttpResponse::Ok().body("OK") 
-            }))
     })
-    .workers(num_cpus::get())
+    .workers(workers)
+    .shutdown_timeout(shutdown_timeout)
+    // Sinyaller aşağıda ele alınır; böylece akışlar ve arka plan işleri sunucudan önce haberdar edilir
+    .disable_signals()
     .bind(&bind_address)?
-    .run()
-    .await?;
+    .run();
 
-    info!("Sunucu kapatılıyor");
+    actix_web::rt::spawn(stop_on_signal(app_state.clone(), server.handle()));
+    server.await?;
+
+    info!("Sunucu durdu, arka plan işleri bekleniyor");
+    if !app_state.shutdown.shutdown(Duration::from_secs(shutdown_timeout)).await {
+        warn!("Arka plan işleri {} saniye içinde bitmedi", shutdown_timeout);
+    }
+    app_state.redis_pool.close();
+    app_state.db.close().await;
+    utils::telemetry::shutdown();
+    info!("Sunucu kapatıldı");
     Ok(())
+}
+
+/// SIGTERM/SIGINT geldiğinde önce kapanışı yayınlar (hazır olma kontrolü 503 döner, açık akışlar
+/// kapanır), ardından yeni bağlantıları reddedip süren isteklerin bitmesini bekler
+async fn stop_on_signal(app_state: Data<AppState>, handle: ServerHandle) {
+    wait_for_signal().await;
+    info!("Kapanış sinyali alındı");
+    app_state.shutdown.trigger();
+    handle.stop(true).await;
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            tokio::select! {
+                _ = terminate.recv() => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+        }
+        Err(e) => {
+            error!("SIGTERM dinlenemiyor: {}", e);
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() {
+    let _ = tokio::signal::ctrl_c().await;
 }
\ No newline at end of file
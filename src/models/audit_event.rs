@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Denetim kaydına yazılan güvenlik olayları
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Register,
+    LoginSuccess,
+    LoginFailure,
+    SuspiciousLogin,
+    LoginDisowned,
+    MagicLinkLogin,
+    Logout,
+    PasswordChange,
+    PasswordReset,
+    RoleChange,
+    SessionRevoke,
+    PhoneVerified,
+    AccountDeletionRequested,
+    AccountDeletionCancelled,
+    ImpersonationStart,
+    ImpersonationStop,
+    InvitationCreate,
+    InvitationRevoke,
+    OrganizationCreate,
+    OrganizationInvite,
+    OrganizationJoin,
+    OrganizationMemberUpdate,
+    OrganizationMemberRemove,
+    OrganizationCredit,
+    SettingsUpdate,
+    FeatureFlagUpdate,
+    FeatureFlagDelete,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Register => "auth.register",
+            AuditAction::LoginSuccess => "auth.login.success",
+            AuditAction::LoginFailure => "auth.login.failure",
+            AuditAction::SuspiciousLogin => "auth.login.suspicious",
+            AuditAction::LoginDisowned => "auth.login.disowned",
+            AuditAction::MagicLinkLogin => "auth.login.magic_link",
+            AuditAction::Logout => "auth.logout",
+            AuditAction::PasswordChange => "auth.password.change",
+            AuditAction::PasswordReset => "auth.password.reset",
+            AuditAction::RoleChange => "user.role.change",
+            AuditAction::SessionRevoke => "user.session.revoke",
+            AuditAction::PhoneVerified => "user.phone.verified",
+            AuditAction::AccountDeletionRequested => "user.deletion.requested",
+            AuditAction::AccountDeletionCancelled => "user.deletion.cancelled",
+            AuditAction::ImpersonationStart => "admin.impersonation.start",
+            AuditAction::ImpersonationStop => "admin.impersonation.stop",
+            AuditAction::InvitationCreate => "admin.invitation.create",
+            AuditAction::InvitationRevoke => "admin.invitation.revoke",
+            AuditAction::OrganizationCreate => "org.create",
+            AuditAction::OrganizationInvite => "org.member.invite",
+            AuditAction::OrganizationJoin => "org.member.join",
+            AuditAction::OrganizationMemberUpdate => "org.member.update",
+            AuditAction::OrganizationMemberRemove => "org.member.remove",
+            AuditAction::OrganizationCredit => "admin.org.credit",
+            AuditAction::SettingsUpdate => "admin.settings.update",
+            AuditAction::FeatureFlagUpdate => "admin.flag.update",
+            AuditAction::FeatureFlagDelete => "admin.flag.delete",
+        }
+    }
+}
+
+/// `audit_event` tablosundaki bir satır; her kayıt bir öncekinin hash'ini içerir
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditEvent {
+    pub id: i64,
+    pub occurred_at: DateTime<Utc>,
+    #[serde(skip_serializing)]
+    pub actor_id: Option<i32>,
+    #[serde(skip_serializing)]
+    pub target_user_id: Option<i32>,
+    pub actor_public_id: Option<Uuid>,
+    pub target_public_id: Option<Uuid>,
+    pub action: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub diff: serde_json::Value,
+    pub prev_hash: String,
+    pub hash: String,
+}
@@ -1,7 +1,6 @@
-use futures::Stream;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use validator::{Validate, ValidationError};
+use validator::Validate;
 #[derive(Debug, Deserialize, Serialize, Validate)]
 
 pub struct RegisterRequest {
@@ -13,10 +12,11 @@ pub struct RegisterRequest {
     pub email: String,
     #[validate(length(min = 8))]
     pub password: String,
-    #[validate(length(equal = 10))]
+    #[validate(custom(function = "crate::utils::validation::validate_phone_number"))]
     pub phone_number: Option<String>,
+    /// Yönetici tarafından üretilen davet kodu; davet-zorunlu modda gereklidir
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub admin_code: Option<String>, 
+    pub invite_code: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -27,31 +27,193 @@ pub struct LoginRequest {
     pub password: String,
 }
 
+#[derive(Debug, Deserialize, Validate)]
+pub struct MagicLinkRequest {
+    #[validate(email)]
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MagicLinkConsumeRequest {
+    #[validate(length(equal = 64))]
+    pub token: String,
+}
+
+/// Şüpheli giriş uyarısındaki "bu ben değildim" bağlantısının token'ı
+#[derive(Debug, Deserialize, Validate)]
+pub struct DisownLoginRequest {
+    #[validate(length(equal = 64))]
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ForgotPasswordRequest {
+    #[validate(email)]
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ResetPasswordRequest {
+    #[validate(length(min = 1))]
+    pub token: String,
+    #[validate(length(min = 8))]
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateInvitationRequest {
+    #[serde(default = "default_invitation_role")]
+    pub role: crate::models::user::UserRole,
+    #[validate(range(min = 1, max = 10000))]
+    #[serde(default = "default_invitation_max_uses")]
+    pub max_uses: i32,
+    #[validate(email)]
+    pub email: Option<String>,
+    #[validate(range(min = 1, max = 8760))]
+    pub expires_in_hours: Option<i64>,
+}
+
+fn default_invitation_role() -> crate::models::user::UserRole {
+    crate::models::user::UserRole::User
+}
+
+fn default_invitation_max_uses() -> i32 {
+    1
+}
+
+/// Davet kodu yalnızca oluşturulduğu anda düz metin olarak döner
+#[derive(Debug, Serialize)]
+pub struct CreatedInvitationResponse {
+    pub code: String,
+    pub invitation: crate::models::invitation::Invitation,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ImpersonationResponse {
+    pub token: String,
+    pub target_user_id: Uuid,
+    pub actor_user_id: Uuid,
+    pub expires_at: usize,
+}
+
 #[derive(Debug, Deserialize, Serialize,Clone)]
 pub struct AuthResponse  {
+    /// Çerez modunda token gövdede dönmez
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub token: String,
-    pub user_id: i32,
+    pub user_id: Uuid,
     pub username: String,
     pub role: String,
+    /// Çerez modunda durum değiştiren isteklerde `X-CSRF-Token` başlığıyla gönderilmesi gereken değer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub csrf_token: Option<String>,
+}
+
+/// JWT'nin hangi amaçla üretildiğini belirtir; bir amaç için üretilen token
+/// başka bir amaç için kabul edilmez.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenType {
+    Access,
+    Refresh,
+    Mfa,
+    Reset,
+}
+
+impl TokenType {
+    /// Token türüne göre geçerlilik süresi (saniye)
+    pub fn lifetime_secs(&self, config: &crate::config::AppConfig) -> usize {
+        match self {
+            TokenType::Access => config.access_token_ttl_secs,
+            TokenType::Refresh => config.refresh_token_ttl_secs,
+            TokenType::Mfa => config.mfa_token_ttl_secs,
+            TokenType::Reset => config.reset_token_ttl_secs,
+        }
+    }
+}
+
+/// RFC 8693 `act` claim'i: token'ı asıl kullanan (taklit eden) yönetici
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ActorClaim {
+    /// Yöneticinin dış kimliği
+    pub sub: Uuid,
 }
 
 #[derive(Debug, Serialize, Deserialize,Clone)]
 pub struct Claims {
-    pub user_id: i32,
+    /// Kullanıcının dış kimliği (`user_info.public_id`); dahili id token'a yazılmaz
+    pub sub: Uuid,
+    pub iss: String,
+    pub aud: String,
+    pub jti: String,
     pub exp: usize,
+    pub nbf: usize,
     pub iat: usize,
     pub role: String,
+    pub token_type: TokenType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<ActorClaim>,
+    /// Aktif organizasyonun dış kimliği; boşsa kişisel hesap adına işlem yapılır
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org: Option<Uuid>,
 }
 
 impl Claims {
-    pub fn from_user(user: &crate::models::user::User) -> Self {
+    pub fn new(
+        public_id: Uuid,
+        role: String,
+        token_type: TokenType,
+        config: &crate::config::AppConfig,
+    ) -> Self {
         let now = chrono::Utc::now().timestamp() as usize;
-        let exp = now + 3600; // 1 hour expiry, adjust as needed
         Claims {
-            user_id: user.id,
-            exp,
+            sub: public_id,
+            iss: config.jwt_issuer.clone(),
+            aud: config.jwt_audience.clone(),
+            jti: Uuid::new_v4().to_string(),
+            exp: now + token_type.lifetime_secs(config),
+            nbf: now,
             iat: now,
-            role: user.user_role.to_string().clone(),
+            role,
+            token_type,
+            sid: None,
+            act: None,
+            org: None,
         }
     }
+
+    /// Token'ı bir yöneticinin taklit (impersonation) oturumu olarak işaretler ve ömrünü kısaltır
+    pub fn with_actor(mut self, actor_public_id: Uuid, lifetime_secs: usize) -> Self {
+        self.act = Some(ActorClaim { sub: actor_public_id });
+        self.exp = self.iat + lifetime_secs;
+        self
+    }
+
+    /// Token'ı `user_session` tablosundaki bir oturuma bağlar
+    pub fn with_session(mut self, session_id: Uuid) -> Self {
+        self.sid = Some(session_id);
+        self
+    }
+
+    /// Token'ı kullanıcının üyesi olduğu bir organizasyon adına işlem yapacak şekilde işaretler
+    pub fn with_organization(mut self, organization_public_id: Option<Uuid>) -> Self {
+        self.org = organization_public_id;
+        self
+    }
+
+    pub fn from_user(
+        user: &crate::models::user::User,
+        token_type: TokenType,
+        config: &crate::config::AppConfig,
+    ) -> Self {
+        Self::new(user.public_id, user.user_role.to_string(), token_type, config)
+    }
+
+    /// Token'ın kalan geçerlilik süresi (saniye)
+    pub fn remaining_secs(&self) -> u64 {
+        let now = chrono::Utc::now().timestamp() as usize;
+        self.exp.saturating_sub(now) as u64
+    }
 }
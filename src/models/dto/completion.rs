@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    System,
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    #[validate(length(min = 1, max = 32000))]
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CompletionRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub model: String,
+    #[validate(length(min = 1, max = 100), nested)]
+    pub messages: Vec<ChatMessage>,
+    #[validate(range(min = 1, max = 32000))]
+    pub max_tokens: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompletionResponse {
+    pub model: String,
+    pub content: String,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// Harcamanın düşüldüğü organizasyon; kişisel bakiyeden düşüldüyse boş
+    pub organization_id: Option<Uuid>,
+    /// Düşümden sonra kalan bakiye (organizasyon havuzu ya da kişisel)
+    pub balance: f64,
+}
@@ -0,0 +1,26 @@
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::models::feature_flag::{FlagKind, FlagRule};
+
+/// Bayrağı oluşturur ya da tamamen değiştirir. Boolean bayraklarda `variants` yok sayılır.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpsertFeatureFlagRequest {
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    #[serde(default = "default_flag_kind")]
+    pub kind: FlagKind,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    #[validate(length(max = 20))]
+    pub variants: Vec<String>,
+    pub default_variant: Option<String>,
+    #[serde(default)]
+    #[validate(length(max = 50))]
+    pub rules: Vec<FlagRule>,
+}
+
+fn default_flag_kind() -> FlagKind {
+    FlagKind::Boolean
+}
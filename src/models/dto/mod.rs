@@ -1,2 +1,6 @@
 pub mod auth;
-pub mod user;
\ No newline at end of file
+pub mod completion;
+pub mod feature_flag;
+pub mod organization;
+pub mod system_config;
+pub mod user;
@@ -0,0 +1,54 @@
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::organization::OrganizationRole;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateOrganizationRequest {
+    #[validate(length(min = 2, max = 100))]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct InviteMemberRequest {
+    #[validate(email)]
+    pub email: String,
+    #[serde(default = "default_member_role")]
+    pub role: OrganizationRole,
+}
+
+fn default_member_role() -> OrganizationRole {
+    OrganizationRole::Member
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AcceptOrganizationInvitationRequest {
+    #[validate(length(equal = 64))]
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateMemberRequest {
+    pub role: Option<OrganizationRole>,
+    /// Ondalık sayı ya da metin ("12.50"); ikiden fazla ondalık basamak reddedilir
+    #[validate(custom(function = "crate::utils::validation::validate_spending_cap"))]
+    pub spending_cap: Option<BigDecimal>,
+    /// Harcama limitini kaldırır (sınırsız)
+    #[serde(default)]
+    pub clear_spending_cap: bool,
+}
+
+/// `organization_id` boşsa kişisel hesaba dönülür
+#[derive(Debug, Deserialize)]
+pub struct SwitchOrganizationRequest {
+    pub organization_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreditOrganizationRequest {
+    /// Ondalık sayı ya da metin ("1500.25"); ikiden fazla ondalık basamak reddedilir
+    #[validate(custom(function = "crate::utils::validation::validate_credit_amount"))]
+    pub amount: BigDecimal,
+}
@@ -0,0 +1,9 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateSettingRequest {
+    pub value: serde_json::Value,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+}
@@ -1,21 +1,71 @@
-use futures::Stream;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use validator::{Validate, ValidationError};
+use validator::Validate;
 
+use crate::models::user_session::UserSession;
 
+#[derive(Debug, Serialize)]
+pub struct SessionResponse {
+    pub id: Uuid,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub device: Option<String>,
+    pub os: Option<String>,
+    pub country: Option<String>,
+    pub city: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
+    /// İsteği yapan token'ın bağlı olduğu oturum
+    pub current: bool,
+}
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
-pub struct UserUpdate {
-    pub id: i32,
-    #[validate(length(min = 3, max = 50))]
-    pub username: Option<String>,
-    #[validate(length(min = 3, max = 80))]
-    pub full_name: Option<String>,
-    #[validate(email)]
-    pub email: Option<String>,
+impl SessionResponse {
+    pub fn from_session(session: UserSession, current_session: Option<Uuid>) -> Self {
+        Self {
+            current: current_session == Some(session.id),
+            id: session.id,
+            ip_address: session.ip_address,
+            user_agent: session.user_agent,
+            device: session.device,
+            os: session.os,
+            country: session.country,
+            city: session.city,
+            created_at: session.created_at,
+            last_seen_at: session.last_seen_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PhoneVerificationRequest {
+    #[validate(custom(function = "crate::utils::validation::validate_phone_number"))]
+    pub phone_number: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PhoneVerificationConfirm {
+    #[validate(length(equal = 6))]
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChangePasswordRequest {
+    #[validate(length(min = 1))]
+    pub current_password: String,
     #[validate(length(min = 8))]
-    pub password: Option<String>,
-    #[validate(length(equal = 10))]
-    pub phone_number: Option<String>,
-}
\ No newline at end of file
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct DeleteAccountRequest {
+    #[validate(length(min = 1))]
+    pub password: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DataExportResponse {
+    #[serde(flatten)]
+    pub export: crate::models::data_export::DataExport,
+    /// Yalnızca dışa aktarım hazır olduğunda dolu; imzalı ve süreli indirme bağlantısı
+    pub download_url: Option<String>,
+}
@@ -0,0 +1,220 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{types::Json, FromRow};
+use uuid::Uuid;
+
+use crate::{models::user::UserRole, utils::sha256::sha256_hash};
+
+/// Boolean bayrakların sabit varyantları
+pub const BOOLEAN_VARIANTS: [&str; 2] = ["off", "on"];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum FlagKind {
+    Boolean,
+    Multivariate,
+}
+
+/// Hedefleme kuralı. Boş bırakılan koşullar her isteğe uyar; dolu olanların hepsi sağlanmalıdır.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FlagRule {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<UserRole>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_ids: Vec<Uuid>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub organization_ids: Vec<Uuid>,
+    /// Kullanıcının bu bayraktaki kovası (0-99) bu değerin altındaysa eşleşir. Kovalar
+    /// bayrak başına sabit olduğundan art arda kurallarda eşikler kümülatif verilmelidir
+    /// (ör. 10 → "a", 30 → "b": %10 "a", %20 "b").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percentage: Option<u8>,
+    pub variant: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FeatureFlag {
+    pub key: String,
+    pub description: Option<String>,
+    pub kind: FlagKind,
+    pub enabled: bool,
+    pub variants: Json<Vec<String>>,
+    pub default_variant: String,
+    pub rules: Json<Vec<FlagRule>>,
+    #[serde(skip_serializing)]
+    pub updated_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Bayrakların değerlendirildiği istek bağlamı; anonim isteklerde alanlar boştur
+#[derive(Debug, Clone, Default)]
+pub struct FlagContext {
+    pub user_id: Option<Uuid>,
+    pub role: Option<String>,
+    pub organization_id: Option<Uuid>,
+}
+
+impl FlagRule {
+    fn matches(&self, flag_key: &str, ctx: &FlagContext) -> bool {
+        if !self.roles.is_empty()
+            && !ctx.role.as_deref().is_some_and(|role| self.roles.iter().any(|r| r.to_string() == role))
+        {
+            return false;
+        }
+        if !self.user_ids.is_empty() && !ctx.user_id.is_some_and(|id| self.user_ids.contains(&id)) {
+            return false;
+        }
+        if !self.organization_ids.is_empty()
+            && !ctx.organization_id.is_some_and(|id| self.organization_ids.contains(&id))
+        {
+            return false;
+        }
+        match self.percentage {
+            // Kovalama kullanıcıya bağlıdır; anonim istekler yüzde kurallarına girmez
+            Some(percentage) => ctx.user_id.is_some_and(|id| bucket(flag_key, id) < u32::from(percentage)),
+            None => true,
+        }
+    }
+}
+
+/// Kullanıcının bayraktaki sabit kovası (0-99); bayrak anahtarı karıştırıldığı için
+/// aynı kullanıcı her bayrakta farklı kovaya düşer
+pub fn bucket(flag_key: &str, user_id: Uuid) -> u32 {
+    let hash = sha256_hash(&format!("{}:{}", flag_key, user_id));
+    u32::from_str_radix(&hash[..8], 16).unwrap_or(0) % 100
+}
+
+impl FeatureFlag {
+    /// Bağlama göre seçilen varyant
+    pub fn variant_for(&self, ctx: &FlagContext) -> &str {
+        if !self.enabled {
+            return &self.default_variant;
+        }
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(&self.key, ctx))
+            .map(|rule| rule.variant.as_str())
+            .unwrap_or(&self.default_variant)
+    }
+
+    /// İstemciye dönen değer: boolean bayraklarda `true`/`false`, diğerlerinde varyant adı
+    pub fn evaluate(&self, ctx: &FlagContext) -> Value {
+        let variant = self.variant_for(ctx);
+        match self.kind {
+            FlagKind::Boolean => Value::Bool(variant == BOOLEAN_VARIANTS[1]),
+            FlagKind::Multivariate => Value::String(variant.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flag(kind: FlagKind, enabled: bool, default_variant: &str, rules: Vec<FlagRule>) -> FeatureFlag {
+        let variants = match kind {
+            FlagKind::Boolean => BOOLEAN_VARIANTS.iter().map(|v| v.to_string()).collect(),
+            FlagKind::Multivariate => vec!["control".to_string(), "a".to_string(), "b".to_string()],
+        };
+        FeatureFlag {
+            key: "new_model".to_string(),
+            description: None,
+            kind,
+            enabled,
+            variants: Json(variants),
+            default_variant: default_variant.to_string(),
+            rules: Json(rules),
+            updated_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn rule(variant: &str) -> FlagRule {
+        FlagRule {
+            roles: Vec::new(),
+            user_ids: Vec::new(),
+            organization_ids: Vec::new(),
+            percentage: None,
+            variant: variant.to_string(),
+        }
+    }
+
+    fn user(id: Uuid) -> FlagContext {
+        FlagContext { user_id: Some(id), role: Some(UserRole::User.to_string()), organization_id: None }
+    }
+
+    #[test]
+    fn bucket_is_stable_and_in_range() {
+        let id = Uuid::new_v4();
+        assert_eq!(bucket("new_model", id), bucket("new_model", id));
+        for _ in 0..1000 {
+            assert!(bucket("new_model", Uuid::new_v4()) < 100);
+        }
+    }
+
+    #[test]
+    fn bucket_spreads_users_evenly() {
+        let below_half = (0..2000).filter(|_| bucket("new_model", Uuid::new_v4()) < 50).count();
+        assert!((800..=1200).contains(&below_half), "{}", below_half);
+    }
+
+    #[test]
+    fn bucket_depends_on_the_flag_key() {
+        let ids: Vec<Uuid> = (0..50).map(|_| Uuid::new_v4()).collect();
+        assert!(ids.iter().any(|id| bucket("a", *id) != bucket("b", *id)));
+    }
+
+    #[test]
+    fn disabled_flag_returns_the_default() {
+        let flag = flag(FlagKind::Boolean, false, "off", vec![rule("on")]);
+        assert_eq!(flag.evaluate(&user(Uuid::new_v4())), Value::Bool(false));
+    }
+
+    #[test]
+    fn boolean_flag_evaluates_to_bool() {
+        let flag = flag(FlagKind::Boolean, true, "off", vec![rule("on")]);
+        assert_eq!(flag.evaluate(&FlagContext::default()), Value::Bool(true));
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let admin_only = FlagRule { roles: vec![UserRole::Admin], ..rule("a") };
+        let flag = flag(FlagKind::Multivariate, true, "control", vec![admin_only, rule("b")]);
+        let admin = FlagContext { role: Some(UserRole::Admin.to_string()), ..user(Uuid::new_v4()) };
+
+        assert_eq!(flag.evaluate(&admin), Value::String("a".to_string()));
+        assert_eq!(flag.evaluate(&user(Uuid::new_v4())), Value::String("b".to_string()));
+    }
+
+    #[test]
+    fn user_and_organization_rules_require_a_match() {
+        let target = Uuid::new_v4();
+        let org = Uuid::new_v4();
+        let by_user = FlagRule { user_ids: vec![target], ..rule("a") };
+        let by_org = FlagRule { organization_ids: vec![org], ..rule("b") };
+        let flag = flag(FlagKind::Multivariate, true, "control", vec![by_user, by_org]);
+
+        assert_eq!(flag.variant_for(&user(target)), "a");
+        let member = FlagContext { organization_id: Some(org), ..user(Uuid::new_v4()) };
+        assert_eq!(flag.variant_for(&member), "b");
+        assert_eq!(flag.variant_for(&user(Uuid::new_v4())), "control");
+    }
+
+    #[test]
+    fn percentage_rules_follow_the_bucket_and_skip_anonymous_requests() {
+        let half = FlagRule { percentage: Some(50), ..rule("on") };
+        let flag = flag(FlagKind::Boolean, true, "off", vec![half]);
+
+        for _ in 0..100 {
+            let id = Uuid::new_v4();
+            let expected = bucket(&flag.key, id) < 50;
+            assert_eq!(flag.evaluate(&user(id)), Value::Bool(expected));
+        }
+        assert_eq!(flag.evaluate(&FlagContext::default()), Value::Bool(false));
+    }
+}
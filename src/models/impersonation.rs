@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ImpersonationEventKind {
+    Start,
+    Stop,
+    Action,
+}
+
+/// Yönetici taklit oturumlarının kalıcı denetim kaydı
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ImpersonationEvent {
+    pub id: i64,
+    pub token_jti: String,
+    #[serde(skip_serializing)]
+    pub actor_id: Option<i32>,
+    #[serde(skip_serializing)]
+    pub target_user_id: Option<i32>,
+    /// API'de yalnızca dış kimlikler gösterilir; kullanıcı silinmişse boş
+    pub actor_public_id: Option<Uuid>,
+    pub target_public_id: Option<Uuid>,
+    pub event: ImpersonationEventKind,
+    pub http_method: Option<String>,
+    pub path: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
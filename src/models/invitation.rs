@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::models::user::UserRole;
+
+/// Yönetici tarafından üretilen davet kodu; kodun kendisi değil SHA-256 hash'i saklanır
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Invitation {
+    pub id: i32,
+    #[serde(skip_serializing)]
+    pub code_hash: String,
+    pub role: UserRole,
+    pub max_uses: i32,
+    pub use_count: i32,
+    pub email: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing)]
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
@@ -1,2 +1,11 @@
+pub mod audit_event;
+pub mod data_export;
 pub mod dto;
+pub mod feature_flag;
+pub mod impersonation;
+pub mod invitation;
+pub mod organization;
+pub mod system_config;
+pub mod system_log;
 pub mod user;
+pub mod user_session;
@@ -0,0 +1,111 @@
+use std::fmt;
+
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Organizasyon içindeki rol; sistem genelindeki `UserRole`'den bağımsızdır
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum OrganizationRole {
+    Owner,
+    Admin,
+    Member,
+}
+
+impl OrganizationRole {
+    /// Üye davet etme, rol ve harcama limiti düzenleme yetkisi
+    pub fn can_manage_members(&self) -> bool {
+        matches!(self, OrganizationRole::Owner | OrganizationRole::Admin)
+    }
+}
+
+impl fmt::Display for OrganizationRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrganizationRole::Owner => write!(f, "owner"),
+            OrganizationRole::Admin => write!(f, "admin"),
+            OrganizationRole::Member => write!(f, "member"),
+        }
+    }
+}
+
+// Token bakiyeleri `User` ile tutarlı olarak API'de sayı olarak döner
+fn serialize_decimal<S: Serializer>(value: &BigDecimal, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(value.to_f64().unwrap_or(0.0))
+}
+
+fn serialize_optional_decimal<S: Serializer>(value: &Option<BigDecimal>, serializer: S) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(v) => serializer.serialize_some(&v.to_f64().unwrap_or(0.0)),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Organization {
+    #[serde(skip)]
+    pub id: i32,
+    #[serde(rename = "id")]
+    pub public_id: Uuid,
+    pub name: String,
+    pub slug: String,
+    #[serde(serialize_with = "serialize_decimal")]
+    pub token_balance: BigDecimal,
+    #[serde(skip_serializing)]
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Kullanıcının üyesi olduğu organizasyonlar listesindeki bir satır
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct OrganizationMembership {
+    #[serde(rename = "id")]
+    pub public_id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub role: OrganizationRole,
+    #[serde(serialize_with = "serialize_optional_decimal")]
+    pub spending_cap: Option<BigDecimal>,
+    #[serde(serialize_with = "serialize_decimal")]
+    pub spent: BigDecimal,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct OrganizationMember {
+    #[serde(skip)]
+    pub user_id: i32,
+    #[serde(rename = "user_id")]
+    pub user_public_id: Uuid,
+    pub username: String,
+    pub full_name: String,
+    pub email: String,
+    pub role: OrganizationRole,
+    #[serde(serialize_with = "serialize_optional_decimal")]
+    pub spending_cap: Option<BigDecimal>,
+    #[serde(serialize_with = "serialize_decimal")]
+    pub spent: BigDecimal,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// E-posta ile gönderilen organizasyon daveti; bağlantıdaki token'ın yalnızca hash'i saklanır
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct OrganizationInvitation {
+    pub id: i32,
+    #[serde(skip_serializing)]
+    pub organization_id: i32,
+    pub email: String,
+    pub role: OrganizationRole,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    #[serde(skip_serializing)]
+    pub invited_by: Option<i32>,
+    pub expires_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// `system_config` tablosundaki bir ayar
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SystemConfigEntry {
+    pub key: String,
+    pub value: serde_json::Value,
+    pub description: Option<String>,
+    #[serde(skip_serializing)]
+    pub updated_by: Option<i32>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SystemConfigChange {
+    pub id: i64,
+    pub key: String,
+    pub old_value: Option<serde_json::Value>,
+    pub new_value: serde_json::Value,
+    #[serde(skip_serializing)]
+    pub changed_by: Option<i32>,
+    pub changed_by_public_id: Option<Uuid>,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Rol başına dakikalık API istek sınırı; 0 sınırsız demektir
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RoleRateLimits {
+    pub user: u32,
+    pub admin: u32,
+    pub guest: u32,
+}
+
+/// Çalışma anında değiştirilebilen ayarların tipli hali. Her alan `system_config`
+/// tablosunda aynı isimli bir anahtara karşılık gelir.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeSettings {
+    /// Kapalıyken davet kodu olmadan kayıt ve misafir hesabı açılamaz
+    pub registration_open: bool,
+    pub default_model: String,
+    pub guest_token_allowance: u32,
+    pub guest_max_per_ip_per_hour: u32,
+    /// İstemcilerde gösterilecek bakım duyurusu
+    pub maintenance_message: Option<String>,
+    pub rate_limit_per_minute: RoleRateLimits,
+}
+
+impl RuntimeSettings {
+    /// Veritabanında kayıt yokken kullanılan değerler; bir kısmı başlangıç yapılandırmasından gelir
+    pub fn defaults(config: &crate::config::AppConfig) -> Self {
+        Self {
+            registration_open: !config.invite_only_registration,
+            default_model: config.default_model.clone(),
+            guest_token_allowance: config.guest_token_allowance,
+            guest_max_per_ip_per_hour: config.guest_max_per_ip_per_hour,
+            maintenance_message: None,
+            rate_limit_per_minute: RoleRateLimits {
+                user: 120,
+                admin: 0,
+                guest: 30,
+            },
+        }
+    }
+
+    pub fn rate_limit_for_role(&self, role: &str) -> u32 {
+        match role {
+            "admin" => self.rate_limit_per_minute.admin,
+            "guest" => self.rate_limit_per_minute.guest,
+            _ => self.rate_limit_per_minute.user,
+        }
+    }
+}
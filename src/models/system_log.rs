@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// `system_log` tablosundaki bir satır
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SystemLog {
+    pub id: i64,
+    pub occurred_at: DateTime<Utc>,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    pub fields: serde_json::Value,
+    pub request_id: Option<String>,
+    #[serde(skip_serializing)]
+    pub user_id: Option<i32>,
+    pub user_public_id: Option<Uuid>,
+}
+
+/// Log katmanının yakaladığı, yazılmayı bekleyen kayıt
+#[derive(Debug, Clone)]
+pub struct NewSystemLog {
+    pub occurred_at: DateTime<Utc>,
+    pub level: &'static str,
+    pub target: String,
+    pub message: String,
+    pub fields: serde_json::Value,
+    pub request_id: Option<String>,
+    pub user_id: Option<i32>,
+}
@@ -3,6 +3,7 @@ use bigdecimal::{BigDecimal, ToPrimitive};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use uuid::Uuid;
 
 use crate::error::AppError;
 
@@ -27,7 +28,12 @@ impl fmt::Display for UserRole {
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct UserSchema {
+    /// Dahili sıralı anahtar; API'ye ve JWT'ye hiçbir zaman çıkmaz
+    #[serde(skip)]
     pub id: i32,
+    /// Dışarıya açılan, tahmin edilemez kimlik (UUIDv7)
+    #[serde(rename = "id")]
+    pub public_id: Uuid,
     pub username: String,
     pub full_name: String,
     pub email: String,
@@ -48,7 +54,12 @@ pub struct UserSchema {
 // User model with f64 token balance for application use
 #[derive(Debug, Serialize, Deserialize)]
 pub struct User {
+    /// Dahili sıralı anahtar; API'ye ve JWT'ye hiçbir zaman çıkmaz
+    #[serde(skip)]
     pub id: i32,
+    /// Dışarıya açılan, tahmin edilemez kimlik (UUIDv7)
+    #[serde(rename = "id")]
+    pub public_id: Uuid,
     pub username: String,
     pub full_name: String,
     pub email: String,
@@ -72,6 +83,7 @@ impl TryFrom<UserSchema> for User {
     fn try_from(value: UserSchema) -> Result<Self, Self::Error> {
         Ok(Self {
             id: value.id,
+            public_id: value.public_id,
             username: value.username,
             full_name: value.full_name,
             email: value.email,
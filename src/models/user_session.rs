@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Bir login işlemiyle açılan oturum; access token'daki `sid` claim'i bu kayda işaret eder
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserSession {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub user_id: i32,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub device: Option<String>,
+    pub os: Option<String>,
+    pub country: Option<String>,
+    pub city: Option<String>,
+    #[serde(skip_serializing)]
+    pub latitude: Option<f64>,
+    #[serde(skip_serializing)]
+    pub longitude: Option<f64>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,153 @@
+use crate::error::AppError;
+use crate::models::audit_event::AuditEvent;
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+use tracing::instrument;
+
+/// Zincire eşzamanlı eklemeleri sıraya sokan advisory lock anahtarı
+const AUDIT_CHAIN_LOCK_KEY: i64 = 0x0A0D_17E7;
+/// Kilit için en fazla bekleme; aşılırsa kayıt düşürülür ve istek beklemeye devam etmez
+const AUDIT_CHAIN_LOCK_TIMEOUT: &str = "2s";
+
+const SELECT_AUDIT_EVENT: &str = r#"
+    SELECT e.id, e.occurred_at, e.actor_id, e.target_user_id,
+           a.public_id as actor_public_id, t.public_id as target_public_id,
+           e.action, e.ip_address, e.user_agent, e.diff, e.prev_hash, e.hash
+    FROM audit_event e
+    LEFT JOIN user_info a ON a.id = e.actor_id
+    LEFT JOIN user_info t ON t.id = e.target_user_id
+"#;
+
+pub struct NewAuditEvent<'a> {
+    pub occurred_at: DateTime<Utc>,
+    pub actor_id: Option<i32>,
+    pub target_user_id: Option<i32>,
+    pub action: &'a str,
+    pub ip_address: Option<&'a str>,
+    pub user_agent: Option<&'a str>,
+    pub diff: &'a serde_json::Value,
+    pub prev_hash: &'a str,
+    pub hash: &'a str,
+}
+
+#[derive(Debug, Default)]
+pub struct AuditEventFilter {
+    pub actor_public_id: Option<Uuid>,
+    pub action: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Transaction boyunca zincirin sonuna yalnızca bir yazarın ekleme yapmasını sağlar.
+///
+/// Her kayıt bir öncekinin hash'ine bağlı olduğundan yazımlar bilinçli olarak tek sıradadır:
+/// kilit `last_hash` + `insert` + commit süresince (üç veritabanı gidiş-dönüşü) tutulur, yani
+/// tüm örnekler için üst sınır kabaca `1 / (3 × RTT)` kayıt/saniyedir (1 ms RTT'de ~300/sn).
+/// Sınıra yaklaşıldığında bekleyenler `AUDIT_CHAIN_LOCK_TIMEOUT` sonunda hata alır.
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn lock_chain(conn: &mut PgConnection) -> Result<(), AppError> {
+    sqlx::query(&format!("SET LOCAL lock_timeout = '{}'", AUDIT_CHAIN_LOCK_TIMEOUT))
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(AUDIT_CHAIN_LOCK_KEY)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn last_hash(conn: &mut PgConnection) -> Result<Option<String>, AppError> {
+    let hash = sqlx::query_scalar!(r#"SELECT hash FROM audit_event ORDER BY id DESC LIMIT 1"#)
+        .fetch_optional(conn)
+        .await?;
+    Ok(hash)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn insert(conn: &mut PgConnection, event: &NewAuditEvent<'_>) -> Result<i64, AppError> {
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO audit_event (occurred_at, actor_id, target_user_id, action, ip_address, user_agent, diff, prev_hash, hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING id
+        "#,
+        event.occurred_at,
+        event.actor_id,
+        event.target_user_id,
+        event.action,
+        event.ip_address,
+        event.user_agent,
+        event.diff,
+        event.prev_hash,
+        event.hash
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(id)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn query(db: &PgPool, filter: &AuditEventFilter) -> Result<Vec<AuditEvent>, AppError> {
+    let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(SELECT_AUDIT_EVENT);
+    builder.push(" WHERE 1 = 1");
+    if let Some(actor) = filter.actor_public_id {
+        builder.push(" AND a.public_id = ").push_bind(actor);
+    }
+    if let Some(action) = &filter.action {
+        // "auth.login" gibi önekler alt eylemleri de kapsar
+        builder
+            .push(" AND (e.action = ")
+            .push_bind(action.clone())
+            .push(" OR e.action LIKE ")
+            .push_bind(format!("{}.%", action.replace('%', "\\%").replace('_', "\\_")))
+            .push(")");
+    }
+    if let Some(from) = filter.from {
+        builder.push(" AND e.occurred_at >= ").push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        builder.push(" AND e.occurred_at < ").push_bind(to);
+    }
+    builder
+        .push(" ORDER BY e.id DESC LIMIT ")
+        .push_bind(filter.limit)
+        .push(" OFFSET ")
+        .push_bind(filter.offset);
+
+    let events = builder.build_query_as::<AuditEvent>().fetch_all(db).await?;
+    Ok(events)
+}
+
+/// Zincir doğrulaması için `after_id`'den sonraki kayıtları id sırasıyla döner
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_after(db: &PgPool, after_id: i64, limit: i64) -> Result<Vec<AuditEvent>, AppError> {
+    let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(SELECT_AUDIT_EVENT);
+    builder
+        .push(" WHERE e.id > ")
+        .push_bind(after_id)
+        .push(" ORDER BY e.id ASC LIMIT ")
+        .push_bind(limit);
+
+    let events = builder.build_query_as::<AuditEvent>().fetch_all(db).await?;
+    Ok(events)
+}
+
+/// Kullanıcının aktör ya da hedef olduğu tüm kayıtlar (kişisel veri dışa aktarımı için)
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_for_user(db: &PgPool, user_id: i32) -> Result<Vec<AuditEvent>, AppError> {
+    let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(SELECT_AUDIT_EVENT);
+    builder
+        .push(" WHERE e.actor_id = ")
+        .push_bind(user_id)
+        .push(" OR e.target_user_id = ")
+        .push_bind(user_id)
+        .push(" ORDER BY e.id ASC");
+
+    let events = builder.build_query_as::<AuditEvent>().fetch_all(db).await?;
+    Ok(events)
+}
@@ -1,11 +1,14 @@
 use crate::error::AppError;
 use crate::models::user::{User, UserRole,UserSchema};
-use sqlx::PgPool;
-use bigdecimal::{BigDecimal, ToPrimitive};
+use sqlx::{PgExecutor, PgPool};
+use bigdecimal::BigDecimal;
 use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use tracing::instrument;
 
-pub async fn create(
-    db: &PgPool,
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn create<'e>(
+    db: impl PgExecutor<'e>,
     user_data: &UserSchema,
     password_hash: &str,
     role: UserRole,
@@ -15,7 +18,7 @@ pub async fn create(
         r#"
         INSERT INTO user_info (username, full_name, email, password_hash, phone_number, user_role)
         VALUES ($1, $2, $3, $4, $5, $6)
-        RETURNING id, username, full_name, email, password_hash, phone_number,
+        RETURNING id, public_id, username, full_name, email, password_hash, phone_number,
                   token_balance, user_role as "user_role: UserRole",
                   subscription_expries, email_verified, phone_verified, 
                   last_login, is_active, created_at, updated_at
@@ -37,6 +40,7 @@ pub async fn create(
 
 
 
+#[instrument(skip_all, fields(db.system = "postgresql"))]
 pub async fn find_by_username_or_email(
      db: &PgPool,
      mail_or_id: &str
@@ -44,7 +48,7 @@ pub async fn find_by_username_or_email(
     let user_schema = sqlx::query_as!(
         UserSchema,
         r#"
-        SELECT id, username, full_name, email, password_hash, phone_number, token_balance, user_role as "user_role: UserRole",
+        SELECT id, public_id, username, full_name, email, password_hash, phone_number, token_balance, user_role as "user_role: UserRole",
                subscription_expries, email_verified, phone_verified, last_login, is_active, created_at, updated_at
         FROM user_info
         WHERE username = $1 OR email = $2
@@ -60,11 +64,12 @@ pub async fn find_by_username_or_email(
     Ok(user)
 }
 
+#[instrument(skip_all, fields(db.system = "postgresql"))]
 pub async fn find_by_id(db: &PgPool, user_id: i32) -> Result<Option<User>, AppError> {
     let user = sqlx::query_as!(
         UserSchema,
         r#"
-        SELECT id, username, full_name, email, password_hash, phone_number, token_balance, user_role as "user_role: UserRole",
+        SELECT id, public_id, username, full_name, email, password_hash, phone_number, token_balance, user_role as "user_role: UserRole",
                     subscription_expries, email_verified, phone_verified, last_login, is_active, created_at, updated_at
         FROM user_info 
         WHERE id = $1  
@@ -76,4 +81,332 @@ pub async fn find_by_id(db: &PgPool, user_id: i32) -> Result<Option<User>, AppEr
     .map(User::try_from)
     .transpose()?;
     Ok(user)
-}
\ No newline at end of file
+}
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn create_guest(
+    db: &PgPool,
+    username: &str,
+    email: &str,
+    password_hash: &str,
+    token_balance: BigDecimal,
+) -> Result<User, AppError> {
+    let user_schema = sqlx::query_as!(
+        UserSchema,
+        r#"
+        INSERT INTO user_info (username, full_name, email, password_hash, token_balance, user_role)
+        VALUES ($1, 'Guest', $2, $3, $4, 'guest')
+        RETURNING id, public_id, username, full_name, email, password_hash, phone_number,
+                  token_balance, user_role as "user_role: UserRole",
+                  subscription_expries, email_verified, phone_verified,
+                  last_login, is_active, created_at, updated_at
+        "#,
+        username,
+        email,
+        password_hash,
+        token_balance
+    )
+    .fetch_one(db)
+    .await?;
+
+    User::try_from(user_schema)
+}
+
+/// Kişisel bakiyeden düşer ve yeni bakiyeyi döner; bakiye yetmiyorsa `None`
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn debit_tokens(db: &PgPool, user_id: i32, amount: &BigDecimal) -> Result<Option<BigDecimal>, AppError> {
+    let balance = sqlx::query_scalar!(
+        r#"
+        UPDATE user_info
+        SET token_balance = token_balance - $2, updated_at = now()
+        WHERE id = $1 AND token_balance >= $2
+        RETURNING token_balance as "token_balance!"
+        "#,
+        user_id,
+        amount
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(balance)
+}
+
+/// Misafir hesabı aynı id ile normal kullanıcıya dönüştürür; sohbet ve geçmiş kayıtları korunur
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn upgrade_guest(
+    db: &PgPool,
+    user_id: i32,
+    user_data: &UserSchema,
+    password_hash: &str,
+) -> Result<Option<User>, AppError> {
+    let user = sqlx::query_as!(
+        UserSchema,
+        r#"
+        UPDATE user_info
+        SET username = $2, full_name = $3, email = $4, password_hash = $5,
+            phone_number = $6, user_role = 'user', updated_at = now()
+        WHERE id = $1 AND user_role = 'guest'
+        RETURNING id, public_id, username, full_name, email, password_hash, phone_number,
+                  token_balance, user_role as "user_role: UserRole",
+                  subscription_expries, email_verified, phone_verified,
+                  last_login, is_active, created_at, updated_at
+        "#,
+        user_id,
+        user_data.username,
+        user_data.full_name,
+        user_data.email,
+        password_hash,
+        user_data.phone_number
+    )
+    .fetch_optional(db)
+    .await?
+    .map(User::try_from)
+    .transpose()?;
+    Ok(user)
+}
+
+/// `cutoff`tan önce oluşturulmuş ve o tarihten beri hiçbir oturumu görülmemiş misafirleri siler
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn delete_stale_guests(db: &PgPool, cutoff: DateTime<Utc>) -> Result<u64, AppError> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM user_info u
+        WHERE u.user_role = 'guest'
+          AND u.created_at < $1
+          AND NOT EXISTS (
+              SELECT 1 FROM user_session s
+              WHERE s.user_id = u.id AND s.last_seen_at >= $1
+          )
+        "#,
+        cutoff
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn set_phone_verified(db: &PgPool, user_id: i32, phone_number: &str) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        UPDATE user_info
+        SET phone_number = $2, phone_verified = true, updated_at = now()
+        WHERE id = $1
+        "#,
+        user_id,
+        phone_number
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn find_by_email(db: &PgPool, email: &str) -> Result<Option<User>, AppError> {
+    let user = sqlx::query_as!(
+        UserSchema,
+        r#"
+        SELECT id, public_id, username, full_name, email, password_hash, phone_number, token_balance, user_role as "user_role: UserRole",
+               subscription_expries, email_verified, phone_verified, last_login, is_active, created_at, updated_at
+        FROM user_info
+        WHERE lower(email) = lower($1)
+        "#,
+        email
+    )
+    .fetch_optional(db)
+    .await?
+    .map(User::try_from)
+    .transpose()?;
+    Ok(user)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn update_password(db: &PgPool, user_id: i32, password_hash: &str) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        UPDATE user_info
+        SET password_hash = $2, updated_at = now()
+        WHERE id = $1
+        "#,
+        user_id,
+        password_hash
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn admin_exists(db: &PgPool) -> Result<bool, AppError> {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM user_info WHERE user_role = 'admin') as "exists!""#
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(exists)
+}
+
+/// Hesap silme talebini kaydeder; silme işlemi `scheduled_for` tarihinde yapılır
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn schedule_deletion(db: &PgPool, user_id: i32, scheduled_for: DateTime<Utc>) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        UPDATE user_info
+        SET deletion_requested_at = now(), deletion_scheduled_for = $2, updated_at = now()
+        WHERE id = $1 AND anonymized_at IS NULL
+        "#,
+        user_id,
+        scheduled_for
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn cancel_deletion(db: &PgPool, user_id: i32) -> Result<bool, AppError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE user_info
+        SET deletion_requested_at = NULL, deletion_scheduled_for = NULL, updated_at = now()
+        WHERE id = $1 AND deletion_scheduled_for IS NOT NULL AND anonymized_at IS NULL
+        "#,
+        user_id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn deletion_scheduled_for(db: &PgPool, user_id: i32) -> Result<Option<DateTime<Utc>>, AppError> {
+    let scheduled = sqlx::query_scalar!(
+        r#"SELECT deletion_scheduled_for FROM user_info WHERE id = $1"#,
+        user_id
+    )
+    .fetch_optional(db)
+    .await?
+    .flatten();
+
+    Ok(scheduled)
+}
+
+/// Bekleme süresi dolmuş silme taleplerinin kullanıcı id'leri
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn find_due_deletions(db: &PgPool) -> Result<Vec<i32>, AppError> {
+    let ids = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM user_info
+        WHERE deletion_scheduled_for IS NOT NULL
+          AND deletion_scheduled_for < now()
+          AND anonymized_at IS NULL
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(ids)
+}
+
+/// Kişisel verileri kaldırır; satır, bağlı kayıtların bütünlüğü için anonim olarak kalır.
+/// Önbellek temizliği için kullanıcının dış kimliğini döner.
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn anonymize<'e>(
+    db: impl PgExecutor<'e>,
+    user_id: i32,
+    unusable_password_hash: &str,
+) -> Result<Uuid, AppError> {
+    let public_id = sqlx::query_scalar!(
+        r#"
+        UPDATE user_info
+        SET username = 'deleted_' || id,
+            full_name = 'Deleted User',
+            email = 'deleted_' || id || '@deleted.invalid',
+            password_hash = $2,
+            phone_number = NULL,
+            email_verified = false,
+            phone_verified = false,
+            is_active = false,
+            last_login = NULL,
+            anonymized_at = now(),
+            updated_at = now()
+        WHERE id = $1
+        RETURNING public_id
+        "#,
+        user_id,
+        unusable_password_hash
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(public_id)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn find_by_public_id(db: &PgPool, public_id: Uuid) -> Result<Option<User>, AppError> {
+    let user = sqlx::query_as!(
+        UserSchema,
+        r#"
+        SELECT id, public_id, username, full_name, email, password_hash, phone_number, token_balance, user_role as "user_role: UserRole",
+               subscription_expries, email_verified, phone_verified, last_login, is_active, created_at, updated_at
+        FROM user_info
+        WHERE public_id = $1
+        "#,
+        public_id
+    )
+    .fetch_optional(db)
+    .await?
+    .map(User::try_from)
+    .transpose()?;
+    Ok(user)
+}
+
+/// JWT'deki dış kimliği dahili anahtara çevirir; pasif hesaplar için `None` döner
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn find_internal_id(db: &PgPool, public_id: Uuid) -> Result<Option<i32>, AppError> {
+    let id = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM user_info
+        WHERE public_id = $1 AND is_active IS DISTINCT FROM false
+        "#,
+        public_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(id)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn password_reset_required(db: &PgPool, user_id: i32) -> Result<bool, AppError> {
+    let required = sqlx::query_scalar!(
+        r#"SELECT password_reset_required FROM user_info WHERE id = $1"#,
+        user_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(required.unwrap_or(false))
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn set_password_reset_required(db: &PgPool, user_id: i32, required: bool) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        UPDATE user_info
+        SET password_reset_required = $2, updated_at = now()
+        WHERE id = $1
+        "#,
+        user_id,
+        required
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use crate::error::AppError;
+use crate::models::data_export::{DataExport, ExportStatus};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use tracing::instrument;
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn create(db: &PgPool, user_id: i32) -> Result<DataExport, AppError> {
+    let export = sqlx::query_as!(
+        DataExport,
+        r#"
+        INSERT INTO data_export (user_id)
+        VALUES ($1)
+        RETURNING id, user_id, status as "status: ExportStatus", file_path, error,
+                  created_at, completed_at, expires_at
+        "#,
+        user_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(export)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn find_by_id(db: &PgPool, id: Uuid) -> Result<Option<DataExport>, AppError> {
+    let export = sqlx::query_as!(
+        DataExport,
+        r#"
+        SELECT id, user_id, status as "status: ExportStatus", file_path, error,
+               created_at, completed_at, expires_at
+        FROM data_export
+        WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(export)
+}
+
+/// `stale_before`'dan önce başlamış bekleyen talepler (yarıda kalmış üretimler) sayılmaz
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn has_pending(db: &PgPool, user_id: i32, stale_before: DateTime<Utc>) -> Result<bool, AppError> {
+    let exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM data_export
+            WHERE user_id = $1 AND status = 'pending' AND created_at >= $2
+        ) as "exists!"
+        "#,
+        user_id,
+        stale_before
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(exists)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn mark_ready(db: &PgPool, id: Uuid, file_path: &str, expires_at: DateTime<Utc>) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        UPDATE data_export
+        SET status = 'ready', file_path = $2, completed_at = now(), expires_at = $3
+        WHERE id = $1
+        "#,
+        id,
+        file_path,
+        expires_at
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn mark_failed(db: &PgPool, id: Uuid, error: &str) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        UPDATE data_export
+        SET status = 'failed', error = $2, completed_at = now()
+        WHERE id = $1
+        "#,
+        id,
+        error
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// `stale_before`'dan önce başlayıp hâlâ bekleyen talepleri (ör. üretim sırasında süreç
+/// yeniden başladıysa) başarısız olarak işaretler
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn fail_stale(db: &PgPool, stale_before: DateTime<Utc>) -> Result<u64, AppError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE data_export
+        SET status = 'failed', error = 'Export generation did not complete', completed_at = now()
+        WHERE status = 'pending' AND created_at < $1
+        "#,
+        stale_before
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Süresi dolan hazır dışa aktarımları `expired` olarak işaretler ve dosya yollarını döner
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn expire_due(db: &PgPool) -> Result<Vec<String>, AppError> {
+    let paths = sqlx::query_scalar!(
+        r#"
+        UPDATE data_export
+        SET status = 'expired'
+        WHERE status = 'ready' AND expires_at < now()
+        RETURNING file_path
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(paths.into_iter().flatten().collect())
+}
+
+/// Kullanıcının tüm dışa aktarım kayıtlarını siler ve dosya yollarını döner
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn delete_for_user(db: &PgPool, user_id: i32) -> Result<Vec<String>, AppError> {
+    let paths = sqlx::query_scalar!(
+        r#"
+        DELETE FROM data_export
+        WHERE user_id = $1
+        RETURNING file_path
+        "#,
+        user_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(paths.into_iter().flatten().collect())
+}
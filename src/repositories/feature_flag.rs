@@ -0,0 +1,77 @@
+use crate::error::AppError;
+use crate::models::feature_flag::{FeatureFlag, FlagKind, FlagRule};
+use sqlx::{types::Json, PgPool};
+use tracing::instrument;
+
+pub struct UpsertFeatureFlag<'a> {
+    pub key: &'a str,
+    pub description: Option<&'a str>,
+    pub kind: FlagKind,
+    pub enabled: bool,
+    pub variants: &'a [String],
+    pub default_variant: &'a str,
+    pub rules: &'a [FlagRule],
+    pub updated_by: i32,
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list(db: &PgPool) -> Result<Vec<FeatureFlag>, AppError> {
+    let flags = sqlx::query_as!(
+        FeatureFlag,
+        r#"
+        SELECT key, description, kind as "kind: FlagKind", enabled,
+               variants as "variants: Json<Vec<String>>", default_variant,
+               rules as "rules: Json<Vec<FlagRule>>", updated_by, created_at, updated_at
+        FROM feature_flag
+        ORDER BY key
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(flags)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn upsert(db: &PgPool, flag: &UpsertFeatureFlag<'_>) -> Result<FeatureFlag, AppError> {
+    let flag = sqlx::query_as!(
+        FeatureFlag,
+        r#"
+        INSERT INTO feature_flag (key, description, kind, enabled, variants, default_variant, rules, updated_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (key) DO UPDATE
+        SET description = EXCLUDED.description,
+            kind = EXCLUDED.kind,
+            enabled = EXCLUDED.enabled,
+            variants = EXCLUDED.variants,
+            default_variant = EXCLUDED.default_variant,
+            rules = EXCLUDED.rules,
+            updated_by = EXCLUDED.updated_by,
+            updated_at = now()
+        RETURNING key, description, kind as "kind: FlagKind", enabled,
+                  variants as "variants: Json<Vec<String>>", default_variant,
+                  rules as "rules: Json<Vec<FlagRule>>", updated_by, created_at, updated_at
+        "#,
+        flag.key,
+        flag.description,
+        flag.kind as _,
+        flag.enabled,
+        Json(flag.variants) as _,
+        flag.default_variant,
+        Json(flag.rules) as _,
+        flag.updated_by
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(flag)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn delete(db: &PgPool, key: &str) -> Result<bool, AppError> {
+    let result = sqlx::query!(r#"DELETE FROM feature_flag WHERE key = $1"#, key)
+        .execute(db)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
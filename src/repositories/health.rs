@@ -0,0 +1,20 @@
+use crate::error::AppError;
+use sqlx::PgPool;
+use tracing::instrument;
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn ping(db: &PgPool) -> Result<(), AppError> {
+    sqlx::query!("SELECT 1 as one").fetch_one(db).await?;
+    Ok(())
+}
+
+/// Başarıyla uygulanmış migration sürümleri. `_sqlx_migrations` tablosu migrator tarafından
+/// oluşturulduğu için derleme anında doğrulanamaz; sorgu çalışma anında hazırlanır.
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn applied_migrations(db: &PgPool) -> Result<Vec<i64>, AppError> {
+    let versions = sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success = true")
+        .fetch_all(db)
+        .await?;
+
+    Ok(versions)
+}
@@ -0,0 +1,99 @@
+use crate::error::AppError;
+use crate::models::impersonation::{ImpersonationEvent, ImpersonationEventKind};
+use sqlx::{PgExecutor, PgPool};
+use tracing::instrument;
+
+pub struct NewImpersonationEvent<'a> {
+    pub token_jti: &'a str,
+    pub actor_id: i32,
+    pub target_user_id: i32,
+    pub event: ImpersonationEventKind,
+    pub http_method: Option<&'a str>,
+    pub path: Option<&'a str>,
+    pub ip_address: Option<&'a str>,
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn record(db: &PgPool, event: &NewImpersonationEvent<'_>) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO impersonation_event (token_jti, actor_id, target_user_id, event, http_method, path, ip_address)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        event.token_jti,
+        event.actor_id,
+        event.target_user_id,
+        event.event as _,
+        event.http_method,
+        event.path,
+        event.ip_address
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_by_token(db: &PgPool, token_jti: &str) -> Result<Vec<ImpersonationEvent>, AppError> {
+    let events = sqlx::query_as!(
+        ImpersonationEvent,
+        r#"
+        SELECT e.id, e.token_jti, e.actor_id, e.target_user_id,
+               a.public_id as "actor_public_id?", t.public_id as "target_public_id?",
+               e.event as "event: ImpersonationEventKind",
+               e.http_method, e.path, e.ip_address, e.created_at
+        FROM impersonation_event e
+        LEFT JOIN user_info a ON a.id = e.actor_id
+        LEFT JOIN user_info t ON t.id = e.target_user_id
+        WHERE e.token_jti = $1
+        ORDER BY e.created_at
+        "#,
+        token_jti
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(events)
+}
+
+/// Kullanıcının yaptığı ya da kendisine yapılan tüm temsil kayıtları
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_for_user(db: &PgPool, user_id: i32) -> Result<Vec<ImpersonationEvent>, AppError> {
+    let events = sqlx::query_as!(
+        ImpersonationEvent,
+        r#"
+        SELECT e.id, e.token_jti, e.actor_id, e.target_user_id,
+               a.public_id as "actor_public_id?", t.public_id as "target_public_id?",
+               e.event as "event: ImpersonationEventKind",
+               e.http_method, e.path, e.ip_address, e.created_at
+        FROM impersonation_event e
+        LEFT JOIN user_info a ON a.id = e.actor_id
+        LEFT JOIN user_info t ON t.id = e.target_user_id
+        WHERE e.actor_id = $1 OR e.target_user_id = $1
+        ORDER BY e.created_at
+        "#,
+        user_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(events)
+}
+
+/// Hesap silinirken kullanıcının taraf olduğu kayıtlardaki IP adreslerini siler
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn clear_ip_addresses_for_user<'e>(executor: impl PgExecutor<'e>, user_id: i32) -> Result<u64, AppError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE impersonation_event
+        SET ip_address = NULL
+        WHERE (actor_id = $1 OR target_user_id = $1) AND ip_address IS NOT NULL
+        "#,
+        user_id
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected())
+}
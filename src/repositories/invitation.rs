@@ -0,0 +1,185 @@
+use crate::error::AppError;
+use crate::models::invitation::Invitation;
+use crate::models::user::UserRole;
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
+use tracing::instrument;
+
+pub struct NewInvitation<'a> {
+    pub code_hash: &'a str,
+    pub role: UserRole,
+    pub max_uses: i32,
+    pub email: Option<&'a str>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_by: Option<i32>,
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn create(db: &PgPool, invitation: &NewInvitation<'_>) -> Result<Invitation, AppError> {
+    let invitation = sqlx::query_as!(
+        Invitation,
+        r#"
+        INSERT INTO invitation (code_hash, role, max_uses, email, expires_at, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, code_hash, role as "role: UserRole", max_uses, use_count, email,
+                  expires_at, created_by, created_at, revoked_at
+        "#,
+        invitation.code_hash,
+        invitation.role.clone() as _,
+        invitation.max_uses,
+        invitation.email,
+        invitation.expires_at,
+        invitation.created_by
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(invitation)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list(db: &PgPool) -> Result<Vec<Invitation>, AppError> {
+    let invitations = sqlx::query_as!(
+        Invitation,
+        r#"
+        SELECT id, code_hash, role as "role: UserRole", max_uses, use_count, email,
+               expires_at, created_by, created_at, revoked_at
+        FROM invitation
+        ORDER BY created_at DESC
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(invitations)
+}
+
+/// Bir e-posta adresine bağlanmış kayıt davetleri
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_for_email(db: &PgPool, email: &str) -> Result<Vec<Invitation>, AppError> {
+    let invitations = sqlx::query_as!(
+        Invitation,
+        r#"
+        SELECT id, code_hash, role as "role: UserRole", max_uses, use_count, email,
+               expires_at, created_by, created_at, revoked_at
+        FROM invitation
+        WHERE lower(email) = lower($1)
+        ORDER BY created_at
+        "#,
+        email
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(invitations)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn revoke(db: &PgPool, id: i32) -> Result<bool, AppError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE invitation
+        SET revoked_at = now()
+        WHERE id = $1 AND revoked_at IS NULL
+        "#,
+        id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Geçerli bir daveti atomik olarak bir kez kullanır ve verdiği rolü döner.
+/// Kod geçersiz, süresi dolmuş, iptal edilmiş, tükenmiş ya da başka bir
+/// e-postaya bağlıysa `None` döner.
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn redeem<'e>(
+    executor: impl PgExecutor<'e>,
+    code_hash: &str,
+    email: &str,
+) -> Result<Option<UserRole>, AppError> {
+    let role = sqlx::query_scalar!(
+        r#"
+        UPDATE invitation
+        SET use_count = use_count + 1
+        WHERE code_hash = $1
+          AND revoked_at IS NULL
+          AND (expires_at IS NULL OR expires_at > now())
+          AND use_count < max_uses
+          AND (email IS NULL OR lower(email) = lower($2))
+        RETURNING role as "role: UserRole"
+        "#,
+        code_hash,
+        email
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(role)
+}
+
+/// Kod hâlâ kullanılabilir mi: iptal edilmemiş, süresi dolmamış ve kullanım hakkı bitmemiş
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn is_redeemable(db: &PgPool, code_hash: &str) -> Result<bool, AppError> {
+    let redeemable = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM invitation
+            WHERE code_hash = $1
+              AND revoked_at IS NULL
+              AND use_count < max_uses
+              AND (expires_at IS NULL OR expires_at > now())
+        ) as "exists!"
+        "#,
+        code_hash
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(redeemable)
+}
+
+/// Aynı kodla eski (süresi dolmuş, kullanılmış ya da iptal edilmiş) bir satır varsa onu
+/// yeniden kullanılabilir hale getirir, yoksa yeni satır ekler
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn create_or_renew(db: &PgPool, invitation: &NewInvitation<'_>) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO invitation (code_hash, role, max_uses, email, expires_at, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (code_hash) DO UPDATE
+        SET role = EXCLUDED.role, max_uses = EXCLUDED.max_uses, use_count = 0,
+            email = EXCLUDED.email, expires_at = EXCLUDED.expires_at, revoked_at = NULL
+        "#,
+        invitation.code_hash,
+        invitation.role.clone() as _,
+        invitation.max_uses,
+        invitation.email,
+        invitation.expires_at,
+        invitation.created_by
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Kullanıcının e-posta adresine bağlı kayıt davetlerinden adresi siler; adrese bağlı bir davet
+/// adressiz kalınca herkese açık hale gelmesin diye aynı anda iptal edilir.
+/// Anonimleştirmeden önce çağrılmalıdır.
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn redact_email_for_user<'e>(executor: impl PgExecutor<'e>, user_id: i32) -> Result<u64, AppError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE invitation
+        SET email = NULL, revoked_at = coalesce(revoked_at, now())
+        WHERE lower(email) = (SELECT lower(email) FROM user_info WHERE id = $1)
+        "#,
+        user_id
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected())
+}
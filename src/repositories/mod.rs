@@ -1 +1,11 @@
+pub mod audit_event;
 pub mod auth;
+pub mod data_export;
+pub mod feature_flag;
+pub mod health;
+pub mod impersonation;
+pub mod invitation;
+pub mod organization;
+pub mod system_config;
+pub mod system_log;
+pub mod user_session;
@@ -0,0 +1,449 @@
+use crate::error::AppError;
+use crate::models::organization::{
+    Organization, OrganizationInvitation, OrganizationMember, OrganizationMembership, OrganizationRole,
+};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+use tracing::instrument;
+
+pub struct NewOrganizationInvitation<'a> {
+    pub organization_id: i32,
+    pub email: &'a str,
+    pub role: OrganizationRole,
+    pub token_hash: &'a str,
+    pub invited_by: i32,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn create<'e>(
+    executor: impl PgExecutor<'e>,
+    name: &str,
+    slug: &str,
+    created_by: i32,
+) -> Result<Organization, AppError> {
+    let organization = sqlx::query_as!(
+        Organization,
+        r#"
+        INSERT INTO organization (name, slug, created_by)
+        VALUES ($1, $2, $3)
+        RETURNING id, public_id, name, slug, token_balance, created_by, created_at, updated_at
+        "#,
+        name,
+        slug,
+        created_by
+    )
+    .fetch_one(executor)
+    .await?;
+
+    Ok(organization)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn find_by_public_id(db: &PgPool, public_id: Uuid) -> Result<Option<Organization>, AppError> {
+    let organization = sqlx::query_as!(
+        Organization,
+        r#"
+        SELECT id, public_id, name, slug, token_balance, created_by, created_at, updated_at
+        FROM organization
+        WHERE public_id = $1
+        "#,
+        public_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(organization)
+}
+
+/// Zaten üyeyse hiçbir şey yapmaz ve false döner
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn add_member<'e>(
+    executor: impl PgExecutor<'e>,
+    organization_id: i32,
+    user_id: i32,
+    role: OrganizationRole,
+) -> Result<bool, AppError> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO organization_member (organization_id, user_id, role)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (organization_id, user_id) DO NOTHING
+        "#,
+        organization_id,
+        user_id,
+        role as _
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_for_user(db: &PgPool, user_id: i32) -> Result<Vec<OrganizationMembership>, AppError> {
+    let memberships = sqlx::query_as!(
+        OrganizationMembership,
+        r#"
+        SELECT o.public_id, o.name, o.slug, m.role as "role: OrganizationRole",
+               m.spending_cap, m.spent, m.joined_at
+        FROM organization_member m
+        JOIN organization o ON o.id = m.organization_id
+        WHERE m.user_id = $1
+        ORDER BY m.joined_at
+        "#,
+        user_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(memberships)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn find_membership(
+    db: &PgPool,
+    organization_id: i32,
+    user_id: i32,
+) -> Result<Option<OrganizationMembership>, AppError> {
+    let membership = sqlx::query_as!(
+        OrganizationMembership,
+        r#"
+        SELECT o.public_id, o.name, o.slug, m.role as "role: OrganizationRole",
+               m.spending_cap, m.spent, m.joined_at
+        FROM organization_member m
+        JOIN organization o ON o.id = m.organization_id
+        WHERE m.organization_id = $1 AND m.user_id = $2
+        "#,
+        organization_id,
+        user_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(membership)
+}
+
+/// JWT'deki `org` claim'inin hâlâ geçerli bir üyeliğe karşılık geldiğini doğrular ve üyelik rolünü döner
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn find_active_membership(
+    db: &PgPool,
+    organization_public_id: Uuid,
+    user_id: i32,
+) -> Result<Option<OrganizationRole>, AppError> {
+    let role = sqlx::query_scalar!(
+        r#"
+        SELECT m.role as "role: OrganizationRole"
+        FROM organization_member m
+        JOIN organization o ON o.id = m.organization_id
+        WHERE o.public_id = $1 AND m.user_id = $2
+        "#,
+        organization_public_id,
+        user_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(role)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn find_member(db: &PgPool, organization_id: i32, user_id: i32) -> Result<Option<OrganizationMember>, AppError> {
+    let member = sqlx::query_as!(
+        OrganizationMember,
+        r#"
+        SELECT m.user_id, u.public_id as user_public_id, u.username, u.full_name, u.email,
+               m.role as "role: OrganizationRole", m.spending_cap, m.spent, m.joined_at
+        FROM organization_member m
+        JOIN user_info u ON u.id = m.user_id
+        WHERE m.organization_id = $1 AND m.user_id = $2
+        "#,
+        organization_id,
+        user_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(member)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_members(db: &PgPool, organization_id: i32) -> Result<Vec<OrganizationMember>, AppError> {
+    let members = sqlx::query_as!(
+        OrganizationMember,
+        r#"
+        SELECT m.user_id, u.public_id as user_public_id, u.username, u.full_name, u.email,
+               m.role as "role: OrganizationRole", m.spending_cap, m.spent, m.joined_at
+        FROM organization_member m
+        JOIN user_info u ON u.id = m.user_id
+        WHERE m.organization_id = $1
+        ORDER BY m.joined_at
+        "#,
+        organization_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(members)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn count_owners(db: &PgPool, organization_id: i32) -> Result<i64, AppError> {
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM organization_member WHERE organization_id = $1 AND role = 'owner'"#,
+        organization_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(count)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn update_member_role(
+    db: &PgPool,
+    organization_id: i32,
+    user_id: i32,
+    role: OrganizationRole,
+) -> Result<bool, AppError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE organization_member
+        SET role = $3
+        WHERE organization_id = $1 AND user_id = $2
+        "#,
+        organization_id,
+        user_id,
+        role as _
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn set_spending_cap(
+    db: &PgPool,
+    organization_id: i32,
+    user_id: i32,
+    spending_cap: Option<BigDecimal>,
+) -> Result<bool, AppError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE organization_member
+        SET spending_cap = $3
+        WHERE organization_id = $1 AND user_id = $2
+        "#,
+        organization_id,
+        user_id,
+        spending_cap
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn remove_member(db: &PgPool, organization_id: i32, user_id: i32) -> Result<bool, AppError> {
+    let result = sqlx::query!(
+        r#"DELETE FROM organization_member WHERE organization_id = $1 AND user_id = $2"#,
+        organization_id,
+        user_id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Hesap silinirken kullanıcının tüm üyeliklerini kaldırır
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn delete_memberships_for_user<'e>(executor: impl PgExecutor<'e>, user_id: i32) -> Result<u64, AppError> {
+    let result = sqlx::query!(r#"DELETE FROM organization_member WHERE user_id = $1"#, user_id)
+        .execute(executor)
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Kullanıcının e-posta adresine gönderilmiş davetleri siler; anonimleştirmeden önce çağrılmalıdır
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn delete_invitations_for_user<'e>(executor: impl PgExecutor<'e>, user_id: i32) -> Result<u64, AppError> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM organization_invitation
+        WHERE lower(email) = (SELECT lower(email) FROM user_info WHERE id = $1)
+        "#,
+        user_id
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Organizasyon havuzuna token ekler ve yeni bakiyeyi döner
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn credit(db: &PgPool, organization_id: i32, amount: &BigDecimal) -> Result<Option<BigDecimal>, AppError> {
+    let balance = sqlx::query_scalar!(
+        r#"
+        UPDATE organization
+        SET token_balance = token_balance + $2, updated_at = now()
+        WHERE id = $1
+        RETURNING token_balance
+        "#,
+        organization_id,
+        amount
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(balance)
+}
+
+/// Üyenin harcamasını artırır; harcama limiti aşılacaksa hiçbir satır güncellenmez ve `false` döner.
+/// Limit kontrolü ve artış tek UPDATE'te yapılır, eşzamanlı istekler limiti aşamaz.
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn add_member_spend<'e>(
+    executor: impl PgExecutor<'e>,
+    organization_id: i32,
+    user_id: i32,
+    amount: &BigDecimal,
+) -> Result<bool, AppError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE organization_member
+        SET spent = spent + $3
+        WHERE organization_id = $1 AND user_id = $2
+          AND spent + $3 <= coalesce(spending_cap, spent + $3)
+        "#,
+        organization_id,
+        user_id,
+        amount
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Havuzdan düşer ve yeni bakiyeyi döner; bakiye yetmiyorsa `None`
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn debit<'e>(
+    executor: impl PgExecutor<'e>,
+    organization_id: i32,
+    amount: &BigDecimal,
+) -> Result<Option<BigDecimal>, AppError> {
+    let balance = sqlx::query_scalar!(
+        r#"
+        UPDATE organization
+        SET token_balance = token_balance - $2, updated_at = now()
+        WHERE id = $1 AND token_balance >= $2
+        RETURNING token_balance
+        "#,
+        organization_id,
+        amount
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(balance)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn create_invitation(
+    db: &PgPool,
+    invitation: &NewOrganizationInvitation<'_>,
+) -> Result<OrganizationInvitation, AppError> {
+    let invitation = sqlx::query_as!(
+        OrganizationInvitation,
+        r#"
+        INSERT INTO organization_invitation (organization_id, email, role, token_hash, invited_by, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, organization_id, email, role as "role: OrganizationRole", token_hash,
+                  invited_by, expires_at, accepted_at, created_at
+        "#,
+        invitation.organization_id,
+        invitation.email,
+        invitation.role as _,
+        invitation.token_hash,
+        invitation.invited_by,
+        invitation.expires_at
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(invitation)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_pending_invitations(db: &PgPool, organization_id: i32) -> Result<Vec<OrganizationInvitation>, AppError> {
+    let invitations = sqlx::query_as!(
+        OrganizationInvitation,
+        r#"
+        SELECT id, organization_id, email, role as "role: OrganizationRole", token_hash,
+               invited_by, expires_at, accepted_at, created_at
+        FROM organization_invitation
+        WHERE organization_id = $1 AND accepted_at IS NULL AND expires_at > now()
+        ORDER BY created_at DESC
+        "#,
+        organization_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(invitations)
+}
+
+/// Bir e-posta adresine gönderilmiş tüm organizasyon davetleri
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_invitations_for_email(db: &PgPool, email: &str) -> Result<Vec<OrganizationInvitation>, AppError> {
+    let invitations = sqlx::query_as!(
+        OrganizationInvitation,
+        r#"
+        SELECT id, organization_id, email, role as "role: OrganizationRole", token_hash,
+               invited_by, expires_at, accepted_at, created_at
+        FROM organization_invitation
+        WHERE lower(email) = lower($1)
+        ORDER BY created_at
+        "#,
+        email
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(invitations)
+}
+
+/// Daveti atomik olarak kabul edilmiş işaretler; davet başka bir e-postaya aitse,
+/// süresi dolmuşsa ya da zaten kullanılmışsa `None` döner
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn accept_invitation<'e>(
+    executor: impl PgExecutor<'e>,
+    token_hash: &str,
+    email: &str,
+) -> Result<Option<OrganizationInvitation>, AppError> {
+    let invitation = sqlx::query_as!(
+        OrganizationInvitation,
+        r#"
+        UPDATE organization_invitation
+        SET accepted_at = now()
+        WHERE token_hash = $1
+          AND accepted_at IS NULL
+          AND expires_at > now()
+          AND lower(email) = lower($2)
+        RETURNING id, organization_id, email, role as "role: OrganizationRole", token_hash,
+                  invited_by, expires_at, accepted_at, created_at
+        "#,
+        token_hash,
+        email
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(invitation)
+}
@@ -0,0 +1,96 @@
+use crate::error::AppError;
+use crate::models::system_config::{SystemConfigChange, SystemConfigEntry};
+use sqlx::PgPool;
+use tracing::instrument;
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list(db: &PgPool) -> Result<Vec<SystemConfigEntry>, AppError> {
+    let entries = sqlx::query_as!(
+        SystemConfigEntry,
+        r#"
+        SELECT key, value, description, updated_by, updated_at
+        FROM system_config
+        ORDER BY key
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(entries)
+}
+
+/// Ayarı yazar ve değişikliği aynı transaction'da geçmişe ekler
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn set(
+    db: &PgPool,
+    key: &str,
+    value: &serde_json::Value,
+    description: Option<&str>,
+    changed_by: i32,
+) -> Result<SystemConfigEntry, AppError> {
+    let mut tx = db.begin().await?;
+    let old_value = sqlx::query_scalar!(
+        r#"SELECT value FROM system_config WHERE key = $1 FOR UPDATE"#,
+        key
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let entry = sqlx::query_as!(
+        SystemConfigEntry,
+        r#"
+        INSERT INTO system_config (key, value, description, updated_by, updated_at)
+        VALUES ($1, $2, $3, $4, now())
+        ON CONFLICT (key) DO UPDATE
+        SET value = EXCLUDED.value,
+            description = COALESCE(EXCLUDED.description, system_config.description),
+            updated_by = EXCLUDED.updated_by,
+            updated_at = now()
+        RETURNING key, value, description, updated_by, updated_at
+        "#,
+        key,
+        value,
+        description,
+        changed_by
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO system_config_history (key, old_value, new_value, changed_by)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        key,
+        old_value,
+        value,
+        changed_by
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(entry)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn history(db: &PgPool, key: Option<&str>, limit: i64) -> Result<Vec<SystemConfigChange>, AppError> {
+    let changes = sqlx::query_as!(
+        SystemConfigChange,
+        r#"
+        SELECT h.id, h.key, h.old_value, h.new_value, h.changed_by,
+               u.public_id as "changed_by_public_id?", h.changed_at
+        FROM system_config_history h
+        LEFT JOIN user_info u ON u.id = h.changed_by
+        WHERE ($1::varchar IS NULL OR h.key = $1)
+        ORDER BY h.id DESC
+        LIMIT $2
+        "#,
+        key,
+        limit
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(changes)
+}
@@ -0,0 +1,121 @@
+use crate::error::AppError;
+use crate::models::system_log::{NewSystemLog, SystemLog};
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+use tracing::instrument;
+
+const SELECT_SYSTEM_LOG: &str = r#"
+    SELECT l.id, l.occurred_at, l.level, l.target, l.message, l.fields, l.request_id,
+           l.user_id, u.public_id as user_public_id
+    FROM system_log l
+    LEFT JOIN user_info u ON u.id = l.user_id
+"#;
+
+#[derive(Debug, Default)]
+pub struct SystemLogFilter {
+    pub level: Option<String>,
+    pub target: Option<String>,
+    pub request_id: Option<String>,
+    pub user_public_id: Option<Uuid>,
+    pub search: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn insert_batch(db: &PgPool, records: &[NewSystemLog]) -> Result<(), AppError> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
+        "INSERT INTO system_log (occurred_at, level, target, message, fields, request_id, user_id) ",
+    );
+    builder.push_values(records, |mut row, record| {
+        row.push_bind(record.occurred_at)
+            .push_bind(record.level)
+            .push_bind(&record.target)
+            .push_bind(&record.message)
+            .push_bind(&record.fields)
+            .push_bind(&record.request_id)
+            .push_bind(record.user_id);
+    });
+    builder.build().execute(db).await?;
+    Ok(())
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn query(db: &PgPool, filter: &SystemLogFilter) -> Result<Vec<SystemLog>, AppError> {
+    let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(SELECT_SYSTEM_LOG);
+    builder.push(" WHERE 1 = 1");
+    if let Some(level) = &filter.level {
+        builder.push(" AND l.level = ").push_bind(level.clone());
+    }
+    if let Some(target) = &filter.target {
+        // Modül önekiyle alt modüllerin logları da gelir
+        builder
+            .push(" AND l.target LIKE ")
+            .push_bind(format!("{}%", escape_like(target)));
+    }
+    if let Some(request_id) = &filter.request_id {
+        builder.push(" AND l.request_id = ").push_bind(request_id.clone());
+    }
+    if let Some(user) = filter.user_public_id {
+        builder.push(" AND u.public_id = ").push_bind(user);
+    }
+    if let Some(search) = &filter.search {
+        builder
+            .push(" AND l.message ILIKE ")
+            .push_bind(format!("%{}%", escape_like(search)));
+    }
+    if let Some(from) = filter.from {
+        builder.push(" AND l.occurred_at >= ").push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        builder.push(" AND l.occurred_at < ").push_bind(to);
+    }
+    builder
+        .push(" ORDER BY l.id DESC LIMIT ")
+        .push_bind(filter.limit)
+        .push(" OFFSET ")
+        .push_bind(filter.offset);
+
+    let logs = builder.build_query_as::<SystemLog>().fetch_all(db).await?;
+    Ok(logs)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn delete_before(db: &PgPool, cutoff: DateTime<Utc>) -> Result<u64, AppError> {
+    let result = sqlx::query!("DELETE FROM system_log WHERE occurred_at < $1", cutoff)
+        .execute(db)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Kullanıcının isteklerinde oluşan log kayıtları (kişisel veri dışa aktarımı için)
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_for_user(db: &PgPool, user_id: i32) -> Result<Vec<SystemLog>, AppError> {
+    let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(SELECT_SYSTEM_LOG);
+    builder
+        .push(" WHERE l.user_id = ")
+        .push_bind(user_id)
+        .push(" ORDER BY l.id ASC");
+
+    let logs = builder.build_query_as::<SystemLog>().fetch_all(db).await?;
+    Ok(logs)
+}
+
+/// Hesap silinirken log kayıtlarının kullanıcıyla bağını koparır
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn detach_user<'e>(executor: impl PgExecutor<'e>, user_id: i32) -> Result<u64, AppError> {
+    let result = sqlx::query!("UPDATE system_log SET user_id = NULL WHERE user_id = $1", user_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,172 @@
+use crate::error::AppError;
+use crate::models::user_session::UserSession;
+use crate::utils::geoip::GeoLocation;
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+use tracing::instrument;
+
+pub struct NewSession<'a> {
+    pub user_id: i32,
+    pub ip_address: Option<&'a str>,
+    pub user_agent: Option<&'a str>,
+    pub device: Option<&'a str>,
+    pub os: Option<&'a str>,
+    pub location: Option<&'a GeoLocation>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn create(db: &PgPool, session: &NewSession<'_>) -> Result<UserSession, AppError> {
+    let session = sqlx::query_as!(
+        UserSession,
+        r#"
+        INSERT INTO user_session (user_id, ip_address, user_agent, device, os, country, city, latitude, longitude, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING id, user_id, ip_address, user_agent, device, os, country, city, latitude, longitude,
+                  created_at, last_seen_at, expires_at, revoked_at
+        "#,
+        session.user_id,
+        session.ip_address,
+        session.user_agent,
+        session.device,
+        session.os,
+        session.location.and_then(|l| l.country.as_deref()),
+        session.location.and_then(|l| l.city.as_deref()),
+        session.location.map(|l| l.latitude),
+        session.location.map(|l| l.longitude),
+        session.expires_at
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(session)
+}
+
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_active_by_user(db: &PgPool, user_id: i32) -> Result<Vec<UserSession>, AppError> {
+    let sessions = sqlx::query_as!(
+        UserSession,
+        r#"
+        SELECT id, user_id, ip_address, user_agent, device, os, country, city, latitude, longitude,
+               created_at, last_seen_at, expires_at, revoked_at
+        FROM user_session
+        WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > now()
+        ORDER BY last_seen_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(sessions)
+}
+
+/// Oturum hâlâ geçerliyse `last_seen_at` alanını günceller ve true döner
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn touch(db: &PgPool, session_id: Uuid, user_id: i32) -> Result<bool, AppError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE user_session
+        SET last_seen_at = now()
+        WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL AND expires_at > now()
+        "#,
+        session_id,
+        user_id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Kullanıcıya ait oturumu iptal eder; oturum yoksa veya zaten iptal edildiyse false döner
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn revoke(db: &PgPool, session_id: Uuid, user_id: i32) -> Result<bool, AppError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE user_session
+        SET revoked_at = now()
+        WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+        "#,
+        session_id,
+        user_id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Kullanıcının `keep` dışındaki tüm açık oturumlarını iptal eder
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn revoke_all_for_user(db: &PgPool, user_id: i32, keep: Option<Uuid>) -> Result<u64, AppError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE user_session
+        SET revoked_at = now()
+        WHERE user_id = $1 AND revoked_at IS NULL AND ($2::uuid IS NULL OR id <> $2)
+        "#,
+        user_id,
+        keep
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Hesap silme sırasında IP ve cihaz bilgileri dahil tüm oturum kayıtlarını kaldırır
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn delete_for_user<'e>(db: impl PgExecutor<'e>, user_id: i32) -> Result<u64, AppError> {
+    let result = sqlx::query!(
+        r#"DELETE FROM user_session WHERE user_id = $1"#,
+        user_id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Dışa aktarma için kullanıcının iptal edilmiş olanlar dahil tüm oturumları
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_all_by_user(db: &PgPool, user_id: i32) -> Result<Vec<UserSession>, AppError> {
+    let sessions = sqlx::query_as!(
+        UserSession,
+        r#"
+        SELECT id, user_id, ip_address, user_agent, device, os, country, city, latitude, longitude,
+               created_at, last_seen_at, expires_at, revoked_at
+        FROM user_session
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(sessions)
+}
+
+/// Şüpheli giriş tespiti için kullanıcının en son açılan oturumları (iptal edilenler dahil)
+#[instrument(skip_all, fields(db.system = "postgresql"))]
+pub async fn list_recent_by_user(db: &PgPool, user_id: i32, limit: i64) -> Result<Vec<UserSession>, AppError> {
+    let sessions = sqlx::query_as!(
+        UserSession,
+        r#"
+        SELECT id, user_id, ip_address, user_agent, device, os, country, city, latitude, longitude,
+               created_at, last_seen_at, expires_at, revoked_at
+        FROM user_session
+        WHERE user_id = $1
+        ORDER BY last_seen_at DESC
+        LIMIT $2
+        "#,
+        user_id,
+        limit
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(sessions)
+}
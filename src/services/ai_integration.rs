@@ -0,0 +1,192 @@
+// src/services/ai_integration.rs
+use std::{sync::Arc, time::Duration};
+
+use futures::future::BoxFuture;
+use tracing::error;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    config::AppConfig,
+    error::AppError,
+    models::dto::completion::{ChatMessage, ChatRole},
+    utils::telemetry,
+};
+
+/// Sağlayıcıdan dönen yanıt ve faturalandırılacak token sayıları
+#[derive(Debug, Clone)]
+pub struct AiCompletion {
+    pub content: String,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+impl AiCompletion {
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+/// Yapay zekâ sağlayıcıları için ortak arayüz
+pub trait AiProvider: Send + Sync {
+    /// Metriklerde ve loglarda kullanılan sağlayıcı adı
+    fn name(&self) -> &'static str;
+
+    fn complete<'a>(
+        &'a self,
+        model: &'a str,
+        messages: &'a [ChatMessage],
+        max_tokens: Option<u32>,
+    ) -> BoxFuture<'a, Result<AiCompletion, AppError>>;
+}
+
+/// Geliştirme ortamı için: son kullanıcı mesajını geri döner, token sayısını kelimelerden tahmin eder
+pub struct EchoAiProvider;
+
+fn estimate_tokens(text: &str) -> u64 {
+    text.split_whitespace().count() as u64
+}
+
+impl AiProvider for EchoAiProvider {
+    fn name(&self) -> &'static str {
+        "echo"
+    }
+
+    fn complete<'a>(
+        &'a self,
+        _model: &'a str,
+        messages: &'a [ChatMessage],
+        max_tokens: Option<u32>,
+    ) -> BoxFuture<'a, Result<AiCompletion, AppError>> {
+        Box::pin(async move {
+            let prompt_tokens = messages.iter().map(|m| estimate_tokens(&m.content)).sum();
+            let last = messages
+                .iter()
+                .rev()
+                .find(|m| m.role == ChatRole::User)
+                .map(|m| m.content.as_str())
+                .unwrap_or_default();
+            let limit = max_tokens.map_or(usize::MAX, |n| n as usize);
+            let content = last.split_whitespace().take(limit).collect::<Vec<_>>().join(" ");
+
+            Ok(AiCompletion {
+                completion_tokens: estimate_tokens(&content),
+                content,
+                prompt_tokens,
+            })
+        })
+    }
+}
+
+/// OpenAI uyumlu `/chat/completions` API'lerine istek atar
+pub struct OpenAiCompatibleProvider {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+}
+
+impl OpenAiCompatibleProvider {
+    pub fn new(url: String, token: Option<String>, timeout: Duration) -> Result<Self, AppError> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| AppError::internal_error(&format!("AI provider client could not be built: {}", e)))?;
+        Ok(Self {
+            client,
+            url: format!("{}/chat/completions", url.trim_end_matches('/')),
+            token,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatCompletionChoice>,
+    usage: ChatCompletionUsage,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionChoice {
+    message: ChatCompletionMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+}
+
+impl AiProvider for OpenAiCompatibleProvider {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    fn complete<'a>(
+        &'a self,
+        model: &'a str,
+        messages: &'a [ChatMessage],
+        max_tokens: Option<u32>,
+    ) -> BoxFuture<'a, Result<AiCompletion, AppError>> {
+        Box::pin(async move {
+            let mut body = json!({ "model": model, "messages": messages });
+            if let Some(max_tokens) = max_tokens {
+                body["max_tokens"] = json!(max_tokens);
+            }
+            let mut request = self.client.post(&self.url).json(&body);
+            if let Some(token) = &self.token {
+                request = request.bearer_auth(token);
+            }
+
+            let mut headers = reqwest::header::HeaderMap::new();
+            telemetry::inject_trace_headers(&mut headers);
+            let response = request.headers(headers).send().await.map_err(|e| {
+                error!("AI provider request failed: {}", e);
+                AppError::internal_error("AI provider unreachable")
+            })?;
+            if !response.status().is_success() {
+                error!("AI provider returned status {}", response.status());
+                return Err(AppError::internal_error("AI provider request failed"));
+            }
+            let completion: ChatCompletionResponse = response.json().await.map_err(|e| {
+                error!("AI provider returned an unexpected body: {}", e);
+                AppError::internal_error("AI provider request failed")
+            })?;
+
+            Ok(AiCompletion {
+                content: completion
+                    .choices
+                    .into_iter()
+                    .next()
+                    .and_then(|c| c.message.content)
+                    .unwrap_or_default(),
+                prompt_tokens: completion.usage.prompt_tokens,
+                completion_tokens: completion.usage.completion_tokens,
+            })
+        })
+    }
+}
+
+/// Yapılandırmaya göre yapay zekâ sağlayıcısını seçer
+pub fn ai_provider_from_config(config: &AppConfig) -> Result<Arc<dyn AiProvider>, AppError> {
+    match config.ai_provider.as_str() {
+        "echo" => Ok(Arc::new(EchoAiProvider)),
+        "openai" => {
+            let url = config
+                .ai_api_url
+                .clone()
+                .ok_or_else(|| AppError::internal_error("AI_API_URL is required when AI_PROVIDER=openai"))?;
+            Ok(Arc::new(OpenAiCompatibleProvider::new(
+                url,
+                config.ai_api_token.clone(),
+                Duration::from_secs(config.ai_request_timeout_secs),
+            )?))
+        }
+        other => Err(AppError::internal_error(&format!("Unknown AI provider: {}", other))),
+    }
+}
@@ -0,0 +1,203 @@
+// src/services/audit.rs
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use tracing::error;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::audit_event::{AuditAction, AuditEvent},
+    repositories::audit_event::{self, AuditEventFilter, NewAuditEvent},
+    utils::{client_info::ClientInfo, sha256::sha256_hash},
+};
+
+/// Zincirin ilk kaydının `prev_hash` değeri
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+const VERIFY_BATCH_SIZE: i64 = 1000;
+const MAX_PAGE_SIZE: i64 = 500;
+
+/// Denetim kaydına yazılacak tek bir güvenlik olayı
+pub struct AuditEntry<'a> {
+    pub actor_id: Option<i32>,
+    pub target_user_id: Option<i32>,
+    pub action: AuditAction,
+    pub client: &'a ClientInfo,
+    pub diff: Value,
+}
+
+impl<'a> AuditEntry<'a> {
+    pub fn new(action: AuditAction, client: &'a ClientInfo) -> Self {
+        Self {
+            actor_id: None,
+            target_user_id: None,
+            action,
+            client,
+            diff: Value::Object(Default::default()),
+        }
+    }
+
+    /// Kimliği doğrulanmış kullanıcının kendi hesabı üzerindeki işlem; taklit
+    /// oturumlarında aktör olarak yönetici yazılır
+    pub fn for_user(action: AuditAction, auth_user: &'a AuthenticatedUser) -> Self {
+        Self::new(action, &auth_user.client)
+            .actor(auth_user.actor_id().unwrap_or(auth_user.user_id))
+            .target(auth_user.user_id)
+    }
+
+    pub fn actor(mut self, actor_id: i32) -> Self {
+        self.actor_id = Some(actor_id);
+        self
+    }
+
+    pub fn target(mut self, target_user_id: i32) -> Self {
+        self.target_user_id = Some(target_user_id);
+        self
+    }
+
+    pub fn diff(mut self, diff: Value) -> Self {
+        self.diff = diff;
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChainVerification {
+    pub valid: bool,
+    pub checked: u64,
+    /// Zincirin ilk bozulduğu kaydın id'si
+    pub first_invalid_id: Option<i64>,
+}
+
+/// Olayı zincire ekler; denetim kaydı yazılamazsa asıl isteği başarısız saymaz. Düşürülen kayıt
+/// loglanır ve `audit_write_failures_total` sayacına eklenir; alarm bu sayaç üzerinden kurulur.
+pub async fn record(app_state: &web::Data<AppState>, entry: AuditEntry<'_>) {
+    let action = entry.action.as_str();
+    if let Err(e) = append(app_state, entry).await {
+        app_state.metrics.audit_write_failed(action);
+        error!("Dropped audit event {}: {}", action, e);
+    }
+}
+
+async fn append(app_state: &web::Data<AppState>, entry: AuditEntry<'_>) -> Result<i64, AppError> {
+    // Postgres mikro saniye hassasiyetinde sakladığı için hash de aynı hassasiyetle hesaplanır
+    let now = Utc::now();
+    let occurred_at = DateTime::from_timestamp_micros(now.timestamp_micros()).unwrap_or(now);
+
+    let mut tx = app_state.db.begin().await?;
+    audit_event::lock_chain(&mut tx).await?;
+    let prev_hash = audit_event::last_hash(&mut tx)
+        .await?
+        .unwrap_or_else(|| GENESIS_HASH.to_string());
+
+    let hash = chain_hash(
+        &prev_hash,
+        occurred_at,
+        entry.actor_id,
+        entry.target_user_id,
+        entry.action.as_str(),
+        entry.client.ip_address.as_deref(),
+        entry.client.user_agent.as_deref(),
+        &entry.diff,
+    );
+    let id = audit_event::insert(
+        &mut tx,
+        &NewAuditEvent {
+            occurred_at,
+            actor_id: entry.actor_id,
+            target_user_id: entry.target_user_id,
+            action: entry.action.as_str(),
+            ip_address: entry.client.ip_address.as_deref(),
+            user_agent: entry.client.user_agent.as_deref(),
+            diff: &entry.diff,
+            prev_hash: &prev_hash,
+            hash: &hash,
+        },
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(id)
+}
+
+pub async fn list_events(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+    mut filter: AuditEventFilter,
+) -> Result<Vec<AuditEvent>, AppError> {
+    admin.require_admin()?;
+    admin.deny_if_impersonated("viewing the audit log")?;
+
+    filter.limit = filter.limit.clamp(1, MAX_PAGE_SIZE);
+    filter.offset = filter.offset.max(0);
+    audit_event::query(&app_state.db, &filter).await
+}
+
+/// Zinciri baştan sona yeniden hesaplar; araya eklenen, silinen ya da değiştirilen kayıtları yakalar
+pub async fn verify_chain(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+) -> Result<ChainVerification, AppError> {
+    admin.require_admin()?;
+    admin.deny_if_impersonated("verifying the audit log")?;
+
+    let mut prev_hash = GENESIS_HASH.to_string();
+    let mut last_id = 0;
+    let mut checked = 0;
+
+    loop {
+        let batch = audit_event::list_after(&app_state.db, last_id, VERIFY_BATCH_SIZE).await?;
+        if batch.is_empty() {
+            break;
+        }
+        for event in &batch {
+            let expected = chain_hash(
+                &prev_hash,
+                event.occurred_at,
+                event.actor_id,
+                event.target_user_id,
+                &event.action,
+                event.ip_address.as_deref(),
+                event.user_agent.as_deref(),
+                &event.diff,
+            );
+            if event.prev_hash != prev_hash || event.hash != expected {
+                return Ok(ChainVerification { valid: false, checked, first_invalid_id: Some(event.id) });
+            }
+            checked += 1;
+            prev_hash = event.hash.clone();
+            last_id = event.id;
+        }
+    }
+
+    Ok(ChainVerification { valid: true, checked, first_invalid_id: None })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn chain_hash(
+    prev_hash: &str,
+    occurred_at: DateTime<Utc>,
+    actor_id: Option<i32>,
+    target_user_id: Option<i32>,
+    action: &str,
+    ip_address: Option<&str>,
+    user_agent: Option<&str>,
+    diff: &Value,
+) -> String {
+    let opt_id = |id: Option<i32>| id.map(|v| v.to_string()).unwrap_or_default();
+    let payload = [
+        prev_hash.to_string(),
+        occurred_at.timestamp_micros().to_string(),
+        opt_id(actor_id),
+        opt_id(target_user_id),
+        action.to_string(),
+        ip_address.unwrap_or_default().to_string(),
+        user_agent.unwrap_or_default().to_string(),
+        diff.to_string(),
+    ]
+    .join("|");
+
+    sha256_hash(&payload)
+}
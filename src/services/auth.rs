@@ -1,13 +1,13 @@
-use std::sync::Arc;
 // src/services/auth.rs
 use actix_web::web;
 use chrono::Utc;
-use log::{debug, error};
+use tracing::{debug, error};
 use validator::Validate;
 use uuid::Uuid;
 use crate::{
-    app_state::AppState, error::AppError, models::{dto::auth::{AuthResponse, Claims, LoginRequest, RegisterRequest },
-    user::{User, UserRole, UserSchema}}, repositories::{self, auth::find_by_username_or_email}, utils::{jwt, password, sha256::sha256_hash, uudi_convert_32byte}
+    app_state::AppState, error::{AppError, ErrorCode}, extension::auth::AuthenticatedUser, models::{audit_event::AuditAction, dto::auth::{AuthResponse, Claims, LoginRequest, RegisterRequest, TokenType },
+    user::{User, UserRole, UserSchema}}, repositories::{self, auth::find_by_username_or_email, user_session::NewSession},
+    services::{audit::{self, AuditEntry}, auth_cache, login_risk}, utils::{client_info::ClientInfo, jwt, metrics::AuthEvent, password, phone, sha256::{keyed_hash, sha256_hash}}
 
 };
 use deadpool_redis::redis::AsyncCommands; // Redis komutları için
@@ -15,43 +15,42 @@ use deadpool_redis::redis::AsyncCommands; // Redis komutları için
 pub async fn register_user(
     app_state: &web::Data<AppState>,
     req: RegisterRequest,
+    client: &ClientInfo,
 ) -> Result<User, AppError> {
-    req.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
-    let hashed_password = password::hash_password(&req.password)?;
+    req.validate()?;
+    app_state
+        .password_policy
+        .check(&req.password, &[&req.username, &req.email, &req.full_name])
+        .await?;
+    let hashed_password = password::hash_password(&req.password, &app_state.password_hashing)?;
     
-    let role = if let Some(admin_code) = &req.admin_code {
-        // Güvenli bir karşılaştırma için constant-time karşılaştırma kullan
-        let code_bytes = admin_code.as_bytes();
-        let stored_bytes = app_state.config.admin_registration_code.as_bytes();
-
-        // Farklı uzunluklar varsa hemen ret döndür
-        if code_bytes.len() != stored_bytes.len() {
-            return Err(AppError::Unauthorized("Geçersiz admin kodu.".to_string()));
+    // Davet kodu varsa kullanıcı oluşturma ile aynı transaction'da tüketilir;
+    // kayıt başarısız olursa davet hakkı da geri alınır
+    let mut tx = app_state.db.begin().await?;
+    let invited = req.invite_code.as_deref().is_some_and(|c| !c.trim().is_empty());
+    let role = match req.invite_code.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        Some(code) => repositories::invitation::redeem(&mut *tx, &sha256_hash(&code.to_uppercase()), &req.email)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Invalid or expired invitation code.".to_string()).with_code(ErrorCode::InvalidInvitation))?,
+        None if !app_state.system_config.current().registration_open => {
+            return Err(AppError::Forbidden("Registration requires an invitation code.".to_string()));
         }
-
-        // XOR ile karşılaştırma - zamanı sabit tutmak için 
-        // tüm karakterleri her durumda kontrol eder
-        let mut result = 0u8;
-        for (a, b) in code_bytes.iter().zip(stored_bytes.iter()) {
-            result |= a ^ b; // XOR - sadece baytlar eşitse 0 olur
-        }
-
-        if result == 0 {
-            UserRole::Admin
-        } else {
-            return Err(AppError::Unauthorized("Geçersiz admin kodu.".to_string()));
-        }
-    } else {
-        UserRole::User
+        None => UserRole::User,
     };
 
+    // id ve public_id veritabanı tarafından atanır
     let user = UserSchema {
-        id: uudi_convert_32byte::convert_i32(Uuid::new_v4()),
+        id: 0,
+        public_id: Uuid::nil(),
         full_name: req.full_name,
         username: req.username,
         email: req.email,
         password_hash: hashed_password.clone(),
-        phone_number: req.phone_number,
+        phone_number: req
+            .phone_number
+            .as_deref()
+            .map(|p| phone::normalize_e164(p, &app_state.config.default_phone_country_code))
+            .transpose()?,
         token_balance: None,
         user_role: role.clone(),  // Clone the role before first use
         subscription_expries: None,
@@ -64,47 +63,152 @@ pub async fn register_user(
     };
     
     let user = repositories::auth::create(
-        &app_state.db,
+        &mut *tx,
         &user,
         &hashed_password,
         role
     )
     .await
-    .map_err(|e| {
-        // Try to match the error if it's an AppError::Database variant
-        if let AppError::SqlxError(sqlx_err) = &e {
-            if let sqlx::Error::Database(db_err) = sqlx_err {
-                if db_err.is_unique_violation() {
-                    return AppError::Conflict("Username or email already exist.".to_string());
-                }
-            }
-        }
-        e 
-    })?;
+    .map_err(map_unique_violation)?;
+    tx.commit().await?;
+
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::Register, client)
+            .actor(user.id)
+            .target(user.id)
+            .diff(serde_json::json!({ "role": user.user_role.to_string(), "invited": invited })),
+    )
+    .await;
 
     Ok(user)
 }
 
+/// Kullanıcı adı / e-posta benzersizlik ihlallerini 409 Conflict'e çevirir
+pub(crate) fn map_unique_violation(e: AppError) -> AppError {
+    if let AppError::SqlxError(sqlx::Error::Database(db_err)) = &e {
+        if db_err.is_unique_violation() {
+            // İstemcinin doğru alanı işaretleyebilmesi için ihlal edilen index'e göre kod seçilir
+            let code = match db_err.constraint() {
+                Some("user_info_email_key") => ErrorCode::EmailTaken,
+                Some("user_info_username_key") => ErrorCode::UsernameTaken,
+                _ => ErrorCode::Conflict,
+            };
+            return AppError::Conflict("Username or email already exist.".to_string()).with_code(code);
+        }
+    }
+    e
+}
+
 pub async fn login_user(
     app_state: &web::Data<AppState>,
-    req: LoginRequest
+    req: LoginRequest,
+    client: &ClientInfo,
 ) -> Result<AuthResponse,AppError> {
-    req.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
+    req.validate()?;
 
-    let user = find_by_username_or_email(&app_state.db, &req.email)
-        .await
-        .map_err(|_| AppError::NotFound("User not found".to_string()))?;
-    if !password::verify_password(&req.password, &user.password_hash)? {
-        return Err(AppError::Unauthorized("Invalid credentials".to_string()));
+    let user = match find_by_username_or_email(&app_state.db, &req.email).await {
+        Ok(user) => user,
+        Err(_) => {
+            app_state.metrics.auth_event(AuthEvent::LoginFailure);
+            audit::record(
+                app_state,
+                AuditEntry::new(AuditAction::LoginFailure, client)
+                    // Girilen tanımlayıcı kişisel veri olabilir; denetim kaydına yalnızca anahtarlı özeti yazılır
+                    .diff(serde_json::json!({
+                        "identifier_hash": keyed_hash(&req.email.trim().to_lowercase(), &app_state.jwt_secret),
+                        "reason": "unknown_user",
+                    })),
+            )
+            .await;
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+    };
+    if user.is_active == Some(false) {
+        app_state.metrics.auth_event(AuthEvent::LoginFailure);
+        audit::record(
+            app_state,
+            AuditEntry::new(AuditAction::LoginFailure, client)
+                .target(user.id)
+                .diff(serde_json::json!({ "reason": "inactive" })),
+        )
+        .await;
+        return Err(AppError::Unauthorized("Invalid credentials".to_string()).with_code(ErrorCode::InvalidCredentials));
+    }
+    let verification = password::verify_password_with_rehash(&req.password, &user.password_hash, &app_state.password_hashing)?;
+    if !verification.valid {
+        app_state.metrics.auth_event(AuthEvent::LoginFailure);
+        audit::record(
+            app_state,
+            AuditEntry::new(AuditAction::LoginFailure, client)
+                .target(user.id)
+                .diff(serde_json::json!({ "reason": "invalid_password" })),
+        )
+        .await;
+        return Err(AppError::Unauthorized("Invalid credentials".to_string()).with_code(ErrorCode::InvalidCredentials));
+    }
+
+    // "Bu ben değildim" bildiriminden sonra parola sıfırlanana kadar parola ile giriş yapılamaz
+    if repositories::auth::password_reset_required(&app_state.db, user.id).await? {
+        return Err(AppError::Forbidden("Password reset required. Check your email for a reset link.".to_string()));
+    }
+
+    // Eski parametrelerle ya da başka algoritmayla üretilmiş hash'i şeffafça yenile
+    if verification.needs_rehash {
+        match password::hash_password(&req.password, &app_state.password_hashing) {
+            Ok(new_hash) => match repositories::auth::update_password(&app_state.db, user.id, &new_hash).await {
+                Ok(()) => debug!("Password hash upgraded for user_id: {}", user.id),
+                Err(e) => error!("Failed to store rehashed password for user_id {}: {}", user.id, e),
+            },
+            Err(e) => error!("Failed to rehash password for user_id {}: {}", user.id, e),
+        }
     }
 
-    let token = jwt::create_jwt(user.id, user.user_role.to_string(), &app_state.jwt_secret)?;
+    // Geçmiş oturumlarla karşılaştırma yeni oturum açılmadan yapılır
+    let risk = login_risk::assess(app_state, &user, client).await?;
+    let response = issue_session_token(app_state, &user, client).await?;
+    app_state.metrics.auth_event(AuthEvent::LoginSuccess);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::LoginSuccess, client).actor(user.id).target(user.id),
+    )
+    .await;
+    if let Some(risk) = risk {
+        login_risk::spawn_alert(app_state, user, client.clone(), risk);
+    }
+
+    Ok(response)
+}
+
+/// Kullanıcı için yeni bir oturum açar ve bu oturuma bağlı access token üretir
+pub(crate) async fn issue_session_token(
+    app_state: &web::Data<AppState>,
+    user: &User,
+    client: &ClientInfo,
+) -> Result<AuthResponse, AppError> {
+    let claims = Claims::from_user(user, TokenType::Access, &app_state.config);
+    let location = client.ip_address.as_deref().and_then(|ip| app_state.geoip.lookup(ip));
+    let session = repositories::user_session::create(
+        &app_state.db,
+        &NewSession {
+            user_id: user.id,
+            ip_address: client.ip_address.as_deref(),
+            user_agent: client.user_agent.as_deref(),
+            device: client.device(),
+            os: client.os(),
+            location: location.as_ref(),
+            expires_at: chrono::DateTime::from_timestamp(claims.exp as i64, 0).unwrap_or_else(Utc::now),
+        },
+    )
+    .await?;
+    let token = jwt::encode_claims(&claims.with_session(session.id), &app_state.jwt_secret)?;
 
     Ok(AuthResponse {
         token,
-        user_id: user.id.clone(),
+        user_id: user.public_id,
         username: user.username.clone(),
         role: user.user_role.clone().to_string(),
+        csrf_token: None,
     })
 }
 
@@ -113,55 +217,51 @@ pub async fn login_user(
 
 pub async fn logout_user(
     app_state: &web::Data<AppState>,
-    claims: &Claims,
-    token: &str,
+    auth_user: &AuthenticatedUser,
+) -> Result<(), AppError> {
+    if let Some(session_id) = auth_user.session_id {
+        repositories::user_session::revoke(&app_state.db, session_id, auth_user.user_id).await?;
+        auth_cache::forget_sessions(&mut app_state.redis().await?, auth_user.user_id).await?;
+    }
+
+    audit::record(
+        app_state,
+        AuditEntry::for_user(AuditAction::Logout, auth_user).diff(serde_json::json!({ "session_id": auth_user.session_id })),
+    )
+    .await;
+
+    revoke_token(app_state, auth_user).await
+}
+
+/// İsteği yapan access token'ı (oturumu kapatmadan) kara listeye alır
+pub(crate) async fn revoke_token(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
 ) -> Result<(), AppError> {
-    let mut conn = app_state.redis_pool.get().await
+    let claims = &auth_user.claims;
+    let mut conn = app_state.redis().await
         .map_err(|e| {
             error!("Redis connection error: {}", e);
             AppError::RedisError(format!("Redis connection error: {}", e))
         })?;
 
-    debug!("Attempting to blacklist token for user_id: {}", claims.user_id);
-    
-    let hashed_token = sha256_hash(token);
-  
-    let blacklist_key = format!("blacklisted_jwt:{}", hashed_token);
-    
-    // SET komutunu çalıştır
-    match conn.set::<_, _, ()>(&blacklist_key, claims.user_id).await {
-        Ok(_) => debug!("Token set in Redis blacklist"),
-        Err(e) => {
-            error!("Failed to set token in Redis: {}", e);
-            return Err(AppError::RedisError(format!("Failed to set token in Redis: {}", e)));
-        }
-    }
-    // Token'ın sona erme süresini hesaplayalım
-    let now = chrono::Utc::now().timestamp() as usize;
-    let ttl = claims.exp - now;
-    
-    // TTL ayarla (token süresi kadar)
-    if ttl > 0 {
-        match conn.expire::<_, bool>(&blacklist_key, ttl as i64).await {
-            Ok(_) => debug!("Token TTL set to {} seconds", ttl),
-            Err(e) => {
-                error!("Failed to set token expiry in Redis: {}", e);
-                return Err(AppError::RedisError(format!("Failed to set token expiry: {}", e)));
-            }
-        }
-    }
-    
-    // İşlemleri kontrol edelim
-    let exists: bool = conn.exists(&blacklist_key).await
-        .map_err(|e| AppError::RedisError(format!("Redis error: {}", e)))?;
-    
-    if !exists {
-        error!("Token was not added to blacklist!");
-        return Err(AppError::InternalServerError("Token blacklisting failed".to_string()));
+    debug!("Attempting to revoke token {} for user_id: {}", claims.jti, auth_user.user_id);
+
+    // Token'ın kalan süresi kadar jti kara listede tutulur; süresi dolan token zaten geçersizdir
+    let ttl = claims.remaining_secs();
+    if ttl == 0 {
+        debug!("Token already expired, nothing to revoke");
+        return Ok(());
     }
-    
-    debug!("Successfully blacklisted token for user_id: {}", claims.user_id);
-    Ok(())
-}
 
+    let revoked_key = jwt::revoked_jti_key(&claims.jti);
+    conn.set_ex::<_, _, ()>(&revoked_key, auth_user.user_id, ttl)
+        .await
+        .map_err(|e| {
+            error!("Failed to revoke token in Redis: {}", e);
+            AppError::RedisError(format!("Failed to revoke token: {}", e))
+        })?;
 
+    debug!("Successfully revoked token {} for user_id: {}", claims.jti, auth_user.user_id);
+    Ok(())
+}
@@ -0,0 +1,75 @@
+// src/services/auth_cache.rs
+use deadpool_redis::redis::AsyncCommands;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{error::AppError, repositories, utils::traced_redis::TracedConnection};
+
+/// Dış kimlik → dahili id eşlemesinin önbellekte kalma süresi
+const INTERNAL_ID_TTL_SECS: u64 = 5 * 60;
+/// Doğrulanmış oturumların önbellekte kalma süresi; `last_seen_at` en fazla bu kadar geri kalır
+const SESSION_TTL_SECS: i64 = 60;
+
+fn internal_id_key(public_id: Uuid) -> String {
+    format!("user_internal_id:{}", public_id)
+}
+
+/// Kullanıcının doğrulanmış oturum id'lerini tutan hash; tek anahtar silinerek tüm oturumlar düşürülür
+fn sessions_key(user_id: i32) -> String {
+    format!("active_sessions:{}", user_id)
+}
+
+/// Token'daki dış kimliği dahili id'ye çevirir; her istekte veritabanına gitmemek için
+/// sonuç kısa süre Redis'te tutulur. Hesap pasifleştirildiğinde `forget_user` çağrılmalıdır.
+pub async fn internal_id(
+    conn: &mut TracedConnection,
+    db: &PgPool,
+    public_id: Uuid,
+) -> Result<Option<i32>, AppError> {
+    let key = internal_id_key(public_id);
+    if let Some(id) = conn.get::<_, Option<i32>>(&key).await? {
+        return Ok(Some(id));
+    }
+    let id = repositories::auth::find_internal_id(db, public_id).await?;
+    if let Some(id) = id {
+        conn.set_ex::<_, _, ()>(&key, id, INTERNAL_ID_TTL_SECS).await?;
+    }
+    Ok(id)
+}
+
+/// Oturumun iptal edilmediğini doğrular ve `last_seen_at`'i günceller. Doğrulanan oturum
+/// `SESSION_TTL_SECS` boyunca önbellekten kabul edilir; iptal eden kod `forget_sessions` çağırır.
+pub async fn touch_session(
+    conn: &mut TracedConnection,
+    db: &PgPool,
+    session_id: Uuid,
+    user_id: i32,
+) -> Result<bool, AppError> {
+    let key = sessions_key(user_id);
+    let field = session_id.to_string();
+    if conn.hexists(&key, &field).await? {
+        return Ok(true);
+    }
+    if !repositories::user_session::touch(db, session_id, user_id).await? {
+        return Ok(false);
+    }
+    // Süre yalnızca hash ilk oluşturulduğunda verilir; böylece önbellek en geç bu sürede boşalır
+    let ttl: i64 = conn.ttl(&key).await?;
+    conn.hset::<_, _, _, ()>(&key, &field, 1).await?;
+    if ttl < 0 {
+        conn.expire::<_, ()>(&key, SESSION_TTL_SECS).await?;
+    }
+    Ok(true)
+}
+
+/// Kullanıcının önbellekteki tüm oturumlarını düşürür; oturum iptalinden sonra çağrılır
+pub async fn forget_sessions(conn: &mut TracedConnection, user_id: i32) -> Result<(), AppError> {
+    conn.del::<_, ()>(sessions_key(user_id)).await?;
+    Ok(())
+}
+
+/// Hesap pasifleştirildiğinde kimlik ve oturum önbelleğini temizler
+pub async fn forget_user(conn: &mut TracedConnection, public_id: Uuid, user_id: i32) -> Result<(), AppError> {
+    conn.del::<_, ()>(internal_id_key(public_id)).await?;
+    forget_sessions(conn, user_id).await
+}
@@ -0,0 +1,137 @@
+// src/services/completion.rs
+use std::time::Instant;
+
+use actix_web::web;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use tracing::{info, Instrument};
+use validator::Validate;
+
+use crate::{
+    app_state::AppState,
+    error::{AppError, ErrorCode},
+    extension::auth::AuthenticatedUser,
+    models::dto::completion::{CompletionRequest, CompletionResponse},
+    repositories,
+    utils::telemetry,
+};
+
+/// Aktif organizasyon varsa harcama onun havuzundan ve üyenin limitinden, yoksa kişisel bakiyeden düşülür
+enum Payer {
+    Organization { id: i32, public_id: uuid::Uuid },
+    Personal,
+}
+
+/// Sağlayıcıyı çağırmadan önce bakiyenin ve harcama limitinin tükenmediğini doğrular.
+/// Kesin kontrol düşüm sırasında atomik olarak yapılır; bu kontrol boşa sağlayıcı çağrısını önler.
+async fn resolve_payer(app_state: &web::Data<AppState>, auth_user: &AuthenticatedUser) -> Result<Payer, AppError> {
+    let Some(active) = &auth_user.organization else {
+        let user = repositories::auth::find_by_id(&app_state.db, auth_user.user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("user"))?;
+        if user.token_balance.unwrap_or_default() <= 0.0 {
+            return Err(AppError::forbidden("insufficient token balance").with_code(ErrorCode::InsufficientTokens));
+        }
+        return Ok(Payer::Personal);
+    };
+
+    let organization = repositories::organization::find_by_public_id(&app_state.db, active.public_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("organization"))?;
+    let member = repositories::organization::find_member(&app_state.db, organization.id, auth_user.user_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("organization"))?;
+    if member.spending_cap.as_ref().is_some_and(|cap| member.spent >= *cap) {
+        return Err(AppError::forbidden("organization spending cap reached").with_code(ErrorCode::SpendingCapReached));
+    }
+    if organization.token_balance <= BigDecimal::from(0) {
+        return Err(AppError::forbidden("insufficient organization token balance").with_code(ErrorCode::InsufficientTokens));
+    }
+    Ok(Payer::Organization {
+        id: organization.id,
+        public_id: organization.public_id,
+    })
+}
+
+/// Üye limiti ve havuz bakiyesi aynı işlemde düşülür; ikisinden biri yetmezse hiçbir şey değişmez
+async fn charge_organization(
+    app_state: &web::Data<AppState>,
+    organization_id: i32,
+    user_id: i32,
+    amount: &BigDecimal,
+) -> Result<BigDecimal, AppError> {
+    let mut tx = app_state.db.begin().await?;
+    if !repositories::organization::add_member_spend(&mut *tx, organization_id, user_id, amount).await? {
+        return Err(AppError::forbidden("organization spending cap reached").with_code(ErrorCode::SpendingCapReached));
+    }
+    let balance = repositories::organization::debit(&mut *tx, organization_id, amount)
+        .await?
+        .ok_or_else(|| AppError::forbidden("insufficient organization token balance").with_code(ErrorCode::InsufficientTokens))?;
+    tx.commit().await?;
+
+    Ok(balance)
+}
+
+/// Sağlayıcıya tamamlama isteği gönderir ve kullanılan token'ları faturalandırır.
+/// Düşüm başarısız olursa yanıt istemciye dönmez.
+pub async fn complete(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    req: CompletionRequest,
+) -> Result<CompletionResponse, AppError> {
+    auth_user.deny_if_impersonated("spending tokens")?;
+    req.validate()?;
+
+    let payer = resolve_payer(app_state, auth_user).await?;
+    let provider = app_state.ai_provider.name();
+    let span = telemetry::ai_provider_span(provider, &req.model);
+    let started = Instant::now();
+    let result = app_state
+        .ai_provider
+        .complete(&req.model, &req.messages, req.max_tokens)
+        .instrument(span.clone())
+        .await;
+    let (prompt_tokens, completion_tokens) = result
+        .as_ref()
+        .map_or((0, 0), |c| (c.prompt_tokens, c.completion_tokens));
+    span.record("ai.prompt_tokens", prompt_tokens);
+    span.record("ai.completion_tokens", completion_tokens);
+    app_state.metrics.observe_ai_request(
+        provider,
+        &req.model,
+        result.is_ok(),
+        started.elapsed(),
+        prompt_tokens,
+        completion_tokens,
+    );
+    let completion = result?;
+
+    let amount = BigDecimal::from(completion.total_tokens());
+    let (balance, organization_id) = match payer {
+        Payer::Organization { id, public_id } => {
+            (charge_organization(app_state, id, auth_user.user_id, &amount).await?, Some(public_id))
+        }
+        Payer::Personal => {
+            let balance = repositories::auth::debit_tokens(&app_state.db, auth_user.user_id, &amount)
+                .await?
+                .ok_or_else(|| AppError::forbidden("insufficient token balance").with_code(ErrorCode::InsufficientTokens))?;
+            (balance, None)
+        }
+    };
+    info!(
+        "Completion on {}/{} charged {} tokens to user_id: {} (organization: {:?})",
+        provider,
+        req.model,
+        amount,
+        auth_user.user_id,
+        organization_id
+    );
+
+    Ok(CompletionResponse {
+        model: req.model,
+        content: completion.content,
+        prompt_tokens: completion.prompt_tokens,
+        completion_tokens: completion.completion_tokens,
+        organization_id,
+        balance: balance.to_f64().unwrap_or(0.0),
+    })
+}
@@ -0,0 +1,76 @@
+// src/services/config_sync.rs
+use std::time::Duration;
+
+use actix_web::web;
+use deadpool_redis::redis::{self, AsyncCommands};
+use futures::StreamExt;
+use tracing::{error, info};
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    services::{feature_flag, system_config},
+};
+
+/// Abonelik koptuğunda yeniden bağlanmadan önce beklenen süre
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Önbelleği değişen örneğin diğer örneklere haber vermesi; mesaj değişen anahtardır
+pub async fn publish(app_state: &web::Data<AppState>, channel: &str, key: &str) -> Result<(), AppError> {
+    let mut conn = app_state.redis().await?;
+    conn.publish::<_, _, ()>(channel, key).await?;
+    Ok(())
+}
+
+/// Çalışma anı ayarları ve özellik bayrakları önbelleklerini yükler
+pub async fn reload_all(app_state: &web::Data<AppState>) -> Result<(), AppError> {
+    system_config::reload(app_state).await?;
+    feature_flag::reload(app_state).await
+}
+
+/// Değişiklik kanallarını dinleyip ilgili önbelleği yeniler. Her (yeniden) bağlantıda
+/// hepsi baştan okunur; böylece bağlantı koptuğu sırada kaçırılan değişiklikler de uygulanır.
+pub fn spawn_listener(app_state: web::Data<AppState>) {
+    let mut shutdown = app_state.shutdown.subscribe();
+    actix_web::rt::spawn(async move {
+        loop {
+            tokio::select! {
+                result = listen(&app_state) => {
+                    if let Err(e) = result {
+                        error!("Config change subscription lost: {}", e);
+                    }
+                }
+                _ = shutdown.recv() => break,
+            }
+            tokio::select! {
+                _ = actix_web::rt::time::sleep(RECONNECT_DELAY) => {}
+                _ = shutdown.recv() => break,
+            }
+        }
+    });
+}
+
+async fn listen(app_state: &web::Data<AppState>) -> Result<(), AppError> {
+    let client = redis::Client::open(app_state.config.redis_url.as_str())?;
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    pubsub.subscribe(system_config::CHANGE_CHANNEL).await?;
+    pubsub.subscribe(feature_flag::CHANGE_CHANNEL).await?;
+    reload_all(app_state).await?;
+
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        let channel = message.get_channel_name().to_string();
+        let key: String = message.get_payload().unwrap_or_default();
+        let result = match channel.as_str() {
+            system_config::CHANGE_CHANNEL => system_config::reload(app_state).await,
+            feature_flag::CHANGE_CHANNEL => feature_flag::reload(app_state).await,
+            _ => continue,
+        };
+        match result {
+            Ok(()) => info!("Reloaded {} after change to {}", channel, key),
+            Err(e) => error!("Failed to reload {} after change to {}: {}", channel, key, e),
+        }
+    }
+
+    Err(AppError::RedisError("subscription stream closed".to_string()))
+}
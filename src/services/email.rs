@@ -0,0 +1,91 @@
+// src/services/email.rs
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use tracing::{error, info};
+use serde_json::json;
+
+use crate::{config::AppConfig, error::AppError, utils::telemetry};
+
+/// E-posta gönderim sağlayıcıları için ortak arayüz
+pub trait EmailSender: Send + Sync {
+    fn send<'a>(&'a self, to: &'a str, subject: &'a str, body: &'a str) -> BoxFuture<'a, Result<(), AppError>>;
+}
+
+/// Geliştirme ortamı için: e-postayı göndermek yerine loglar
+pub struct LoggingEmailSender;
+
+impl EmailSender for LoggingEmailSender {
+    fn send<'a>(&'a self, to: &'a str, subject: &'a str, body: &'a str) -> BoxFuture<'a, Result<(), AppError>> {
+        Box::pin(async move {
+            info!("[email] to={} subject={} body={}", to, subject, body);
+            Ok(())
+        })
+    }
+}
+
+/// JSON kabul eden transactional e-posta API'lerine POST isteği atar
+pub struct HttpEmailSender {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+    from: String,
+}
+
+impl HttpEmailSender {
+    pub fn new(url: String, token: Option<String>, from: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            token,
+            from,
+        }
+    }
+}
+
+impl EmailSender for HttpEmailSender {
+    fn send<'a>(&'a self, to: &'a str, subject: &'a str, body: &'a str) -> BoxFuture<'a, Result<(), AppError>> {
+        Box::pin(async move {
+            let mut request = self.client.post(&self.url).json(&json!({
+                "from": self.from,
+                "to": to,
+                "subject": subject,
+                "text": body,
+            }));
+            if let Some(token) = &self.token {
+                request = request.bearer_auth(token);
+            }
+
+            let mut headers = reqwest::header::HeaderMap::new();
+            telemetry::inject_trace_headers(&mut headers);
+            let response = request.headers(headers).send().await.map_err(|e| {
+                error!("Email API request failed: {}", e);
+                AppError::internal_error("Email API unreachable")
+            })?;
+            if !response.status().is_success() {
+                error!("Email API returned status {}", response.status());
+                return Err(AppError::internal_error("Email could not be sent"));
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Yapılandırmaya göre e-posta sağlayıcısını seçer
+pub fn email_sender_from_config(config: &AppConfig) -> Result<Arc<dyn EmailSender>, AppError> {
+    match config.email_provider.as_str() {
+        "log" => Ok(Arc::new(LoggingEmailSender)),
+        "http" => {
+            let url = config
+                .email_api_url
+                .clone()
+                .ok_or_else(|| AppError::internal_error("EMAIL_API_URL is required when EMAIL_PROVIDER=http"))?;
+            Ok(Arc::new(HttpEmailSender::new(
+                url,
+                config.email_api_token.clone(),
+                config.email_from.clone(),
+            )))
+        }
+        other => Err(AppError::internal_error(&format!("Unknown email provider: {}", other))),
+    }
+}
@@ -0,0 +1,198 @@
+// src/services/feature_flag.rs
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use actix_web::web;
+use tracing::{error, info};
+use serde_json::{json, Value};
+use validator::Validate;
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::{
+        audit_event::AuditAction,
+        dto::feature_flag::UpsertFeatureFlagRequest,
+        feature_flag::{FeatureFlag, FlagContext, FlagKind, BOOLEAN_VARIANTS},
+    },
+    repositories::{self, feature_flag::UpsertFeatureFlag},
+    services::{
+        audit::{self, AuditEntry},
+        config_sync,
+    },
+};
+
+/// Bayrak değiştiğinde tüm örneklere haber veren Redis kanalı
+pub const CHANGE_CHANNEL: &str = "feature_flags:changed";
+const MAX_KEY_LEN: usize = 100;
+
+/// Bayrakların süreç içi önbelleği; istek başına değerlendirme veritabanına gitmez
+#[derive(Default)]
+pub struct FeatureFlagStore {
+    flags: RwLock<Arc<Vec<FeatureFlag>>>,
+}
+
+impl FeatureFlagStore {
+    pub fn current(&self) -> Arc<Vec<FeatureFlag>> {
+        self.flags.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn replace(&self, flags: Vec<FeatureFlag>) {
+        *self.flags.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(flags);
+    }
+}
+
+/// Bayrakları veritabanından yeniden okur ve önbelleği günceller
+pub async fn reload(app_state: &web::Data<AppState>) -> Result<(), AppError> {
+    let flags = repositories::feature_flag::list(&app_state.db).await?;
+    app_state.feature_flags.replace(flags);
+    Ok(())
+}
+
+/// Tüm bayrakların bağlama göre değerleri; anahtar sırasıyla döner
+pub fn evaluate_all(flags: &[FeatureFlag], ctx: &FlagContext) -> BTreeMap<String, Value> {
+    flags.iter().map(|flag| (flag.key.clone(), flag.evaluate(ctx))).collect()
+}
+
+pub async fn list_flags(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+) -> Result<Vec<FeatureFlag>, AppError> {
+    admin.require_admin()?;
+
+    repositories::feature_flag::list(&app_state.db).await
+}
+
+/// Bayrağı oluşturur ya da tanımını tamamen değiştirir
+pub async fn upsert_flag(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+    key: &str,
+    req: UpsertFeatureFlagRequest,
+) -> Result<FeatureFlag, AppError> {
+    admin.require_admin()?;
+    admin.deny_if_impersonated("changing feature flags")?;
+    req.validate()?;
+    validate_key(key)?;
+
+    let variants: Vec<String> = match req.kind {
+        FlagKind::Boolean => BOOLEAN_VARIANTS.iter().map(|v| v.to_string()).collect(),
+        FlagKind::Multivariate => req.variants.iter().map(|v| v.trim().to_string()).collect(),
+    };
+    let default_variant = req
+        .default_variant
+        .as_deref()
+        .map(|v| v.trim().to_string())
+        .or_else(|| variants.first().cloned())
+        .unwrap_or_default();
+    validate_variants(&variants, &default_variant, &req)?;
+
+    let flag = repositories::feature_flag::upsert(
+        &app_state.db,
+        &UpsertFeatureFlag {
+            key,
+            description: req.description.as_deref(),
+            kind: req.kind,
+            enabled: req.enabled,
+            variants: &variants,
+            default_variant: &default_variant,
+            rules: &req.rules,
+            updated_by: admin.user_id,
+        },
+    )
+    .await?;
+    info!("Feature flag {} updated by user_id: {}", key, admin.user_id);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::FeatureFlagUpdate, &admin.client)
+            .actor(admin.user_id)
+            .diff(json!({
+                "key": key,
+                "enabled": flag.enabled,
+                "default_variant": flag.default_variant,
+                "rules": flag.rules.0,
+            })),
+    )
+    .await;
+
+    sync(app_state, key).await;
+    Ok(flag)
+}
+
+pub async fn delete_flag(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+    key: &str,
+) -> Result<(), AppError> {
+    admin.require_admin()?;
+    admin.deny_if_impersonated("changing feature flags")?;
+
+    if !repositories::feature_flag::delete(&app_state.db, key).await? {
+        return Err(AppError::NotFound("Feature flag not found".to_string()));
+    }
+    info!("Feature flag {} deleted by user_id: {}", key, admin.user_id);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::FeatureFlagDelete, &admin.client)
+            .actor(admin.user_id)
+            .diff(json!({ "key": key })),
+    )
+    .await;
+
+    sync(app_state, key).await;
+    Ok(())
+}
+
+/// Bu örneğin önbelleğini hemen yeniler ve diğer örneklere yayınlar
+async fn sync(app_state: &web::Data<AppState>, key: &str) {
+    if let Err(e) = reload(app_state).await {
+        error!("Failed to reload feature flags after change to {}: {}", key, e);
+    }
+    if let Err(e) = config_sync::publish(app_state, CHANGE_CHANNEL, key).await {
+        error!("Failed to publish feature flag change {}: {}", key, e);
+    }
+}
+
+fn validate_key(key: &str) -> Result<(), AppError> {
+    let valid = !key.is_empty()
+        && key.len() <= MAX_KEY_LEN
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '-' | '.'));
+    if !valid {
+        return Err(AppError::ValidationError(
+            "Flag key must be 1-100 characters of a-z, 0-9, '_', '-' or '.'".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_variants(variants: &[String], default_variant: &str, req: &UpsertFeatureFlagRequest) -> Result<(), AppError> {
+    let invalid = |message: String| Err(AppError::ValidationError(message));
+
+    if variants.len() < 2 {
+        return invalid("A multivariate flag needs at least two variants".to_string());
+    }
+    let mut seen = HashSet::new();
+    for variant in variants {
+        if variant.is_empty() || variant.len() > MAX_KEY_LEN {
+            return invalid("Variant names must be 1-100 characters".to_string());
+        }
+        if !seen.insert(variant.as_str()) {
+            return invalid(format!("Duplicate variant '{}'", variant));
+        }
+    }
+    if !seen.contains(default_variant) {
+        return invalid(format!("Unknown default variant '{}'", default_variant));
+    }
+    for (index, rule) in req.rules.iter().enumerate() {
+        if !seen.contains(rule.variant.as_str()) {
+            return invalid(format!("Rule {} serves unknown variant '{}'", index, rule.variant));
+        }
+        if rule.percentage.is_some_and(|p| p > 100) {
+            return invalid(format!("Rule {} percentage must be between 0 and 100", index));
+        }
+    }
+    Ok(())
+}
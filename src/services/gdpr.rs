@@ -0,0 +1,288 @@
+// src/services/gdpr.rs
+use std::{path::PathBuf, time::Duration as StdDuration};
+
+use actix_web::web;
+use chrono::{DateTime, Duration, Utc};
+use tracing::{error, info, warn};
+use serde_json::json;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    app_state::AppState,
+    error::{AppError, ErrorCode},
+    extension::auth::AuthenticatedUser,
+    models::{
+        audit_event::AuditAction,
+        data_export::{DataExport, ExportStatus},
+        dto::user::{DataExportResponse, DeleteAccountRequest},
+    },
+    repositories,
+    services::{
+        audit::{self, AuditEntry},
+        auth_cache,
+    },
+    utils::{password, signed_url},
+};
+
+/// Silme ve dışa aktarma süre dolumlarını işleyen görevin çalışma aralığı
+const GDPR_WORKER_INTERVAL: StdDuration = StdDuration::from_secs(3600);
+/// İmzalı indirme bağlantısının geçerlilik süresi
+const DOWNLOAD_LINK_TTL_SECS: i64 = 15 * 60;
+/// Bu süreden uzun bekleyen dışa aktarma talebi yarıda kalmış sayılır
+const EXPORT_STALE_AFTER_MINUTES: i64 = 30;
+
+fn export_stale_before() -> DateTime<Utc> {
+    Utc::now() - Duration::minutes(EXPORT_STALE_AFTER_MINUTES)
+}
+
+fn download_path(export_id: Uuid) -> String {
+    format!("/api/users/exports/{}/download", export_id)
+}
+
+/// Dışa aktarma talebi oluşturur; arşiv arka planda hazırlanır
+pub async fn request_export(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+) -> Result<DataExportResponse, AppError> {
+    auth_user.deny_if_impersonated("exporting personal data")?;
+    if repositories::data_export::has_pending(&app_state.db, auth_user.user_id, export_stale_before()).await? {
+        return Err(AppError::Conflict("A data export is already being prepared.".to_string()).with_code(ErrorCode::ExportInProgress));
+    }
+
+    let export = repositories::data_export::create(&app_state.db, auth_user.user_id).await?;
+    let state = app_state.clone();
+    let export_id = export.id;
+    let user_id = export.user_id;
+    actix_web::rt::spawn(async move {
+        if let Err(e) = generate_export(&state, export_id, user_id).await {
+            error!("Data export {} failed: {}", export_id, e);
+            if let Err(e) = repositories::data_export::mark_failed(&state.db, export_id, &e.to_string()).await {
+                error!("Could not mark data export {} as failed: {}", export_id, e);
+            }
+        }
+    });
+
+    Ok(DataExportResponse { export, download_url: None })
+}
+
+pub async fn get_export(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    export_id: Uuid,
+) -> Result<DataExportResponse, AppError> {
+    let export = repositories::data_export::find_by_id(&app_state.db, export_id)
+        .await?
+        .filter(|e| e.user_id == auth_user.user_id)
+        .ok_or_else(|| AppError::not_found("data export"))?;
+
+    let download_url = (export.status == ExportStatus::Ready).then(|| {
+        let expires = Utc::now().timestamp() + DOWNLOAD_LINK_TTL_SECS;
+        signed_url::signed_path(&download_path(export.id), expires, &app_state.jwt_secret)
+    });
+
+    Ok(DataExportResponse { export, download_url })
+}
+
+/// İmzalı bağlantıyı doğrular ve arşiv içeriğini döner
+pub async fn download_export(
+    app_state: &web::Data<AppState>,
+    export_id: Uuid,
+    expires: i64,
+    signature: &str,
+) -> Result<(DataExport, Vec<u8>), AppError> {
+    signed_url::verify(&download_path(export_id), expires, signature, &app_state.jwt_secret)?;
+
+    let export = repositories::data_export::find_by_id(&app_state.db, export_id)
+        .await?
+        .filter(|e| e.status == ExportStatus::Ready)
+        .ok_or_else(|| AppError::not_found("data export"))?;
+    let path = export
+        .file_path
+        .clone()
+        .ok_or_else(|| AppError::not_found("data export file"))?;
+
+    let content = web::block(move || std::fs::read(path))
+        .await
+        .map_err(|e| AppError::internal_error(&e.to_string()))?
+        .map_err(|e| AppError::internal_error(&format!("Export file could not be read: {}", e)))?;
+
+    Ok((export, content))
+}
+
+/// Kullanıcıya ait kişisel verileri tek bir JSON belgesinde toplar: profil, oturumlar,
+/// denetim kayıtları, organizasyon üyelikleri ve davetleri, kayıt davetleri, temsil
+/// (impersonation) kayıtları ve kullanıcının isteklerine bağlı sistem logları.
+/// Dışarıda bırakılanlar: parola/kod/token özetleri ve diğer kullanıcıların iç kimlikleri
+/// (modellerde serileştirilmez), dışa aktarım talepleri ve organizasyonun kendi kayıtları.
+/// Sohbet, mesaj, kullanım ve token işlem tabloları eklendikçe burada ayrı bölümler olarak yer almalıdır.
+async fn build_export_document(app_state: &web::Data<AppState>, user_id: i32) -> Result<serde_json::Value, AppError> {
+    let db = &app_state.db;
+    let user = repositories::auth::find_by_id(db, user_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("user"))?;
+    let sessions = repositories::user_session::list_all_by_user(db, user_id).await?;
+    let audit_events = repositories::audit_event::list_for_user(db, user_id).await?;
+    let organizations = repositories::organization::list_for_user(db, user_id).await?;
+    let organization_invitations = repositories::organization::list_invitations_for_email(db, &user.email).await?;
+    let invitations = repositories::invitation::list_for_email(db, &user.email).await?;
+    let impersonation_events = repositories::impersonation::list_for_user(db, user_id).await?;
+    let system_logs = repositories::system_log::list_for_user(db, user_id).await?;
+
+    Ok(json!({
+        "format_version": 2,
+        "generated_at": Utc::now(),
+        "profile": user,
+        "sessions": sessions,
+        "audit_events": audit_events,
+        "organizations": organizations,
+        "organization_invitations": organization_invitations,
+        "invitations": invitations,
+        "impersonation_events": impersonation_events,
+        "system_logs": system_logs,
+    }))
+}
+
+async fn generate_export(app_state: &web::Data<AppState>, export_id: Uuid, user_id: i32) -> Result<(), AppError> {
+    let document = build_export_document(app_state, user_id).await?;
+    let bytes = serde_json::to_vec_pretty(&document)
+        .map_err(|e| AppError::internal_error(&e.to_string()))?;
+
+    let dir = PathBuf::from(&app_state.config.export_dir);
+    let path = dir.join(format!("{}.json", export_id));
+    let write_path = path.clone();
+    web::block(move || {
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(&write_path, bytes)
+    })
+    .await
+    .map_err(|e| AppError::internal_error(&e.to_string()))?
+    .map_err(|e| AppError::internal_error(&format!("Export file could not be written: {}", e)))?;
+
+    let expires_at = Utc::now() + Duration::hours(app_state.config.export_ttl_hours);
+    repositories::data_export::mark_ready(&app_state.db, export_id, &path.to_string_lossy(), expires_at).await?;
+    info!("Data export {} ready for user_id: {}", export_id, user_id);
+    Ok(())
+}
+
+/// Parola onayıyla hesap silme talebi oluşturur; veriler bekleme süresi sonunda anonimleştirilir
+pub async fn request_deletion(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    req: DeleteAccountRequest,
+) -> Result<DateTime<Utc>, AppError> {
+    auth_user.deny_if_impersonated("deleting the account")?;
+    req.validate()?;
+
+    let user = repositories::auth::find_by_id(&app_state.db, auth_user.user_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("user"))?;
+    if !password::verify_password(&req.password, &user.password_hash, &app_state.password_hashing)? {
+        return Err(AppError::Unauthorized("Invalid credentials".to_string()).with_code(ErrorCode::InvalidCredentials));
+    }
+
+    let scheduled_for = Utc::now() + Duration::days(app_state.config.deletion_grace_days);
+    repositories::auth::schedule_deletion(&app_state.db, user.id, scheduled_for).await?;
+    info!("Account deletion scheduled for user_id: {} at {}", user.id, scheduled_for);
+    audit::record(
+        app_state,
+        AuditEntry::for_user(AuditAction::AccountDeletionRequested, auth_user)
+            .diff(json!({ "scheduled_for": scheduled_for })),
+    )
+    .await;
+    Ok(scheduled_for)
+}
+
+pub async fn cancel_deletion(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+) -> Result<(), AppError> {
+    auth_user.deny_if_impersonated("cancelling account deletion")?;
+    if !repositories::auth::cancel_deletion(&app_state.db, auth_user.user_id).await? {
+        return Err(AppError::not_found("pending account deletion"));
+    }
+    info!("Account deletion cancelled for user_id: {}", auth_user.user_id);
+    audit::record(app_state, AuditEntry::for_user(AuditAction::AccountDeletionCancelled, auth_user)).await;
+    Ok(())
+}
+
+pub async fn deletion_status(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+) -> Result<Option<DateTime<Utc>>, AppError> {
+    repositories::auth::deletion_scheduled_for(&app_state.db, auth_user.user_id).await
+}
+
+/// Kullanıcıyı anonimleştirir; oturumlarını, organizasyon üyeliklerini, e-postasına gönderilmiş
+/// davetleri ve dışa aktarım dosyalarını siler, taklit ve sistem log kayıtlarındaki bağları kaldırır.
+/// Denetim kaydında kişisel veri tutulmaz (giriş denemelerinde yalnızca tanımlayıcının özeti yazılır).
+async fn erase_user(app_state: &web::Data<AppState>, user_id: i32) -> Result<(), AppError> {
+    let unusable_hash = password::hash_password(&Uuid::new_v4().to_string(), &app_state.password_hashing)?;
+
+    let mut tx = app_state.db.begin().await?;
+    // E-postaya göre eşleşenler, adres anonimleştirilmeden önce temizlenmeli
+    repositories::organization::delete_invitations_for_user(&mut *tx, user_id).await?;
+    repositories::invitation::redact_email_for_user(&mut *tx, user_id).await?;
+    repositories::organization::delete_memberships_for_user(&mut *tx, user_id).await?;
+    repositories::impersonation::clear_ip_addresses_for_user(&mut *tx, user_id).await?;
+    repositories::system_log::detach_user(&mut *tx, user_id).await?;
+    let public_id = repositories::auth::anonymize(&mut *tx, user_id, &unusable_hash).await?;
+    repositories::user_session::delete_for_user(&mut *tx, user_id).await?;
+    tx.commit().await?;
+    auth_cache::forget_user(&mut app_state.redis().await?, public_id, user_id).await?;
+
+    let files = repositories::data_export::delete_for_user(&app_state.db, user_id).await?;
+    remove_files(files).await;
+    info!("User {} anonymized after deletion grace period", user_id);
+    Ok(())
+}
+
+async fn remove_files(paths: Vec<String>) {
+    let _ = web::block(move || {
+        for path in paths {
+            if let Err(e) = std::fs::remove_file(&path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    warn!("Could not remove export file {}: {}", path, e);
+                }
+            }
+        }
+    })
+    .await;
+}
+
+/// Süresi dolan silme taleplerini ve dışa aktarımları periyodik olarak işleyen arka plan görevi
+pub fn spawn_gdpr_worker(app_state: web::Data<AppState>) {
+    let mut shutdown = app_state.shutdown.subscribe();
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(GDPR_WORKER_INTERVAL);
+        loop {
+            // Başlamış silme/anonimleştirme turu kapanışta tamamlanır, ardından döngüden çıkılır
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.recv() => break,
+            }
+
+            match repositories::auth::find_due_deletions(&app_state.db).await {
+                Ok(user_ids) => {
+                    for user_id in user_ids {
+                        if let Err(e) = erase_user(&app_state, user_id).await {
+                            error!("Erasure of user {} failed: {}", user_id, e);
+                        }
+                    }
+                }
+                Err(e) => error!("Could not load due account deletions: {}", e),
+            }
+
+            match repositories::data_export::fail_stale(&app_state.db, export_stale_before()).await {
+                Ok(0) => {}
+                Ok(n) => warn!("Marked {} stalled data exports as failed", n),
+                Err(e) => error!("Could not fail stalled data exports: {}", e),
+            }
+
+            match repositories::data_export::expire_due(&app_state.db).await {
+                Ok(files) => remove_files(files).await,
+                Err(e) => error!("Could not expire data exports: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,162 @@
+// src/services/guest.rs
+use std::time::Duration;
+
+use actix_web::web;
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::{
+        audit_event::AuditAction,
+        dto::auth::{AuthResponse, RegisterRequest},
+        user::{UserRole, UserSchema},
+    },
+    repositories,
+    services::{
+        audit::{self, AuditEntry},
+        auth::{issue_session_token, logout_user, map_unique_violation},
+    },
+    utils::{client_info::ClientInfo, password, phone, rate_limit},
+};
+
+/// Misafir temizliğinin çalışma aralığı
+const GUEST_CLEANUP_INTERVAL: Duration = Duration::from_secs(3600);
+
+pub async fn create_guest(
+    app_state: &web::Data<AppState>,
+    client: &ClientInfo,
+) -> Result<AuthResponse, AppError> {
+    let settings = app_state.system_config.current();
+    if !settings.registration_open {
+        return Err(AppError::Forbidden("Guest access is disabled while registration is invite-only.".to_string()));
+    }
+    enforce_guest_rate_limit(app_state, client, settings.guest_max_per_ip_per_hour).await?;
+
+    let suffix = Uuid::new_v4().simple().to_string();
+    let username = format!("guest_{}", &suffix[..12]);
+    let email = format!("{}@guest.invalid", username);
+    // Misafir hesabına parola ile giriş yapılamaz; rastgele parolanın hash'i saklanır
+    let password_hash = password::hash_password(&Uuid::new_v4().to_string(), &app_state.password_hashing)?;
+
+    let user = repositories::auth::create_guest(
+        &app_state.db,
+        &username,
+        &email,
+        &password_hash,
+        BigDecimal::from(settings.guest_token_allowance),
+    )
+    .await?;
+    debug!("Guest user created: {}", user.id);
+
+    issue_session_token(app_state, &user, client).await
+}
+
+pub async fn upgrade_guest(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    req: RegisterRequest,
+    client: &ClientInfo,
+) -> Result<AuthResponse, AppError> {
+    auth_user.deny_if_impersonated("setting a password")?;
+    if auth_user.role != UserRole::Guest.to_string() {
+        return Err(AppError::forbidden("only guest accounts can be upgraded"));
+    }
+    req.validate()?;
+    if req.invite_code.is_some() {
+        return Err(AppError::bad_request("invite_code is not accepted when upgrading a guest"));
+    }
+    app_state
+        .password_policy
+        .check(&req.password, &[&req.username, &req.email, &req.full_name])
+        .await?;
+
+    let hashed_password = password::hash_password(&req.password, &app_state.password_hashing)?;
+    let user_data = UserSchema {
+        id: auth_user.user_id,
+        public_id: auth_user.public_id,
+        full_name: req.full_name,
+        username: req.username,
+        email: req.email,
+        password_hash: hashed_password.clone(),
+        phone_number: req
+            .phone_number
+            .as_deref()
+            .map(|p| phone::normalize_e164(p, &app_state.config.default_phone_country_code))
+            .transpose()?,
+        token_balance: None,
+        user_role: UserRole::User,
+        subscription_expries: None,
+        email_verified: None,
+        phone_verified: None,
+        last_login: None,
+        is_active: Some(true),
+        created_at: None,
+        updated_at: Some(Utc::now()),
+    };
+
+    let user = repositories::auth::upgrade_guest(&app_state.db, auth_user.user_id, &user_data, &hashed_password)
+        .await
+        .map_err(map_unique_violation)?
+        .ok_or_else(|| AppError::not_found("guest user"))?;
+
+    // Misafir rolünü taşıyan token artık geçersiz; yeni rol ile yeni oturum açılır
+    logout_user(app_state, auth_user).await?;
+    info!("Guest user {} upgraded to a regular account", user.id);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::RoleChange, client)
+            .actor(user.id)
+            .target(user.id)
+            .diff(serde_json::json!({
+                "role": { "from": UserRole::Guest.to_string(), "to": user.user_role.to_string() }
+            })),
+    )
+    .await;
+
+    issue_session_token(app_state, &user, client).await
+}
+
+async fn enforce_guest_rate_limit(
+    app_state: &web::Data<AppState>,
+    client: &ClientInfo,
+    max_per_hour: u32,
+) -> Result<(), AppError> {
+    let ip = client.ip_address.as_deref().unwrap_or("unknown");
+    let mut conn = app_state.redis().await?;
+
+    rate_limit::enforce(
+        &mut conn,
+        &format!("guest_create:{}", ip),
+        max_per_hour,
+        3600,
+        "Too many guest accounts created from this address.",
+    )
+    .await
+}
+
+/// Süresi dolan misafir hesaplarını periyodik olarak silen arka plan görevini başlatır
+pub fn spawn_guest_cleanup(app_state: web::Data<AppState>) {
+    let mut shutdown = app_state.shutdown.subscribe();
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(GUEST_CLEANUP_INTERVAL);
+        loop {
+            // Kapanış yalnızca turlar arasında dinlenir; başlamış temizlik yarıda kesilmez
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.recv() => break,
+            }
+            let cutoff = Utc::now() - chrono::Duration::hours(app_state.config.guest_ttl_hours);
+            match repositories::auth::delete_stale_guests(&app_state.db, cutoff).await {
+                Ok(0) => {}
+                Ok(n) => info!("Purged {} stale guest accounts", n),
+                Err(e) => error!("Guest cleanup failed: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,177 @@
+// src/services/health.rs
+use std::collections::{BTreeMap, HashSet};
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use actix_web::web;
+use deadpool_redis::redis;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::{
+    app_state::{AppState, MIGRATOR},
+    error::AppError,
+    repositories,
+};
+
+/// Tek bir kontrolün süresi; prob zaman aşımından kısa tutulmalıdır
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Up,
+    Down,
+    /// Bağımlılık yapılandırılmamış; çalışmayı engellemez
+    Disabled,
+}
+
+/// Uç nokta kimlik doğrulamasız olduğu için hata ayrıntısı yanıtta yer almaz, yalnızca loglanır
+#[derive(Debug, Serialize)]
+pub struct CheckResult {
+    pub status: CheckStatus,
+    /// Başarısız olduğunda hazır olma durumu düşer
+    pub critical: bool,
+    pub latency_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessReport {
+    /// "ok", "degraded" (kritik olmayan bir kontrol başarısız) ya da "unavailable"
+    pub status: &'static str,
+    pub checks: BTreeMap<String, CheckResult>,
+}
+
+impl ReadinessReport {
+    pub fn is_ready(&self) -> bool {
+        self.status != "unavailable"
+    }
+}
+
+/// Kontrolü zaman aşımıyla çalıştırır, süresini ölçer ve başarısızlığın nedenini loglar
+async fn run_check<F>(name: String, critical: bool, check: F) -> (String, CheckResult)
+where
+    F: Future<Output = Result<CheckStatus, AppError>>,
+{
+    let started = Instant::now();
+    let outcome = actix_web::rt::time::timeout(CHECK_TIMEOUT, check).await;
+    let latency_ms = (started.elapsed().as_secs_f64() * 1000.0 * 100.0).round() / 100.0;
+
+    let status = match outcome {
+        Ok(Ok(status)) => status,
+        Ok(Err(e)) => {
+            warn!("Readiness check {} failed: {}", name, e);
+            CheckStatus::Down
+        }
+        Err(_) => {
+            warn!("Readiness check {} timed out after {}s", name, CHECK_TIMEOUT.as_secs());
+            CheckStatus::Down
+        }
+    };
+    (name, CheckResult { status, critical, latency_ms })
+}
+
+async fn check_database(app_state: &web::Data<AppState>) -> Result<CheckStatus, AppError> {
+    repositories::health::ping(&app_state.db).await?;
+    Ok(CheckStatus::Up)
+}
+
+async fn check_redis(app_state: &web::Data<AppState>) -> Result<CheckStatus, AppError> {
+    let mut conn = app_state.redis().await?;
+    redis::cmd("PING").query_async::<_, String>(&mut conn).await?;
+    Ok(CheckStatus::Up)
+}
+
+/// Bu sürümle gelen ama veritabanına uygulanmamış migration varsa hazır değildir
+async fn check_migrations(app_state: &web::Data<AppState>) -> Result<CheckStatus, AppError> {
+    let applied: HashSet<i64> = repositories::health::applied_migrations(&app_state.db)
+        .await?
+        .into_iter()
+        .collect();
+    let pending: Vec<i64> = MIGRATOR
+        .iter()
+        .map(|migration| migration.version)
+        .filter(|version| !applied.contains(version))
+        .collect();
+    if !pending.is_empty() {
+        return Err(AppError::internal_error(&format!("pending migrations: {:?}", pending)));
+    }
+    Ok(CheckStatus::Up)
+}
+
+/// Veritabanı açılışta belleğe yüklenir; dosya sonradan kaldırılmış ya da okunamıyorsa
+/// bir sonraki başlatmada açılamayacağı için "degraded" bildirilir
+async fn check_geoip(app_state: &web::Data<AppState>) -> Result<CheckStatus, AppError> {
+    let path = match app_state.config.geoip_db_path.as_deref() {
+        Some(path) if app_state.geoip.is_enabled() => path,
+        _ => return Ok(CheckStatus::Disabled),
+    };
+    actix_web::rt::task::spawn_blocking({
+        let path = path.to_string();
+        move || std::fs::File::open(path)
+    })
+    .await
+    .map_err(|e| AppError::internal_error(&e.to_string()))?
+    .map_err(|e| AppError::internal_error(&format!("GeoIP database is not readable: {}", e)))?;
+    Ok(CheckStatus::Up)
+}
+
+fn http_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| reqwest::Client::builder().timeout(CHECK_TIMEOUT).build().unwrap_or_default())
+}
+
+/// Sağlayıcının sağlık adresi 2xx dönüyorsa erişilebilir sayılır
+async fn check_ai_provider(url: &str) -> Result<CheckStatus, AppError> {
+    let response = http_client()
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| AppError::internal_error(&e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(AppError::internal_error(&format!("responded with {}", response.status())));
+    }
+    Ok(CheckStatus::Up)
+}
+
+/// Bağımlılıkları paralel kontrol eder. Postgres, Redis ve migration'lar kritiktir;
+/// GeoIP ve yapay zekâ sağlayıcıları (`ai:<ad>`) yalnızca "degraded" durumuna düşürür.
+pub async fn readiness(app_state: &web::Data<AppState>) -> ReadinessReport {
+    // Kapanış başladıysa yük dengeleyicinin bu örneğe yeni trafik göndermemesi için hazır değiliz
+    if app_state.shutdown.is_triggered() {
+        let check = CheckResult { status: CheckStatus::Down, critical: true, latency_ms: 0.0 };
+        return ReadinessReport { status: "unavailable", checks: BTreeMap::from([("shutdown".to_string(), check)]) };
+    }
+
+    let providers = app_state.config.ai_provider_health_urls();
+    let provider_checks = providers
+        .iter()
+        .map(|(name, url)| run_check(format!("ai:{}", name), false, check_ai_provider(url)));
+    let (database, redis, migrations, geoip, ai_providers) = futures::join!(
+        run_check("database".to_string(), true, check_database(app_state)),
+        run_check("redis".to_string(), true, check_redis(app_state)),
+        run_check("migrations".to_string(), true, check_migrations(app_state)),
+        run_check("geoip".to_string(), false, check_geoip(app_state)),
+        futures::future::join_all(provider_checks),
+    );
+    let checks: BTreeMap<String, CheckResult> = [database, redis, migrations, geoip]
+        .into_iter()
+        .chain(ai_providers)
+        .collect();
+
+    let failed = |critical: bool| {
+        checks
+            .values()
+            .any(|check| check.critical == critical && check.status == CheckStatus::Down)
+    };
+    let status = if failed(true) {
+        "unavailable"
+    } else if failed(false) {
+        "degraded"
+    } else {
+        "ok"
+    };
+
+    ReadinessReport { status, checks }
+}
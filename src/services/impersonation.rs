@@ -0,0 +1,121 @@
+// src/services/impersonation.rs
+use actix_web::web;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::{
+        audit_event::AuditAction,
+        dto::auth::{Claims, ImpersonationResponse, TokenType},
+        impersonation::{ImpersonationEvent, ImpersonationEventKind},
+        user::UserRole,
+    },
+    repositories::{self, impersonation::NewImpersonationEvent},
+    services::{
+        audit::{self, AuditEntry},
+        auth::logout_user,
+    },
+    utils::{client_info::ClientInfo, jwt},
+};
+
+pub async fn start_impersonation(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+    target_public_id: Uuid,
+    client: &ClientInfo,
+) -> Result<ImpersonationResponse, AppError> {
+    admin.require_admin()?;
+    admin.deny_if_impersonated("starting a new impersonation")?;
+
+    let target = repositories::auth::find_by_public_id(&app_state.db, target_public_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("user"))?;
+    if target.user_role == UserRole::Admin {
+        return Err(AppError::forbidden("administrators cannot be impersonated"));
+    }
+
+    let lifetime = app_state.config.impersonation_minutes as usize * 60;
+    let claims = Claims::new(target.public_id, target.user_role.to_string(), TokenType::Access, &app_state.config)
+        .with_actor(admin.public_id, lifetime);
+
+    repositories::impersonation::record(
+        &app_state.db,
+        &NewImpersonationEvent {
+            token_jti: &claims.jti,
+            actor_id: admin.user_id,
+            target_user_id: target.id,
+            event: ImpersonationEventKind::Start,
+            http_method: None,
+            path: None,
+            ip_address: client.ip_address.as_deref(),
+        },
+    )
+    .await?;
+    info!("Admin {} started impersonating user {}", admin.user_id, target.id);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::ImpersonationStart, client)
+            .actor(admin.user_id)
+            .target(target.id)
+            .diff(serde_json::json!({ "token_jti": claims.jti, "expires_at": claims.exp })),
+    )
+    .await;
+
+    let token = jwt::encode_claims(&claims, &app_state.jwt_secret)?;
+    Ok(ImpersonationResponse {
+        token,
+        target_user_id: target.public_id,
+        actor_user_id: admin.public_id,
+        expires_at: claims.exp,
+    })
+}
+
+pub async fn stop_impersonation(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    client: &ClientInfo,
+) -> Result<(), AppError> {
+    let actor_id = auth_user
+        .actor_id()
+        .ok_or_else(|| AppError::bad_request("token is not an impersonation token"))?;
+
+    logout_user(app_state, auth_user).await?;
+    repositories::impersonation::record(
+        &app_state.db,
+        &NewImpersonationEvent {
+            token_jti: &auth_user.claims.jti,
+            actor_id,
+            target_user_id: auth_user.user_id,
+            event: ImpersonationEventKind::Stop,
+            http_method: None,
+            path: None,
+            ip_address: client.ip_address.as_deref(),
+        },
+    )
+    .await?;
+    info!("Admin {} stopped impersonating user {}", actor_id, auth_user.user_id);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::ImpersonationStop, client)
+            .actor(actor_id)
+            .target(auth_user.user_id)
+            .diff(serde_json::json!({ "token_jti": auth_user.claims.jti })),
+    )
+    .await;
+
+    Ok(())
+}
+
+pub async fn list_events(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+    token_jti: &str,
+) -> Result<Vec<ImpersonationEvent>, AppError> {
+    admin.require_admin()?;
+    admin.deny_if_impersonated("viewing impersonation logs")?;
+
+    repositories::impersonation::list_by_token(&app_state.db, token_jti).await
+}
@@ -0,0 +1,124 @@
+// src/services/invitation.rs
+use actix_web::web;
+use chrono::{Duration, Utc};
+use tracing::info;
+use validator::Validate;
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::{
+        audit_event::AuditAction,
+        dto::auth::{CreateInvitationRequest, CreatedInvitationResponse},
+        invitation::Invitation,
+        user::UserRole,
+    },
+    repositories::{self, invitation::NewInvitation},
+    services::audit::{self, AuditEntry},
+    utils::{otp, sha256::sha256_hash},
+};
+
+pub async fn create_invitation(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+    req: CreateInvitationRequest,
+) -> Result<CreatedInvitationResponse, AppError> {
+    admin.require_admin()?;
+    admin.deny_if_impersonated("creating invitations")?;
+    req.validate()?;
+    if req.role == UserRole::Guest {
+        return Err(AppError::bad_request("invitations cannot grant the guest role"));
+    }
+
+    let code = otp::generate_invite_code();
+    let invitation = repositories::invitation::create(
+        &app_state.db,
+        &NewInvitation {
+            code_hash: &sha256_hash(&code),
+            role: req.role,
+            max_uses: req.max_uses,
+            email: req.email.as_deref(),
+            expires_at: req.expires_in_hours.map(|h| Utc::now() + Duration::hours(h)),
+            created_by: Some(admin.user_id),
+        },
+    )
+    .await?;
+    info!("Invitation {} created by admin {} (role: {})", invitation.id, admin.user_id, invitation.role);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::InvitationCreate, &admin.client)
+            .actor(admin.user_id)
+            .diff(serde_json::json!({
+                "invitation_id": invitation.id,
+                "role": invitation.role.to_string(),
+                "max_uses": invitation.max_uses,
+                "email": invitation.email,
+            })),
+    )
+    .await;
+
+    Ok(CreatedInvitationResponse { code, invitation })
+}
+
+pub async fn list_invitations(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+) -> Result<Vec<Invitation>, AppError> {
+    admin.require_admin()?;
+    repositories::invitation::list(&app_state.db).await
+}
+
+pub async fn revoke_invitation(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+    invitation_id: i32,
+) -> Result<(), AppError> {
+    admin.require_admin()?;
+    admin.deny_if_impersonated("revoking invitations")?;
+
+    if !repositories::invitation::revoke(&app_state.db, invitation_id).await? {
+        return Err(AppError::not_found("invitation"));
+    }
+    info!("Invitation {} revoked by admin {}", invitation_id, admin.user_id);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::InvitationRevoke, &admin.client)
+            .actor(admin.user_id)
+            .diff(serde_json::json!({ "invitation_id": invitation_id })),
+    )
+    .await;
+    Ok(())
+}
+
+/// Henüz hiç yönetici yoksa, yapılandırmadaki `bootstrap_admin_invite_code` ile
+/// tek kullanımlık bir yönetici daveti oluşturur (ilk kurulum için).
+pub async fn ensure_bootstrap_invitation(app_state: &web::Data<AppState>) -> Result<(), AppError> {
+    let Some(code) = app_state.config.bootstrap_admin_invite_code.as_deref().filter(|c| !c.is_empty()) else {
+        return Ok(());
+    };
+    if repositories::auth::admin_exists(&app_state.db).await? {
+        return Ok(());
+    }
+
+    let code_hash = sha256_hash(&code.trim().to_uppercase());
+    // Süresi dolan ya da kullanılıp yönetici hesabı silinen davet yeniden açılır; aksi halde
+    // yeni bir kurulum yönetici oluşturamaz
+    if repositories::invitation::is_redeemable(&app_state.db, &code_hash).await? {
+        return Ok(());
+    }
+    repositories::invitation::create_or_renew(
+        &app_state.db,
+        &NewInvitation {
+            code_hash: &code_hash,
+            role: UserRole::Admin,
+            max_uses: 1,
+            email: None,
+            expires_at: Some(Utc::now() + Duration::hours(24)),
+            created_by: None,
+        },
+    )
+    .await?;
+    info!("Bootstrap admin invitation created (valid for 24 hours)");
+    Ok(())
+}
@@ -0,0 +1,216 @@
+// src/services/login_risk.rs
+use actix_web::web;
+use chrono::Utc;
+use deadpool_redis::redis::{self, AsyncCommands};
+use tracing::{error, info, warn};
+use serde_json::json;
+use validator::Validate;
+
+use crate::{
+    app_state::AppState,
+    error::{AppError, ErrorCode},
+    models::{audit_event::AuditAction, dto::auth::DisownLoginRequest, user::User, user_session::UserSession},
+    repositories,
+    services::{
+        audit::{self, AuditEntry},
+        auth_cache,
+        password::spawn_reset_link,
+        security_notifier::LoginAlert,
+    },
+    utils::{client_info::ClientInfo, geoip::{haversine_km, GeoLocation}, otp, sha256::sha256_hash},
+};
+
+/// Karşılaştırmada kullanılan geçmiş oturum sayısı
+const SESSION_HISTORY_LIMIT: i64 = 50;
+/// Bu mesafenin altındaki yer değişimleri (GeoIP hassasiyeti) seyahat sayılmaz
+const MIN_TRAVEL_DISTANCE_KM: f64 = 100.0;
+
+fn disown_key(token_hash: &str) -> String {
+    format!("login_disown:{}", token_hash)
+}
+
+/// Girişin neden şüpheli bulunduğu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiskReason {
+    NewDevice,
+    NewIpAndCountry,
+    ImpossibleTravel,
+}
+
+impl RiskReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RiskReason::NewDevice => "new_device",
+            RiskReason::NewIpAndCountry => "new_ip_and_country",
+            RiskReason::ImpossibleTravel => "impossible_travel",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoginRisk {
+    pub reasons: Vec<RiskReason>,
+    pub location: Option<GeoLocation>,
+    /// İmkânsız seyahatte önceki oturumdan bu yana hesaplanan hız (km/sa)
+    pub travel_speed_kmh: Option<f64>,
+}
+
+/// Girişi kullanıcının geçmiş oturumlarıyla karşılaştırır; şüpheli değilse `None` döner.
+/// Yeni oturum açılmadan önce çağrılmalıdır. Hiç oturum geçmişi olmayan kullanıcılar
+/// (ilk giriş) işaretlenmez; tek başına yeni IP, mobil ağlarda sık değiştiği için
+/// ancak ülke de değişmişse şüpheli sayılır.
+pub async fn assess(
+    app_state: &web::Data<AppState>,
+    user: &User,
+    client: &ClientInfo,
+) -> Result<Option<LoginRisk>, AppError> {
+    let history = repositories::user_session::list_recent_by_user(&app_state.db, user.id, SESSION_HISTORY_LIMIT).await?;
+    if history.is_empty() {
+        return Ok(None);
+    }
+
+    let location = client.ip_address.as_deref().and_then(|ip| app_state.geoip.lookup(ip));
+    let mut reasons = Vec::new();
+
+    let (device, os) = (client.device(), client.os());
+    if !history.iter().any(|s| s.device.as_deref() == device && s.os.as_deref() == os) {
+        reasons.push(RiskReason::NewDevice);
+    }
+
+    let new_ip = !history.iter().any(|s| s.ip_address.is_some() && s.ip_address == client.ip_address);
+    let country = location.as_ref().and_then(|l| l.country.as_deref());
+    let new_country = country.is_some() && !history.iter().any(|s| s.country.as_deref() == country);
+    if new_ip && new_country {
+        reasons.push(RiskReason::NewIpAndCountry);
+    }
+
+    let travel_speed_kmh = location.as_ref().and_then(|loc| travel_speed_kmh(&history, loc));
+    if travel_speed_kmh.is_some_and(|speed| speed > app_state.config.impossible_travel_kmh) {
+        reasons.push(RiskReason::ImpossibleTravel);
+    }
+
+    if reasons.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(LoginRisk { reasons, location, travel_speed_kmh }))
+}
+
+/// Konumu bilinen son oturumdan bu yana gereken seyahat hızı
+fn travel_speed_kmh(history: &[UserSession], location: &GeoLocation) -> Option<f64> {
+    let last = history.iter().find(|s| s.latitude.is_some() && s.longitude.is_some())?;
+    let distance = haversine_km(last.latitude?, last.longitude?, location.latitude, location.longitude);
+    if distance < MIN_TRAVEL_DISTANCE_KM {
+        return None;
+    }
+    // Aynı anda iki uzak konumdan yapılan girişlerde sıfıra bölmeyi önlemek için en az bir dakika
+    let hours = ((Utc::now() - last.last_seen_at).num_seconds().max(60)) as f64 / 3600.0;
+    Some(distance / hours)
+}
+
+/// Uyarıyı arka planda gönderir; giriş yanıtı bildirimin tamamlanmasını beklemez
+pub fn spawn_alert(app_state: &web::Data<AppState>, user: User, client: ClientInfo, risk: LoginRisk) {
+    let app_state = app_state.clone();
+    // Kapanış, bildirim bitene kadar bekler
+    let shutdown_guard = app_state.shutdown.subscribe();
+    actix_web::rt::spawn(async move {
+        let _shutdown_guard = shutdown_guard;
+        alert(&app_state, &user, &client, &risk).await;
+    });
+}
+
+/// Şüpheli girişi denetim kaydına yazar ve kullanıcıyı "bu ben değildim" bağlantısıyla bilgilendirir.
+/// Bildirim hataları girişi engellemez, yalnızca loglanır.
+async fn alert(app_state: &web::Data<AppState>, user: &User, client: &ClientInfo, risk: &LoginRisk) {
+    let reasons: Vec<&'static str> = risk.reasons.iter().map(RiskReason::as_str).collect();
+    warn!("Suspicious login for user_id {}: {:?}", user.id, reasons);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::SuspiciousLogin, client)
+            .actor(user.id)
+            .target(user.id)
+            .diff(json!({
+                "reasons": reasons,
+                "country": risk.location.as_ref().and_then(|l| l.country.clone()),
+                "travel_speed_kmh": risk.travel_speed_kmh.map(|s| s.round()),
+            })),
+    )
+    .await;
+
+    let disown_url = match create_disown_link(app_state, user).await {
+        Ok(url) => url,
+        Err(e) => {
+            error!("Failed to create disown link for user_id {}: {}", user.id, e);
+            return;
+        }
+    };
+    let location = risk.location.as_ref().map(|l| match (&l.city, &l.country) {
+        (Some(city), Some(country)) => format!("{}, {}", city, country),
+        (None, Some(country)) => country.clone(),
+        (Some(city), None) => city.clone(),
+        (None, None) => format!("{:.2}, {:.2}", l.latitude, l.longitude),
+    });
+    let alert = LoginAlert {
+        reasons,
+        ip_address: client.ip_address.clone(),
+        device: client.device(),
+        os: client.os(),
+        location,
+        disown_url,
+    };
+    if let Err(e) = app_state.security_notifier.suspicious_login(user, &alert).await {
+        error!("Failed to send suspicious login alert to user_id {}: {}", user.id, e);
+    }
+}
+
+/// E-postadaki bağlantı istemcinin `/auth/not-me` sayfasını açar (`public_base_url` istemci
+/// adresidir); sayfa kullanıcıdan onay aldıktan sonra token'ı `POST /api/auth/not-me` ile gönderir.
+/// Bağlantıyı önizleyen e-posta tarayıcıları böylece oturumları kapatamaz.
+async fn create_disown_link(app_state: &web::Data<AppState>, user: &User) -> Result<String, AppError> {
+    let token = otp::generate_token();
+    let ttl = app_state.config.login_alert_link_ttl_hours * 3600;
+    let mut conn = app_state.redis().await?;
+    conn.set_ex::<_, _, ()>(disown_key(&sha256_hash(&token)), user.id, ttl).await?;
+
+    Ok(format!(
+        "{}/auth/not-me?token={}",
+        app_state.config.public_base_url.trim_end_matches('/'),
+        token
+    ))
+}
+
+/// "Bu ben değildim": tüm oturumları kapatır, parola ile girişi sıfırlamaya kadar engeller
+/// ve kayıtlı adrese parola sıfırlama bağlantısı gönderir
+pub async fn disown_login(
+    app_state: &web::Data<AppState>,
+    req: DisownLoginRequest,
+    client: &ClientInfo,
+) -> Result<(), AppError> {
+    req.validate()?;
+
+    let mut conn = app_state.redis().await?;
+    let user_id: Option<i32> = redis::cmd("GETDEL")
+        .arg(disown_key(&sha256_hash(&req.token)))
+        .query_async(&mut conn)
+        .await?;
+    let user_id = user_id.ok_or_else(|| AppError::Unauthorized("Link is invalid or expired".to_string()).with_code(ErrorCode::LinkInvalid))?;
+
+    let user = repositories::auth::find_by_id(&app_state.db, user_id)
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("Link is invalid or expired".to_string()).with_code(ErrorCode::LinkInvalid))?;
+
+    repositories::auth::set_password_reset_required(&app_state.db, user.id, true).await?;
+    let revoked = repositories::user_session::revoke_all_for_user(&app_state.db, user.id, None).await?;
+    auth_cache::forget_sessions(&mut app_state.redis().await?, user.id).await?;
+    info!("Login disowned by user_id: {} ({} sessions revoked)", user.id, revoked);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::LoginDisowned, client)
+            .actor(user.id)
+            .target(user.id)
+            .diff(json!({ "revoked_sessions": revoked })),
+    )
+    .await;
+
+    spawn_reset_link(app_state, user);
+    Ok(())
+}
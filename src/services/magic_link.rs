@@ -0,0 +1,149 @@
+// src/services/magic_link.rs
+use actix_web::web;
+use deadpool_redis::redis::{self, AsyncCommands};
+use tracing::{debug, error, warn};
+use validator::Validate;
+
+use crate::{
+    app_state::AppState,
+    error::{AppError, ErrorCode},
+    models::{
+        audit_event::AuditAction,
+        dto::auth::{AuthResponse, MagicLinkConsumeRequest, MagicLinkRequest},
+        user::UserRole,
+    },
+    repositories,
+    services::{
+        audit::{self, AuditEntry},
+        auth::issue_session_token,
+    },
+    utils::{client_info::ClientInfo, metrics::AuthEvent, otp, rate_limit, sha256::sha256_hash},
+};
+
+const MAGIC_LINK_TTL_SECS: u64 = 15 * 60;
+const MAX_PER_EMAIL: u32 = 3;
+const EMAIL_WINDOW_SECS: i64 = 15 * 60;
+const MAX_PER_IP: u32 = 20;
+const IP_WINDOW_SECS: i64 = 3600;
+
+fn link_key(token_hash: &str) -> String {
+    format!("magic_link:{}", token_hash)
+}
+
+fn used_key(token_hash: &str) -> String {
+    format!("magic_link_used:{}", token_hash)
+}
+
+/// Kayıtlı e-posta adresine tek kullanımlık giriş bağlantısı gönderir.
+/// Hesap var olsun ya da olmasın aynı yanıt döner; e-posta adresleri sızdırılmaz. E-posta
+/// arka planda gönderilir, böylece yanıt süresi de hesabın varlığını ele vermez.
+pub async fn request_magic_link(
+    app_state: &web::Data<AppState>,
+    req: MagicLinkRequest,
+    client: &ClientInfo,
+) -> Result<(), AppError> {
+    req.validate()?;
+    let email = req.email.trim().to_lowercase();
+
+    let mut conn = app_state.redis().await?;
+    let ip = client.ip_address.as_deref().unwrap_or("unknown");
+    rate_limit::enforce(
+        &mut conn,
+        &format!("magic_link_ip:{}", ip),
+        MAX_PER_IP,
+        IP_WINDOW_SECS,
+        "Too many sign-in link requests from this address.",
+    )
+    .await?;
+    rate_limit::enforce(
+        &mut conn,
+        &format!("magic_link_email:{}", sha256_hash(&email)),
+        MAX_PER_EMAIL,
+        EMAIL_WINDOW_SECS,
+        "Too many sign-in link requests for this email.",
+    )
+    .await?;
+
+    let user = match repositories::auth::find_by_email(&app_state.db, &email).await? {
+        Some(user) if user.is_active != Some(false) && user.user_role != UserRole::Guest => user,
+        _ => {
+            debug!("Magic link requested for unknown or inactive email");
+            return Ok(());
+        }
+    };
+
+    let token = otp::generate_token();
+    let token_hash = sha256_hash(&token);
+    conn.set_ex::<_, _, ()>(link_key(&token_hash), user.id, MAGIC_LINK_TTL_SECS).await?;
+
+    let link = format!(
+        "{}/auth/magic-link?token={}",
+        app_state.config.public_base_url.trim_end_matches('/'),
+        token
+    );
+    let body = format!(
+        "Merhaba {},\n\nHesabınıza giriş yapmak için aşağıdaki bağlantıya tıklayın. Bağlantı {} dakika geçerlidir ve yalnızca bir kez kullanılabilir.\n\n{}\n\nBu isteği siz yapmadıysanız bu e-postayı yok sayabilirsiniz.",
+        user.full_name,
+        MAGIC_LINK_TTL_SECS / 60,
+        link
+    );
+    let email_sender = app_state.email_sender.clone();
+    // Kapanış, gönderim bitene kadar bekler
+    let shutdown_guard = app_state.shutdown.subscribe();
+    actix_web::rt::spawn(async move {
+        let _shutdown_guard = shutdown_guard;
+        match email_sender.send(&user.email, "Giriş bağlantınız", &body).await {
+            Ok(()) => debug!("Magic link sent to user_id: {}", user.id),
+            Err(e) => error!("Magic link email to user_id {} failed: {}", user.id, e),
+        }
+    });
+
+    Ok(())
+}
+
+/// Bağlantıdaki token'ı tüketir ve normal giriş yanıtını döner
+pub async fn consume_magic_link(
+    app_state: &web::Data<AppState>,
+    req: MagicLinkConsumeRequest,
+    client: &ClientInfo,
+) -> Result<AuthResponse, AppError> {
+    req.validate()?;
+    let token_hash = sha256_hash(&req.token);
+
+    let mut conn = app_state.redis().await?;
+    // GETDEL atomiktir: aynı bağlantı eşzamanlı iki istekte kullanılamaz
+    let user_id: Option<i32> = redis::cmd("GETDEL")
+        .arg(link_key(&token_hash))
+        .query_async(&mut conn)
+        .await?;
+
+    let Some(user_id) = user_id else {
+        let reused: bool = conn.exists(used_key(&token_hash)).await?;
+        if reused {
+            warn!("Attempted reuse of a consumed magic link");
+            return Err(AppError::Unauthorized("Sign-in link has already been used".to_string()).with_code(ErrorCode::LinkUsed));
+        }
+        return Err(AppError::Unauthorized("Sign-in link is invalid or expired".to_string()).with_code(ErrorCode::LinkInvalid));
+    };
+    conn.set_ex::<_, _, ()>(used_key(&token_hash), user_id, MAGIC_LINK_TTL_SECS).await?;
+
+    let user = repositories::auth::find_by_id(&app_state.db, user_id)
+        .await?
+        .filter(|u| u.is_active != Some(false))
+        .ok_or_else(|| AppError::Unauthorized("Sign-in link is invalid or expired".to_string()).with_code(ErrorCode::LinkInvalid))?;
+
+    // "Bu ben değildim" bildiriminden sonra bağlantıyla da giriş yapılamaz; hesap parola sıfırlanana kadar kilitli kalır
+    if repositories::auth::password_reset_required(&app_state.db, user.id).await? {
+        return Err(AppError::Forbidden("Password reset required. Check your email for a reset link.".to_string()));
+    }
+
+    let response = issue_session_token(app_state, &user, client).await?;
+    app_state.metrics.auth_event(AuthEvent::MagicLinkLogin);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::MagicLinkLogin, client).actor(user.id).target(user.id),
+    )
+    .await;
+
+    Ok(response)
+}
@@ -1 +1,23 @@
+pub mod ai_integration;
+pub mod audit;
 pub mod auth;
+pub mod auth_cache;
+pub mod completion;
+pub mod config_sync;
+pub mod email;
+pub mod feature_flag;
+pub mod gdpr;
+pub mod guest;
+pub mod health;
+pub mod impersonation;
+pub mod invitation;
+pub mod magic_link;
+pub mod organization;
+pub mod login_risk;
+pub mod password;
+pub mod phone;
+pub mod security_notifier;
+pub mod session;
+pub mod sms;
+pub mod system_config;
+pub mod system_log;
@@ -0,0 +1,404 @@
+// src/services/organization.rs
+use actix_web::web;
+use bigdecimal::BigDecimal;
+use chrono::{Duration, Utc};
+use tracing::info;
+use serde_json::json;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    app_state::AppState,
+    error::{AppError, ErrorCode},
+    extension::auth::AuthenticatedUser,
+    models::{
+        audit_event::AuditAction,
+        dto::{
+            auth::{AuthResponse, Claims, TokenType},
+            organization::{
+                AcceptOrganizationInvitationRequest, CreateOrganizationRequest, CreditOrganizationRequest,
+                InviteMemberRequest, SwitchOrganizationRequest, UpdateMemberRequest,
+            },
+        },
+        organization::{Organization, OrganizationInvitation, OrganizationMember, OrganizationMembership, OrganizationRole},
+        user::UserRole,
+    },
+    repositories::{self, organization::NewOrganizationInvitation},
+    services::{
+        audit::{self, AuditEntry},
+        auth::revoke_token,
+    },
+    utils::{jwt, otp, sha256::{keyed_hash, sha256_hash}},
+};
+
+const INVITATION_TTL_DAYS: i64 = 7;
+
+/// "Acme Corp." → "acme-corp-3f9a1c"; sonek aynı isimli organizasyonların çakışmasını önler
+fn slugify(name: &str) -> String {
+    let base: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let base = base.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-");
+    let suffix = &Uuid::new_v4().simple().to_string()[..6];
+    if base.is_empty() {
+        suffix.to_string()
+    } else {
+        format!("{}-{}", base.chars().take(100).collect::<String>(), suffix)
+    }
+}
+
+/// Organizasyonu ve isteği yapanın üyeliğini döner; üye olmayanlar için organizasyonun varlığı sızdırılmaz
+async fn load_membership(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    organization_public_id: Uuid,
+) -> Result<(Organization, OrganizationMember), AppError> {
+    let organization = repositories::organization::find_by_public_id(&app_state.db, organization_public_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("organization"))?;
+    let member = repositories::organization::find_member(&app_state.db, organization.id, auth_user.user_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("organization"))?;
+    Ok((organization, member))
+}
+
+async fn load_manager(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    organization_public_id: Uuid,
+) -> Result<(Organization, OrganizationMember), AppError> {
+    let (organization, member) = load_membership(app_state, auth_user, organization_public_id).await?;
+    if !member.role.can_manage_members() {
+        return Err(AppError::forbidden("organization owner or admin role required"));
+    }
+    Ok((organization, member))
+}
+
+async fn find_target_member(
+    app_state: &web::Data<AppState>,
+    organization_id: i32,
+    member_public_id: Uuid,
+) -> Result<OrganizationMember, AppError> {
+    let user_id = repositories::auth::find_internal_id(&app_state.db, member_public_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("member"))?;
+    repositories::organization::find_member(&app_state.db, organization_id, user_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("member"))
+}
+
+pub async fn create_organization(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    req: CreateOrganizationRequest,
+) -> Result<Organization, AppError> {
+    auth_user.deny_if_impersonated("creating an organization")?;
+    if auth_user.role == UserRole::Guest.to_string() {
+        return Err(AppError::forbidden("guest accounts cannot create organizations"));
+    }
+    req.validate()?;
+
+    let name = req.name.trim();
+    let mut tx = app_state.db.begin().await?;
+    let organization = repositories::organization::create(&mut *tx, name, &slugify(name), auth_user.user_id).await?;
+    repositories::organization::add_member(&mut *tx, organization.id, auth_user.user_id, OrganizationRole::Owner).await?;
+    tx.commit().await?;
+
+    info!("Organization {} created by user_id: {}", organization.id, auth_user.user_id);
+    audit::record(
+        app_state,
+        AuditEntry::for_user(AuditAction::OrganizationCreate, auth_user)
+            .diff(json!({ "organization_id": organization.public_id, "name": organization.name })),
+    )
+    .await;
+
+    Ok(organization)
+}
+
+pub async fn list_organizations(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+) -> Result<Vec<OrganizationMembership>, AppError> {
+    repositories::organization::list_for_user(&app_state.db, auth_user.user_id).await
+}
+
+pub async fn get_organization(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    organization_public_id: Uuid,
+) -> Result<Organization, AppError> {
+    let (organization, _) = load_membership(app_state, auth_user, organization_public_id).await?;
+    Ok(organization)
+}
+
+pub async fn list_members(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    organization_public_id: Uuid,
+) -> Result<Vec<OrganizationMember>, AppError> {
+    let (organization, _) = load_membership(app_state, auth_user, organization_public_id).await?;
+    repositories::organization::list_members(&app_state.db, organization.id).await
+}
+
+pub async fn invite_member(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    organization_public_id: Uuid,
+    req: InviteMemberRequest,
+) -> Result<OrganizationInvitation, AppError> {
+    auth_user.deny_if_impersonated("inviting organization members")?;
+    req.validate()?;
+    let (organization, manager) = load_manager(app_state, auth_user, organization_public_id).await?;
+    match req.role {
+        OrganizationRole::Owner => return Err(AppError::bad_request("ownership cannot be granted by invitation")),
+        OrganizationRole::Admin if manager.role != OrganizationRole::Owner => {
+            return Err(AppError::forbidden("only owners can invite admins"));
+        }
+        _ => {}
+    }
+
+    let email = req.email.trim().to_lowercase();
+    let token = otp::generate_token();
+    let invitation = repositories::organization::create_invitation(
+        &app_state.db,
+        &NewOrganizationInvitation {
+            organization_id: organization.id,
+            email: &email,
+            role: req.role,
+            token_hash: &sha256_hash(&token),
+            invited_by: auth_user.user_id,
+            expires_at: Utc::now() + Duration::days(INVITATION_TTL_DAYS),
+        },
+    )
+    .await?;
+
+    let link = format!(
+        "{}/organizations/join?token={}",
+        app_state.config.public_base_url.trim_end_matches('/'),
+        token
+    );
+    app_state
+        .email_sender
+        .send(
+            &email,
+            &format!("{} organizasyonuna davet edildiniz", organization.name),
+            &format!(
+                "Merhaba,\n\n{} sizi {} organizasyonuna katılmaya davet etti. Daveti kabul etmek için bu e-posta adresiyle giriş yapıp aşağıdaki bağlantıya tıklayın. Bağlantı {} gün geçerlidir.\n\n{}",
+                manager.full_name,
+                organization.name,
+                INVITATION_TTL_DAYS,
+                link
+            ),
+        )
+        .await?;
+
+    audit::record(
+        app_state,
+        AuditEntry::for_user(AuditAction::OrganizationInvite, auth_user).diff(json!({
+            "organization_id": organization.public_id,
+            // Denetim kaydı silinemediği için davet edilen adresin yalnızca anahtarlı özeti tutulur
+            "email_hash": keyed_hash(&email, &app_state.jwt_secret),
+            "role": invitation.role.to_string(),
+        })),
+    )
+    .await;
+
+    Ok(invitation)
+}
+
+pub async fn list_invitations(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    organization_public_id: Uuid,
+) -> Result<Vec<OrganizationInvitation>, AppError> {
+    let (organization, _) = load_manager(app_state, auth_user, organization_public_id).await?;
+    repositories::organization::list_pending_invitations(&app_state.db, organization.id).await
+}
+
+/// Davet yalnızca davet edilen e-posta adresine sahip hesapla kabul edilebilir
+pub async fn accept_invitation(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    req: AcceptOrganizationInvitationRequest,
+) -> Result<OrganizationMembership, AppError> {
+    auth_user.deny_if_impersonated("joining an organization")?;
+    req.validate()?;
+    let user = repositories::auth::find_by_id(&app_state.db, auth_user.user_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("user"))?;
+
+    let mut tx = app_state.db.begin().await?;
+    let invitation = repositories::organization::accept_invitation(&mut *tx, &sha256_hash(&req.token), &user.email)
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("Invitation is invalid, expired or for another email".to_string())
+            .with_code(ErrorCode::InvalidInvitation))?;
+    if !repositories::organization::add_member(&mut *tx, invitation.organization_id, user.id, invitation.role).await? {
+        return Err(AppError::Conflict("Already a member of this organization".to_string()).with_code(ErrorCode::AlreadyMember));
+    }
+    tx.commit().await?;
+    info!("User {} joined organization {}", user.id, invitation.organization_id);
+
+    let membership = repositories::organization::find_membership(&app_state.db, invitation.organization_id, user.id)
+        .await?
+        .ok_or_else(|| AppError::internal_error("membership not found after joining"))?;
+    audit::record(
+        app_state,
+        AuditEntry::for_user(AuditAction::OrganizationJoin, auth_user)
+            .diff(json!({ "organization_id": membership.public_id, "role": invitation.role.to_string() })),
+    )
+    .await;
+
+    Ok(membership)
+}
+
+pub async fn update_member(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    organization_public_id: Uuid,
+    member_public_id: Uuid,
+    req: UpdateMemberRequest,
+) -> Result<OrganizationMember, AppError> {
+    auth_user.deny_if_impersonated("changing organization members")?;
+    req.validate()?;
+    let (organization, manager) = load_manager(app_state, auth_user, organization_public_id).await?;
+    let target = find_target_member(app_state, organization.id, member_public_id).await?;
+
+    if let Some(role) = req.role.filter(|r| *r != target.role) {
+        // Sahiplik ve yöneticilik yalnızca sahipler tarafından verilip alınabilir
+        let touches_privileged = matches!(role, OrganizationRole::Owner | OrganizationRole::Admin)
+            || target.role != OrganizationRole::Member;
+        if touches_privileged && manager.role != OrganizationRole::Owner {
+            return Err(AppError::forbidden("only owners can change admin or owner roles"));
+        }
+        if target.role == OrganizationRole::Owner
+            && repositories::organization::count_owners(&app_state.db, organization.id).await? <= 1
+        {
+            return Err(AppError::Conflict("An organization must keep at least one owner".to_string()).with_code(ErrorCode::LastOwner));
+        }
+        repositories::organization::update_member_role(&app_state.db, organization.id, target.user_id, role).await?;
+    }
+
+    if req.clear_spending_cap {
+        repositories::organization::set_spending_cap(&app_state.db, organization.id, target.user_id, None).await?;
+    } else if let Some(cap) = req.spending_cap {
+        repositories::organization::set_spending_cap(&app_state.db, organization.id, target.user_id, Some(cap)).await?;
+    }
+
+    let updated = find_target_member(app_state, organization.id, member_public_id).await?;
+    audit::record(
+        app_state,
+        AuditEntry::for_user(AuditAction::OrganizationMemberUpdate, auth_user)
+            .target(updated.user_id)
+            .diff(json!({
+                "organization_id": organization.public_id,
+                "role": { "from": target.role.to_string(), "to": updated.role.to_string() },
+                "spending_cap": { "from": target.spending_cap.as_ref().map(|c| c.to_string()), "to": updated.spending_cap.as_ref().map(|c| c.to_string()) },
+            })),
+    )
+    .await;
+
+    Ok(updated)
+}
+
+/// Yöneticiler üye çıkarabilir; her üye kendisi ayrılabilir. Son sahip ayrılamaz.
+pub async fn remove_member(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    organization_public_id: Uuid,
+    member_public_id: Uuid,
+) -> Result<(), AppError> {
+    auth_user.deny_if_impersonated("removing organization members")?;
+    let (organization, actor) = load_membership(app_state, auth_user, organization_public_id).await?;
+    let target = find_target_member(app_state, organization.id, member_public_id).await?;
+
+    let leaving = target.user_id == actor.user_id;
+    if !leaving {
+        if !actor.role.can_manage_members() {
+            return Err(AppError::forbidden("organization owner or admin role required"));
+        }
+        if target.role != OrganizationRole::Member && actor.role != OrganizationRole::Owner {
+            return Err(AppError::forbidden("only owners can remove admins or owners"));
+        }
+    }
+    if target.role == OrganizationRole::Owner
+        && repositories::organization::count_owners(&app_state.db, organization.id).await? <= 1
+    {
+        return Err(AppError::Conflict("An organization must keep at least one owner".to_string()).with_code(ErrorCode::LastOwner));
+    }
+
+    repositories::organization::remove_member(&app_state.db, organization.id, target.user_id).await?;
+    info!("User {} removed from organization {} by user_id: {}", target.user_id, organization.id, actor.user_id);
+    audit::record(
+        app_state,
+        AuditEntry::for_user(AuditAction::OrganizationMemberRemove, auth_user)
+            .target(target.user_id)
+            .diff(json!({ "organization_id": organization.public_id, "role": target.role.to_string(), "left": leaving })),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Aktif organizasyonu değiştirir: aynı oturuma bağlı, `org` claim'i güncellenmiş yeni bir token üretir
+/// ve eski token'ı geçersiz kılar
+pub async fn switch_organization(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    req: SwitchOrganizationRequest,
+) -> Result<AuthResponse, AppError> {
+    auth_user.deny_if_impersonated("switching organizations")?;
+    if let Some(organization_public_id) = req.organization_id {
+        load_membership(app_state, auth_user, organization_public_id).await?;
+    }
+    let user = repositories::auth::find_by_id(&app_state.db, auth_user.user_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("user"))?;
+
+    let mut claims = Claims::from_user(&user, TokenType::Access, &app_state.config).with_organization(req.organization_id);
+    if let Some(session_id) = auth_user.session_id {
+        claims = claims.with_session(session_id);
+    }
+    let token = jwt::encode_claims(&claims, &app_state.jwt_secret)?;
+    revoke_token(app_state, auth_user).await?;
+
+    Ok(AuthResponse {
+        token,
+        user_id: user.public_id,
+        username: user.username,
+        role: user.user_role.to_string(),
+        csrf_token: None,
+    })
+}
+
+/// Sistem yöneticisinin organizasyon havuzuna token yüklemesi
+pub async fn credit_organization(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+    organization_public_id: Uuid,
+    req: CreditOrganizationRequest,
+) -> Result<BigDecimal, AppError> {
+    admin.require_admin()?;
+    admin.deny_if_impersonated("crediting organizations")?;
+    req.validate()?;
+
+    let organization = repositories::organization::find_by_public_id(&app_state.db, organization_public_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("organization"))?;
+    let amount = req.amount;
+    let balance = repositories::organization::credit(&app_state.db, organization.id, &amount)
+        .await?
+        .ok_or_else(|| AppError::not_found("organization"))?;
+
+    info!("Organization {} credited with {} tokens by admin {}", organization.id, amount, admin.user_id);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::OrganizationCredit, &admin.client)
+            .actor(admin.user_id)
+            .diff(json!({ "organization_id": organization.public_id, "amount": amount.to_string(), "balance": balance.to_string() })),
+    )
+    .await;
+
+    Ok(balance)
+}
@@ -0,0 +1,210 @@
+// src/services/password.rs
+use actix_web::web;
+use deadpool_redis::redis::{self, AsyncCommands};
+use tracing::{debug, error, info};
+use validator::Validate;
+
+use crate::{
+    app_state::AppState,
+    error::{AppError, ErrorCode},
+    extension::auth::AuthenticatedUser,
+    models::{
+        audit_event::AuditAction,
+        dto::{
+            auth::{Claims, ForgotPasswordRequest, ResetPasswordRequest, TokenType},
+            user::ChangePasswordRequest,
+        },
+        user::{User, UserRole},
+    },
+    repositories,
+    services::{
+        audit::{self, AuditEntry},
+        auth_cache,
+    },
+    utils::{client_info::ClientInfo, jwt, password, rate_limit, sha256::sha256_hash},
+};
+
+const MAX_RESET_PER_EMAIL: u32 = 3;
+const MAX_RESET_PER_IP: u32 = 20;
+const RESET_WINDOW_SECS: i64 = 3600;
+
+/// Anahtardaki jti bağlantınınkiyle aynıysa siler ve 1 döner; karşılaştırma ve silme tek adımdır
+const CONSUME_RESET_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+/// Kullanıcıya en son gönderilen sıfırlama bağlantısının jti'si; yeni bağlantı öncekileri geçersiz kılar
+fn reset_nonce_key(user_id: i32) -> String {
+    format!("password_reset_current:{}", user_id)
+}
+
+pub async fn change_password(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    req: ChangePasswordRequest,
+) -> Result<(), AppError> {
+    auth_user.deny_if_impersonated("changing the password")?;
+    req.validate()?;
+
+    let user = repositories::auth::find_by_id(&app_state.db, auth_user.user_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("user"))?;
+    if !password::verify_password(&req.current_password, &user.password_hash, &app_state.password_hashing)? {
+        return Err(AppError::Unauthorized("Invalid credentials".to_string()).with_code(ErrorCode::InvalidCredentials));
+    }
+    app_state
+        .password_policy
+        .check(&req.new_password, &[&user.username, &user.email, &user.full_name])
+        .await?;
+
+    let hashed_password = password::hash_password(&req.new_password, &app_state.password_hashing)?;
+    repositories::auth::update_password(&app_state.db, user.id, &hashed_password).await?;
+
+    // Mevcut oturum dışındaki tüm oturumlar kapatılır
+    let revoked = repositories::user_session::revoke_all_for_user(&app_state.db, user.id, auth_user.session_id).await?;
+    auth_cache::forget_sessions(&mut app_state.redis().await?, user.id).await?;
+    info!("Password changed for user_id: {} ({} other sessions revoked)", user.id, revoked);
+    audit::record(
+        app_state,
+        AuditEntry::for_user(AuditAction::PasswordChange, auth_user)
+            .diff(serde_json::json!({ "revoked_sessions": revoked })),
+    )
+    .await;
+    Ok(())
+}
+
+/// Kayıtlı adrese kısa ömürlü parola sıfırlama bağlantısı gönderir; hesabın varlığı sızdırılmaz
+pub async fn request_password_reset(
+    app_state: &web::Data<AppState>,
+    req: ForgotPasswordRequest,
+    client: &ClientInfo,
+) -> Result<(), AppError> {
+    req.validate()?;
+    let email = req.email.trim().to_lowercase();
+
+    let mut conn = app_state.redis().await?;
+    let ip = client.ip_address.as_deref().unwrap_or("unknown");
+    rate_limit::enforce(
+        &mut conn,
+        &format!("password_reset_ip:{}", ip),
+        MAX_RESET_PER_IP,
+        RESET_WINDOW_SECS,
+        "Too many password reset requests from this address.",
+    )
+    .await?;
+    rate_limit::enforce(
+        &mut conn,
+        &format!("password_reset_email:{}", sha256_hash(&email)),
+        MAX_RESET_PER_EMAIL,
+        RESET_WINDOW_SECS,
+        "Too many password reset requests for this email.",
+    )
+    .await?;
+
+    let user = match repositories::auth::find_by_email(&app_state.db, &email).await? {
+        Some(user) if user.is_active != Some(false) && user.user_role != UserRole::Guest => user,
+        _ => {
+            debug!("Password reset requested for unknown or inactive email");
+            return Ok(());
+        }
+    };
+
+    spawn_reset_link(app_state, user);
+    Ok(())
+}
+
+/// Bağlantıyı arka planda gönderir; yanıt süresi hesabın var olup olmadığını ele vermez
+pub(crate) fn spawn_reset_link(app_state: &web::Data<AppState>, user: User) {
+    let app_state = app_state.clone();
+    // Kapanış, gönderim bitene kadar bekler
+    let shutdown_guard = app_state.shutdown.subscribe();
+    actix_web::rt::spawn(async move {
+        let _shutdown_guard = shutdown_guard;
+        if let Err(e) = send_reset_link(&app_state, &user).await {
+            error!("Password reset email to user_id {} failed: {}", user.id, e);
+        }
+    });
+}
+
+/// Kullanıcıya kısa ömürlü, tek kullanımlık parola sıfırlama bağlantısı gönderir
+async fn send_reset_link(app_state: &web::Data<AppState>, user: &User) -> Result<(), AppError> {
+    let claims = Claims::new(user.public_id, user.user_role.to_string(), TokenType::Reset, &app_state.config);
+    let lifetime_secs = TokenType::Reset.lifetime_secs(&app_state.config);
+    app_state
+        .redis()
+        .await?
+        .set_ex::<_, _, ()>(reset_nonce_key(user.id), &claims.jti, lifetime_secs as u64)
+        .await?;
+    let token = jwt::encode_claims(&claims, &app_state.jwt_secret)?;
+    let link = format!(
+        "{}/auth/reset-password?token={}",
+        app_state.config.public_base_url.trim_end_matches('/'),
+        token
+    );
+    app_state
+        .email_sender
+        .send(
+            &user.email,
+            "Parola sıfırlama",
+            &format!(
+                "Merhaba {},\n\nParolanızı sıfırlamak için aşağıdaki bağlantıya tıklayın. Bağlantı {} dakika geçerlidir.\n\n{}\n\nBu isteği siz yapmadıysanız bu e-postayı yok sayabilirsiniz.",
+                user.full_name,
+                lifetime_secs / 60,
+                link
+            ),
+        )
+        .await?;
+    debug!("Password reset link sent to user_id: {}", user.id);
+
+    Ok(())
+}
+
+pub async fn reset_password(
+    app_state: &web::Data<AppState>,
+    req: ResetPasswordRequest,
+    client: &ClientInfo,
+) -> Result<(), AppError> {
+    req.validate()?;
+    let claims = jwt::decode_jwt(&req.token, TokenType::Reset, &app_state.config)?;
+
+    let user = repositories::auth::find_by_public_id(&app_state.db, claims.sub)
+        .await?
+        .ok_or_else(|| AppError::not_found("user"))?;
+    app_state
+        .password_policy
+        .check(&req.new_password, &[&user.username, &user.email, &user.full_name])
+        .await?;
+
+    // Yalnızca en son gönderilen bağlantı geçerlidir ve kullanılınca silinir; politika hatasında token yakılmaz
+    let mut conn = app_state.redis().await?;
+    let consumed: i64 = redis::cmd("EVAL")
+        .arg(CONSUME_RESET_SCRIPT)
+        .arg(1)
+        .arg(reset_nonce_key(user.id))
+        .arg(&claims.jti)
+        .query_async(&mut conn)
+        .await?;
+    if consumed == 0 {
+        return Err(AppError::Unauthorized("Reset link has already been used or was superseded".to_string())
+            .with_code(ErrorCode::LinkUsed));
+    }
+
+    let hashed_password = password::hash_password(&req.new_password, &app_state.password_hashing)?;
+    repositories::auth::update_password(&app_state.db, user.id, &hashed_password).await?;
+    repositories::auth::set_password_reset_required(&app_state.db, user.id, false).await?;
+    let revoked = repositories::user_session::revoke_all_for_user(&app_state.db, user.id, None).await?;
+    auth_cache::forget_sessions(&mut app_state.redis().await?, user.id).await?;
+    info!("Password reset for user_id: {} ({} sessions revoked)", user.id, revoked);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::PasswordReset, client)
+            .target(user.id)
+            .diff(serde_json::json!({ "revoked_sessions": revoked })),
+    )
+    .await;
+
+    Ok(())
+}
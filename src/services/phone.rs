@@ -0,0 +1,119 @@
+// src/services/phone.rs
+use std::collections::HashMap;
+
+use actix_web::web;
+use deadpool_redis::redis::{self, AsyncCommands};
+use tracing::debug;
+use validator::Validate;
+
+use crate::{
+    app_state::AppState,
+    error::{AppError, ErrorCode},
+    extension::auth::AuthenticatedUser,
+    models::{
+        audit_event::AuditAction,
+        dto::user::{PhoneVerificationConfirm, PhoneVerificationRequest},
+    },
+    repositories,
+    services::audit::{self, AuditEntry},
+    utils::{otp, phone},
+};
+
+const OTP_DIGITS: u32 = 6;
+const OTP_TTL_SECS: i64 = 10 * 60;
+const OTP_MAX_ATTEMPTS: i64 = 5;
+const OTP_RESEND_COOLDOWN_SECS: u64 = 60;
+
+fn otp_key(user_id: i32) -> String {
+    format!("phone_otp:{}", user_id)
+}
+
+fn cooldown_key(user_id: i32) -> String {
+    format!("phone_otp_cooldown:{}", user_id)
+}
+
+/// Numarayı normalize eder ve kullanıcıya doğrulama kodu gönderir
+pub async fn request_verification(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    req: PhoneVerificationRequest,
+) -> Result<String, AppError> {
+    auth_user.deny_if_impersonated("changing the phone number")?;
+    req.validate()?;
+    let phone_number = phone::normalize_e164(&req.phone_number, &app_state.config.default_phone_country_code)?;
+
+    let mut conn = app_state.redis().await?;
+    // Aynı kullanıcıya kısa sürede tekrar SMS gönderilmesini engelle. Anahtar ve süresi tek
+    // komutla yazılır; aksi halde arada süreç ölürse anahtar hiç silinmez
+    let first_request: Option<String> = redis::cmd("SET")
+        .arg(cooldown_key(auth_user.user_id))
+        .arg(1)
+        .arg("NX")
+        .arg("EX")
+        .arg(OTP_RESEND_COOLDOWN_SECS)
+        .query_async(&mut conn)
+        .await?;
+    if first_request.is_none() {
+        return Err(AppError::Conflict("A verification code was sent recently, please wait.".to_string())
+            .with_code(ErrorCode::VerificationCodeRecentlySent));
+    }
+
+    let code = otp::generate_numeric_code(OTP_DIGITS);
+    let key = otp_key(auth_user.user_id);
+    conn.del::<_, ()>(&key).await?;
+    conn.hset_multiple::<_, _, _, ()>(
+        &key,
+        &[
+            ("phone", phone_number.as_str()),
+            ("code_hash", otp::hash_code(&code).as_str()),
+        ],
+    )
+    .await?;
+    conn.expire::<_, ()>(&key, OTP_TTL_SECS).await?;
+
+    app_state
+        .sms_sender
+        .send(&phone_number, &format!("Doğrulama kodunuz: {}", code))
+        .await?;
+    debug!("Phone verification code sent to user_id: {}", auth_user.user_id);
+
+    Ok(phone::mask(&phone_number))
+}
+
+/// Kodu doğrular; başarılıysa numarayı kaydedip `phone_verified` alanını işaretler
+pub async fn confirm_verification(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    req: PhoneVerificationConfirm,
+) -> Result<String, AppError> {
+    auth_user.deny_if_impersonated("changing the phone number")?;
+    req.validate()?;
+
+    let key = otp_key(auth_user.user_id);
+    let mut conn = app_state.redis().await?;
+    let pending: HashMap<String, String> = conn.hgetall(&key).await?;
+    let (Some(phone_number), Some(code_hash)) = (pending.get("phone"), pending.get("code_hash")) else {
+        return Err(AppError::bad_request("no pending phone verification or code expired"));
+    };
+
+    let attempts: i64 = conn.hincr(&key, "attempts", 1).await?;
+    if attempts > OTP_MAX_ATTEMPTS {
+        conn.del::<_, ()>(&key).await?;
+        return Err(AppError::Forbidden("Too many invalid attempts, request a new code.".to_string()));
+    }
+    if !otp::code_matches(&req.code, code_hash) {
+        return Err(AppError::Unauthorized("Invalid verification code".to_string()).with_code(ErrorCode::InvalidVerificationCode));
+    }
+
+    repositories::auth::set_phone_verified(&app_state.db, auth_user.user_id, phone_number).await?;
+    conn.del::<_, ()>(&key).await?;
+    debug!("Phone number verified for user_id: {}", auth_user.user_id);
+    audit::record(
+        app_state,
+        AuditEntry::for_user(AuditAction::PhoneVerified, auth_user)
+            .diff(serde_json::json!({ "phone_number": phone::mask(phone_number) })),
+    )
+    .await;
+
+    Ok(phone::mask(phone_number))
+}
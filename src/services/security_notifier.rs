@@ -0,0 +1,84 @@
+// src/services/security_notifier.rs
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use tracing::info;
+
+use crate::{
+    config::AppConfig,
+    error::AppError,
+    models::user::User,
+    services::email::EmailSender,
+};
+
+/// Kullanıcıya iletilen şüpheli giriş uyarısının içeriği
+#[derive(Debug, Clone)]
+pub struct LoginAlert {
+    pub reasons: Vec<&'static str>,
+    pub ip_address: Option<String>,
+    pub device: Option<&'static str>,
+    pub os: Option<&'static str>,
+    pub location: Option<String>,
+    /// Tek tıkla oturumları kapatıp parola sıfırlamayı zorunlu kılan bağlantı
+    pub disown_url: String,
+}
+
+/// Güvenlik bildirimlerinin kullanıcıya ulaştırıldığı kanal için ortak arayüz
+pub trait SecurityNotifier: Send + Sync {
+    fn suspicious_login<'a>(&'a self, user: &'a User, alert: &'a LoginAlert) -> BoxFuture<'a, Result<(), AppError>>;
+}
+
+/// Geliştirme ortamı için: uyarıyı göndermek yerine loglar
+pub struct LoggingSecurityNotifier;
+
+impl SecurityNotifier for LoggingSecurityNotifier {
+    fn suspicious_login<'a>(&'a self, user: &'a User, alert: &'a LoginAlert) -> BoxFuture<'a, Result<(), AppError>> {
+        Box::pin(async move {
+            info!(
+                "[security] suspicious login user_id={} reasons={:?} ip={:?} location={:?} disown_url={}",
+                user.id, alert.reasons, alert.ip_address, alert.location, alert.disown_url
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Uyarıyı yapılandırılmış e-posta sağlayıcısı üzerinden gönderir
+pub struct EmailSecurityNotifier {
+    email_sender: Arc<dyn EmailSender>,
+}
+
+impl EmailSecurityNotifier {
+    pub fn new(email_sender: Arc<dyn EmailSender>) -> Self {
+        Self { email_sender }
+    }
+}
+
+impl SecurityNotifier for EmailSecurityNotifier {
+    fn suspicious_login<'a>(&'a self, user: &'a User, alert: &'a LoginAlert) -> BoxFuture<'a, Result<(), AppError>> {
+        Box::pin(async move {
+            let body = format!(
+                "Merhaba {},\n\nHesabınıza alışılmadık bir yerden giriş yapıldı.\n\nCihaz: {} / {}\nIP adresi: {}\nKonum: {}\n\nBu giriş size aitse herhangi bir işlem yapmanıza gerek yok. Size ait değilse aşağıdaki bağlantıya tıklayın; tüm oturumlarınız kapatılacak ve parolanızı sıfırlamanız istenecek.\n\n{}",
+                user.full_name,
+                alert.device.unwrap_or("bilinmiyor"),
+                alert.os.unwrap_or("bilinmiyor"),
+                alert.ip_address.as_deref().unwrap_or("bilinmiyor"),
+                alert.location.as_deref().unwrap_or("bilinmiyor"),
+                alert.disown_url
+            );
+            self.email_sender.send(&user.email, "Hesabınıza yeni bir giriş yapıldı", &body).await
+        })
+    }
+}
+
+/// Yapılandırmaya göre bildirim kanalını seçer
+pub fn security_notifier_from_config(
+    config: &AppConfig,
+    email_sender: Arc<dyn EmailSender>,
+) -> Result<Arc<dyn SecurityNotifier>, AppError> {
+    match config.security_notifier.as_str() {
+        "log" => Ok(Arc::new(LoggingSecurityNotifier)),
+        "email" => Ok(Arc::new(EmailSecurityNotifier::new(email_sender))),
+        other => Err(AppError::internal_error(&format!("Unknown security notifier: {}", other))),
+    }
+}
@@ -0,0 +1,51 @@
+// src/services/session.rs
+use actix_web::web;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::{audit_event::AuditAction, dto::user::SessionResponse},
+    repositories,
+    services::{
+        audit::{self, AuditEntry},
+        auth_cache,
+    },
+};
+
+pub async fn list_sessions(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+) -> Result<Vec<SessionResponse>, AppError> {
+    let sessions = repositories::user_session::list_active_by_user(&app_state.db, auth_user.user_id).await?;
+
+    Ok(sessions
+        .into_iter()
+        .map(|s| SessionResponse::from_session(s, auth_user.session_id))
+        .collect())
+}
+
+pub async fn revoke_session(
+    app_state: &web::Data<AppState>,
+    auth_user: &AuthenticatedUser,
+    session_id: Uuid,
+) -> Result<(), AppError> {
+    auth_user.deny_if_impersonated("revoking sessions")?;
+
+    let revoked = repositories::user_session::revoke(&app_state.db, session_id, auth_user.user_id).await?;
+    if !revoked {
+        return Err(AppError::not_found("session"));
+    }
+    auth_cache::forget_sessions(&mut app_state.redis().await?, auth_user.user_id).await?;
+
+    debug!("Session {} revoked by user_id: {}", session_id, auth_user.user_id);
+    audit::record(
+        app_state,
+        AuditEntry::for_user(AuditAction::SessionRevoke, auth_user)
+            .diff(serde_json::json!({ "session_id": session_id })),
+    )
+    .await;
+    Ok(())
+}
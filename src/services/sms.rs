@@ -0,0 +1,91 @@
+// src/services/sms.rs
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use tracing::{error, info};
+use serde_json::json;
+
+use crate::{config::AppConfig, error::AppError, utils::{phone, telemetry}};
+
+/// SMS gönderim sağlayıcıları için ortak arayüz
+pub trait SmsSender: Send + Sync {
+    fn send<'a>(&'a self, to: &'a str, body: &'a str) -> BoxFuture<'a, Result<(), AppError>>;
+}
+
+/// Geliştirme ortamı için: mesajı göndermek yerine loglar
+pub struct LoggingSmsSender;
+
+impl SmsSender for LoggingSmsSender {
+    fn send<'a>(&'a self, to: &'a str, body: &'a str) -> BoxFuture<'a, Result<(), AppError>> {
+        Box::pin(async move {
+            // Geliştiricinin kodu okuyabilmesi için mesaj olduğu gibi yazılır; yalnızca numara maskelenir
+            info!("[sms] to={} body={}", phone::mask(to), body);
+            Ok(())
+        })
+    }
+}
+
+/// JSON kabul eden genel bir HTTP SMS ağ geçidine POST isteği atar
+pub struct HttpSmsSender {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+    sender_id: Option<String>,
+}
+
+impl HttpSmsSender {
+    pub fn new(url: String, token: Option<String>, sender_id: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            token,
+            sender_id,
+        }
+    }
+}
+
+impl SmsSender for HttpSmsSender {
+    fn send<'a>(&'a self, to: &'a str, body: &'a str) -> BoxFuture<'a, Result<(), AppError>> {
+        Box::pin(async move {
+            let mut request = self.client.post(&self.url).json(&json!({
+                "to": to,
+                "from": self.sender_id,
+                "message": body,
+            }));
+            if let Some(token) = &self.token {
+                request = request.bearer_auth(token);
+            }
+
+            let mut headers = reqwest::header::HeaderMap::new();
+            telemetry::inject_trace_headers(&mut headers);
+            let response = request.headers(headers).send().await.map_err(|e| {
+                error!("SMS gateway request failed: {}", e);
+                AppError::internal_error("SMS gateway unreachable")
+            })?;
+            if !response.status().is_success() {
+                error!("SMS gateway returned status {}", response.status());
+                return Err(AppError::internal_error("SMS could not be sent"));
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Yapılandırmaya göre SMS sağlayıcısını seçer
+pub fn sms_sender_from_config(config: &AppConfig) -> Result<Arc<dyn SmsSender>, AppError> {
+    match config.sms_provider.as_str() {
+        "log" => Ok(Arc::new(LoggingSmsSender)),
+        "http" => {
+            let url = config
+                .sms_gateway_url
+                .clone()
+                .ok_or_else(|| AppError::internal_error("SMS_GATEWAY_URL is required when SMS_PROVIDER=http"))?;
+            Ok(Arc::new(HttpSmsSender::new(
+                url,
+                config.sms_gateway_token.clone(),
+                config.sms_sender_id.clone(),
+            )))
+        }
+        other => Err(AppError::internal_error(&format!("Unknown SMS provider: {}", other))),
+    }
+}
@@ -0,0 +1,190 @@
+// src/services/system_config.rs
+use std::sync::{Arc, RwLock};
+
+use actix_web::web;
+use tracing::{error, info, warn};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use validator::Validate;
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::{
+        audit_event::AuditAction,
+        dto::system_config::UpdateSettingRequest,
+        system_config::{RuntimeSettings, SystemConfigChange, SystemConfigEntry},
+    },
+    repositories,
+    services::{
+        audit::{self, AuditEntry},
+        config_sync,
+    },
+};
+
+/// Ayar değiştiğinde tüm örneklere haber veren Redis kanalı
+pub const CHANGE_CHANNEL: &str = "system_config:changed";
+const MAX_HISTORY_LIMIT: i64 = 500;
+const MAX_MAINTENANCE_MESSAGE_LEN: usize = 1000;
+
+/// Çalışma anı ayarlarının süreç içi önbelleği. Okumalar veritabanına gitmez;
+/// değişiklikler `reload` ile toptan değiştirilir.
+pub struct SystemConfigStore {
+    defaults: RuntimeSettings,
+    current: RwLock<Arc<RuntimeSettings>>,
+}
+
+impl SystemConfigStore {
+    pub fn new(defaults: RuntimeSettings) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(defaults.clone())),
+            defaults,
+        }
+    }
+
+    pub fn current(&self) -> Arc<RuntimeSettings> {
+        self.current.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn replace(&self, settings: RuntimeSettings) {
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(settings);
+    }
+
+    /// Kayıtlı değerleri varsayılanların üzerine yazar. Bilinmeyen ya da tipi uymayan
+    /// anahtarlar (ör. kaldırılmış bir ayar) atlanır ve loglanır.
+    fn merge(&self, entries: &[SystemConfigEntry]) -> RuntimeSettings {
+        let mut merged = self.defaults.clone();
+        for entry in entries {
+            match apply(&merged, &entry.key, &entry.value) {
+                Ok(settings) => merged = settings,
+                Err(e) => warn!("Ignoring invalid system setting {}: {}", entry.key, e),
+            }
+        }
+        merged
+    }
+}
+
+/// Tek bir anahtarı uygulanmış ayarları döner; anahtar tanımsızsa ya da değer tipe uymuyorsa hata verir
+fn apply(settings: &RuntimeSettings, key: &str, value: &Value) -> Result<RuntimeSettings, String> {
+    let mut object = match serde_json::to_value(settings) {
+        Ok(Value::Object(object)) => object,
+        _ => Map::new(),
+    };
+    if !object.contains_key(key) {
+        return Err(format!("unknown setting '{}'", key));
+    }
+    object.insert(key.to_string(), value.clone());
+    serde_json::from_value(Value::Object(object)).map_err(|e| format!("invalid value for '{}': {}", key, e))
+}
+
+fn check(settings: &RuntimeSettings) -> Result<(), String> {
+    if settings.default_model.trim().is_empty() {
+        return Err("default_model must not be empty".to_string());
+    }
+    if settings
+        .maintenance_message
+        .as_ref()
+        .is_some_and(|m| m.chars().count() > MAX_MAINTENANCE_MESSAGE_LEN)
+    {
+        return Err(format!("maintenance_message must be at most {} characters", MAX_MAINTENANCE_MESSAGE_LEN));
+    }
+    Ok(())
+}
+
+/// Herkese açık ayarlar; istemciler kayıt formunu ve bakım duyurusunu buna göre gösterir
+#[derive(Debug, Serialize)]
+pub struct PublicSettings {
+    pub registration_open: bool,
+    pub default_model: String,
+    pub maintenance_message: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SettingsOverview {
+    /// Varsayılanlarla birleştirilmiş, şu an geçerli değerler
+    pub effective: Arc<RuntimeSettings>,
+    /// Yönetici tarafından değiştirilmiş anahtarlar
+    pub overrides: Vec<SystemConfigEntry>,
+}
+
+/// Ayarları veritabanından yeniden okur ve önbelleği günceller
+pub async fn reload(app_state: &web::Data<AppState>) -> Result<(), AppError> {
+    let entries = repositories::system_config::list(&app_state.db).await?;
+    let settings = app_state.system_config.merge(&entries);
+    app_state.system_config.replace(settings);
+    Ok(())
+}
+
+pub fn public_settings(app_state: &web::Data<AppState>) -> PublicSettings {
+    let settings = app_state.system_config.current();
+    PublicSettings {
+        registration_open: settings.registration_open,
+        default_model: settings.default_model.clone(),
+        maintenance_message: settings.maintenance_message.clone(),
+    }
+}
+
+pub async fn list_settings(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+) -> Result<SettingsOverview, AppError> {
+    admin.require_admin()?;
+
+    let overrides = repositories::system_config::list(&app_state.db).await?;
+    Ok(SettingsOverview {
+        effective: app_state.system_config.current(),
+        overrides,
+    })
+}
+
+/// Ayarı doğrulayıp kaydeder, bu örnekte hemen uygular ve diğer örneklere yayınlar
+pub async fn update_setting(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+    key: &str,
+    req: UpdateSettingRequest,
+) -> Result<Arc<RuntimeSettings>, AppError> {
+    admin.require_admin()?;
+    admin.deny_if_impersonated("changing system settings")?;
+    req.validate()?;
+
+    let candidate = apply(&app_state.system_config.current(), key, &req.value)
+        .and_then(|settings| check(&settings).map(|_| settings))
+        .map_err(AppError::ValidationError)?;
+
+    let previous = serde_json::to_value(app_state.system_config.current().as_ref())
+        .ok()
+        .and_then(|v| v.get(key).cloned());
+    repositories::system_config::set(&app_state.db, key, &req.value, req.description.as_deref(), admin.user_id).await?;
+    info!("System setting {} updated by user_id: {}", key, admin.user_id);
+    audit::record(
+        app_state,
+        AuditEntry::new(AuditAction::SettingsUpdate, &admin.client)
+            .actor(admin.user_id)
+            .diff(json!({ "key": key, "old": previous, "new": req.value })),
+    )
+    .await;
+
+    // Yayın başarısız olsa bile bu örnek yeni değeri kullanır; diğerleri yeniden bağlandıklarında yükler
+    if let Err(e) = reload(app_state).await {
+        error!("Failed to reload system settings after update, applying locally: {}", e);
+        app_state.system_config.replace(candidate);
+    }
+    if let Err(e) = config_sync::publish(app_state, CHANGE_CHANNEL, key).await {
+        error!("Failed to publish system setting change {}: {}", key, e);
+    }
+
+    Ok(app_state.system_config.current())
+}
+
+pub async fn list_history(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+    key: Option<&str>,
+    limit: i64,
+) -> Result<Vec<SystemConfigChange>, AppError> {
+    admin.require_admin()?;
+
+    repositories::system_config::history(&app_state.db, key, limit.clamp(1, MAX_HISTORY_LIMIT)).await
+}
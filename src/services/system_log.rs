@@ -0,0 +1,100 @@
+// src/services/system_log.rs
+use std::time::Duration;
+
+use actix_web::web;
+use chrono::Utc;
+use tracing::{error, info, Instrument};
+
+use crate::{
+    app_state::AppState,
+    error::AppError,
+    extension::auth::AuthenticatedUser,
+    models::system_log::{NewSystemLog, SystemLog},
+    repositories::{self, system_log::SystemLogFilter},
+    utils::system_log::{SystemLogReceiver, WRITER_SPAN},
+};
+
+const MAX_PAGE_SIZE: i64 = 500;
+/// Tek seferde yazılan en fazla kayıt sayısı
+const BATCH_SIZE: usize = 200;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Log katmanının kuyruğunu boşaltan arka plan işi: kayıtları toplu yazar ve saklama
+/// süresi dolanları siler. Kapanışta kuyrukta kalanlar yazıldıktan sonra çıkar.
+pub fn spawn_writer(app_state: web::Data<AppState>, mut receiver: SystemLogReceiver) {
+    let mut shutdown = app_state.shutdown.subscribe();
+    actix_web::rt::spawn(async move {
+        let mut flush_interval = actix_web::rt::time::interval(FLUSH_INTERVAL);
+        let mut prune_interval = actix_web::rt::time::interval(PRUNE_INTERVAL);
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        loop {
+            tokio::select! {
+                received = receiver.recv() => match received {
+                    Some(record) => {
+                        batch.push(record);
+                        if batch.len() >= BATCH_SIZE {
+                            flush(&app_state, &mut batch).await;
+                        }
+                    }
+                    None => break,
+                },
+                _ = flush_interval.tick() => flush(&app_state, &mut batch).await,
+                _ = prune_interval.tick() => prune(&app_state).await,
+                _ = shutdown.recv() => break,
+            }
+        }
+
+        while let Ok(record) = receiver.try_recv() {
+            batch.push(record);
+        }
+        flush(&app_state, &mut batch).await;
+    });
+}
+
+async fn flush(app_state: &web::Data<AppState>, batch: &mut Vec<NewSystemLog>) {
+    if batch.is_empty() {
+        return;
+    }
+    async {
+        for chunk in batch.chunks(BATCH_SIZE) {
+            if let Err(e) = repositories::system_log::insert_batch(&app_state.db, chunk).await {
+                error!("Failed to persist {} system log records: {}", chunk.len(), e);
+            }
+        }
+    }
+    .instrument(tracing::info_span!(WRITER_SPAN))
+    .await;
+    batch.clear();
+}
+
+async fn prune(app_state: &web::Data<AppState>) {
+    let cutoff = Utc::now() - chrono::Duration::days(app_state.config.system_log_retention_days);
+    async {
+        match repositories::system_log::delete_before(&app_state.db, cutoff).await {
+            Ok(0) => {}
+            Ok(n) => info!("Pruned {} system log records older than {}", n, cutoff),
+            Err(e) => error!("System log pruning failed: {}", e),
+        }
+    }
+    .instrument(tracing::info_span!(WRITER_SPAN))
+    .await;
+}
+
+pub async fn list_logs(
+    app_state: &web::Data<AppState>,
+    admin: &AuthenticatedUser,
+    mut filter: SystemLogFilter,
+) -> Result<Vec<SystemLog>, AppError> {
+    admin.require_admin()?;
+    admin.deny_if_impersonated("viewing the system log")?;
+
+    if let Some(level) = &filter.level {
+        if !matches!(level.as_str(), "warn" | "error") {
+            return Err(AppError::ValidationError("level must be warn or error".to_string()));
+        }
+    }
+    filter.limit = filter.limit.clamp(1, MAX_PAGE_SIZE);
+    filter.offset = filter.offset.max(0);
+    repositories::system_log::query(&app_state.db, &filter).await
+}
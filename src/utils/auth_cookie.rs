@@ -0,0 +1,201 @@
+use actix_web::{
+    cookie::{time::Duration, Cookie, SameSite},
+    http::Method,
+    HttpRequest, HttpResponseBuilder,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{
+    config::AppConfig,
+    error::{AppError, JwtTokenError},
+    models::dto::auth::{AuthResponse, TokenType},
+    utils::{jwt, otp::constant_time_eq},
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// İstemcinin token'ı gövde yerine çerezde istediğini belirten başlık (`X-Auth-Mode: cookie`)
+pub const AUTH_MODE_HEADER: &str = "X-Auth-Mode";
+/// Çerezle kimlik doğrulanan durum değiştiren isteklerde CSRF token'ının gönderildiği başlık
+pub const CSRF_HEADER: &str = "X-CSRF-Token";
+
+/// Access token'ın istekte nereden okunduğu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenSource {
+    Header,
+    Cookie,
+}
+
+pub fn wants_cookie(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(AUTH_MODE_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .is_some_and(|mode| mode.eq_ignore_ascii_case("cookie"))
+}
+
+/// Token'ın jti'sine bağlı CSRF token'ı; çerez enjekte edilse bile başka bir oturum için geçerli olmaz
+pub fn csrf_token(jti: &str, secret: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(b"csrf\n");
+    mac.update(jti.as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Önce `Authorization: Bearer` başlığına, yoksa oturum çerezine bakar
+pub fn token_from_request(req: &HttpRequest, config: &AppConfig) -> Result<(String, TokenSource), AppError> {
+    if let Some(header) = req.headers().get("Authorization") {
+        let value = header.to_str()?;
+        let token = value
+            .split_once(' ')
+            .filter(|(scheme, token)| scheme.eq_ignore_ascii_case("bearer") && !token.trim().is_empty())
+            .map(|(_, token)| token.trim().to_string())
+            .ok_or_else(|| AppError::bad_request("Missing or invalid authorization header"))?;
+        return Ok((token, TokenSource::Header));
+    }
+    if let Some(cookie) = req.cookie(&config.auth_cookie_name) {
+        return Ok((cookie.value().to_string(), TokenSource::Cookie));
+    }
+    Err(AppError::token_missing())
+}
+
+/// Çerezle gelen ve durum değiştiren isteklerde başlıktaki CSRF token'ını doğrular (double-submit)
+pub fn verify_csrf(method: &Method, csrf_header: Option<&str>, jti: &str, secret: &str) -> Result<(), AppError> {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return Ok(());
+    }
+    let provided = csrf_header.ok_or_else(|| AppError::Forbidden("Missing CSRF token".to_string()))?;
+    if !constant_time_eq(provided.as_bytes(), csrf_token(jti, secret).as_bytes()) {
+        return Err(AppError::Forbidden("Invalid CSRF token".to_string()));
+    }
+    Ok(())
+}
+
+fn same_site(config: &AppConfig) -> SameSite {
+    match config.cookie_same_site.to_ascii_lowercase().as_str() {
+        "lax" => SameSite::Lax,
+        "none" => SameSite::None,
+        _ => SameSite::Strict,
+    }
+}
+
+fn build_cookie(config: &AppConfig, name: &str, value: String, http_only: bool, max_age: Duration) -> Cookie<'static> {
+    let mut builder = Cookie::build(name.to_string(), value)
+        .path("/")
+        .http_only(http_only)
+        .secure(config.cookie_secure)
+        .same_site(same_site(config))
+        .max_age(max_age);
+    if let Some(domain) = config.cookie_domain.clone().filter(|d| !d.is_empty()) {
+        builder = builder.domain(domain);
+    }
+    builder.finish()
+}
+
+/// İstemci çerez modunu istediyse token'ı HttpOnly çereze, CSRF token'ını da
+/// JavaScript'in okuyabileceği ayrı bir çereze yazar; yanıt gövdesinden token çıkarılır
+pub fn apply_session_cookies(
+    req: &HttpRequest,
+    config: &AppConfig,
+    response: &mut HttpResponseBuilder,
+    auth: &mut AuthResponse,
+) -> Result<(), AppError> {
+    if !wants_cookie(req) {
+        return Ok(());
+    }
+    let claims = jwt::decode_jwt(&auth.token, TokenType::Access, config)?;
+    let max_age = Duration::seconds(claims.remaining_secs() as i64);
+    let csrf = csrf_token(&claims.jti, &config.jwt_secret);
+
+    response.cookie(build_cookie(config, &config.auth_cookie_name, std::mem::take(&mut auth.token), true, max_age));
+    response.cookie(build_cookie(config, &config.csrf_cookie_name, csrf.clone(), false, max_age));
+    auth.csrf_token = Some(csrf);
+    Ok(())
+}
+
+/// Çıkışta oturum ve CSRF çerezlerini siler
+pub fn clear_session_cookies(config: &AppConfig, response: &mut HttpResponseBuilder) {
+    response.cookie(build_cookie(config, &config.auth_cookie_name, String::new(), true, Duration::ZERO));
+    response.cookie(build_cookie(config, &config.csrf_cookie_name, String::new(), false, Duration::ZERO));
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{cookie::Cookie, test::TestRequest, ResponseError};
+    use serde_json::json;
+
+    use super::*;
+
+    const SECRET: &str = "0123456789abcdef0123456789abcdef";
+
+    fn config() -> AppConfig {
+        serde_json::from_value(json!({
+            "database_url": "postgres://localhost/ann_ai",
+            "redis_url": "redis://localhost",
+            "jwt_secret": SECRET,
+        }))
+        .expect("test config should deserialize")
+    }
+
+    #[test]
+    fn safe_methods_skip_csrf() {
+        for method in [Method::GET, Method::HEAD, Method::OPTIONS] {
+            assert!(verify_csrf(&method, None, "jti", SECRET).is_ok());
+        }
+    }
+
+    #[test]
+    fn state_changing_methods_require_the_matching_csrf_token() {
+        let token = csrf_token("jti-1", SECRET);
+        assert!(verify_csrf(&Method::POST, Some(&token), "jti-1", SECRET).is_ok());
+        assert!(matches!(verify_csrf(&Method::POST, None, "jti-1", SECRET), Err(AppError::Forbidden(_))));
+        // Başka oturumun ya da başka anahtarın token'ı kabul edilmez
+        assert!(matches!(verify_csrf(&Method::DELETE, Some(&token), "jti-2", SECRET), Err(AppError::Forbidden(_))));
+        let other_secret = csrf_token("jti-1", "another-secret");
+        assert!(matches!(verify_csrf(&Method::PUT, Some(&other_secret), "jti-1", SECRET), Err(AppError::Forbidden(_))));
+    }
+
+    #[test]
+    fn bearer_header_takes_precedence_over_cookie() {
+        let config = config();
+        let req = TestRequest::default()
+            .insert_header(("Authorization", "bearer  header-token "))
+            .cookie(Cookie::new(config.auth_cookie_name.clone(), "cookie-token"))
+            .to_http_request();
+        let (token, source) = token_from_request(&req, &config).unwrap();
+        assert_eq!((token.as_str(), source), ("header-token", TokenSource::Header));
+
+        let req = TestRequest::default()
+            .cookie(Cookie::new(config.auth_cookie_name.clone(), "cookie-token"))
+            .to_http_request();
+        let (token, source) = token_from_request(&req, &config).unwrap();
+        assert_eq!((token.as_str(), source), ("cookie-token", TokenSource::Cookie));
+    }
+
+    #[test]
+    fn malformed_authorization_header_is_rejected_without_falling_back_to_cookie() {
+        let config = config();
+        for value in ["Basic dXNlcjpwYXNz", "Bearer", "Bearer   ", "header-token"] {
+            let req = TestRequest::default()
+                .insert_header(("Authorization", value))
+                .cookie(Cookie::new(config.auth_cookie_name.clone(), "cookie-token"))
+                .to_http_request();
+            assert!(
+                matches!(token_from_request(&req, &config), Err(AppError::BadRequest(_))),
+                "{:?}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn missing_token_is_unauthorized() {
+        let req = TestRequest::default().to_http_request();
+        let err = token_from_request(&req, &config()).unwrap_err();
+        assert_eq!(err.status_code(), actix_web::http::StatusCode::UNAUTHORIZED);
+    }
+}
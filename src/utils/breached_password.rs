@@ -0,0 +1,80 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use sha1::{Digest, Sha1};
+
+/// Parolanın HIBP k-anonimlik biçimindeki yerel listede olup olmadığını kontrol eder.
+///
+/// `dir` içinde SHA-1 hash'in ilk 5 hanesiyle adlandırılmış `<PREFIX>.txt`
+/// dosyaları beklenir (haveibeenpwned-downloader çıktısı / range API yanıtı);
+/// her satır `SUFFIX:COUNT` biçimindedir. Önek dosyası yoksa parola listede kabul edilmez.
+pub fn is_breached(dir: &Path, password: &str) -> io::Result<bool> {
+    let hash: String = Sha1::digest(password.as_bytes())
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect();
+    let (prefix, suffix) = hash.split_at(5);
+
+    let file = match File::open(dir.join(format!("{}.txt", prefix))) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let Some((candidate, count)) = line.trim().split_once(':') else {
+            continue;
+        };
+        // Dolgu (padding) satırları sayacı 0 olarak gelir
+        if candidate.eq_ignore_ascii_case(suffix) && count.trim() != "0" {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+
+    // SHA-1("password") = 5BAA6 1E4C9B93F3F0682250B6CF8331B7EE68FD8
+    const PREFIX: &str = "5BAA6";
+    const SUFFIX: &str = "1E4C9B93F3F0682250B6CF8331B7EE68FD8";
+
+    fn range_dir(name: &str, contents: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("breached-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(format!("{}.txt", PREFIX)), contents).unwrap();
+        dir
+    }
+
+    #[test]
+    fn finds_the_suffix_in_the_prefix_file_case_insensitively() {
+        let contents = format!("0018A45C4D1DEF81644B54AB7F969B88D65:3\r\n{}:9545824\r\n", SUFFIX.to_lowercase());
+        let dir = range_dir("hit", &contents);
+        assert!(is_breached(&dir, "password").unwrap());
+        assert!(!is_breached(&dir, "Password").unwrap());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn ignores_padding_and_malformed_lines() {
+        let dir = range_dir("padding", &format!("not-a-range-line\n{}:0\n", SUFFIX));
+        assert!(!is_breached(&dir, "password").unwrap());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_prefix_file_means_not_breached() {
+        let dir = range_dir("missing", "");
+        fs::remove_file(dir.join(format!("{}.txt", PREFIX))).unwrap();
+        assert!(!is_breached(&dir, "password").unwrap());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
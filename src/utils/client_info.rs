@@ -0,0 +1,154 @@
+use std::net::IpAddr;
+
+use actix_web::{http::header, web, HttpRequest};
+
+use crate::app_state::AppState;
+
+/// İsteği yapan istemciye ait, oturum kaydında saklanan bilgiler
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl ClientInfo {
+    pub fn from_request(req: &HttpRequest) -> Self {
+        let trusted_proxies = req
+            .app_data::<web::Data<AppState>>()
+            .map(|state| state.config.trusted_proxies())
+            .unwrap_or_default();
+        let ip_address = client_ip(req, &trusted_proxies).map(|ip| ip.to_string());
+        let user_agent = req
+            .headers()
+            .get(header::USER_AGENT)
+            .and_then(|h| h.to_str().ok())
+            .map(|ua| ua.chars().take(512).collect());
+
+        Self { ip_address, user_agent }
+    }
+
+    /// User-Agent'tan kaba bir cihaz türü çıkarır (mobile/tablet/desktop/bot)
+    pub fn device(&self) -> Option<&'static str> {
+        let ua = self.user_agent.as_deref()?.to_lowercase();
+        let device = if ua.contains("bot") || ua.contains("spider") || ua.contains("curl") {
+            "bot"
+        } else if ua.contains("ipad") || ua.contains("tablet") {
+            "tablet"
+        } else if ua.contains("mobile") || ua.contains("iphone") || ua.contains("android") {
+            "mobile"
+        } else {
+            "desktop"
+        };
+        Some(device)
+    }
+
+    /// User-Agent'tan işletim sistemi adını çıkarır
+    pub fn os(&self) -> Option<&'static str> {
+        let ua = self.user_agent.as_deref()?.to_lowercase();
+        let os = if ua.contains("windows") {
+            "Windows"
+        } else if ua.contains("iphone") || ua.contains("ipad") || ua.contains("ios") {
+            "iOS"
+        } else if ua.contains("android") {
+            "Android"
+        } else if ua.contains("mac os") || ua.contains("macintosh") {
+            "macOS"
+        } else if ua.contains("cros") {
+            "ChromeOS"
+        } else if ua.contains("linux") {
+            "Linux"
+        } else {
+            "Unknown"
+        };
+        Some(os)
+    }
+}
+
+/// İstemci IP'si. Başlıklar istemci tarafından yazılabildiği için `X-Forwarded-For` yalnızca
+/// bağlantı güvenilen bir proxy'den geldiğinde okunur; zincir sağdan sola yürünür ve güvenilen
+/// proxy olmayan ilk adres alınır.
+fn client_ip(req: &HttpRequest, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    let peer = req.peer_addr()?.ip().to_canonical();
+    if !trusted_proxies.contains(&peer) {
+        return Some(peer);
+    }
+
+    let forwarded: Vec<IpAddr> = req
+        .headers()
+        .get_all("x-forwarded-for")
+        .filter_map(|h| h.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|addr| strip_port(addr.trim()).parse::<IpAddr>().ok())
+        .map(|ip| ip.to_canonical())
+        .collect();
+    Some(
+        forwarded
+            .into_iter()
+            .rev()
+            .find(|ip| !trusted_proxies.contains(ip))
+            .unwrap_or(peer),
+    )
+}
+
+fn strip_port(addr: &str) -> &str {
+    // IPv6 adresleri "[::1]:8080" biçiminde gelir
+    if let Some(rest) = addr.strip_prefix('[') {
+        return rest.split(']').next().unwrap_or(rest);
+    }
+    match addr.rsplit_once(':') {
+        Some((host, _)) if !host.contains(':') => host,
+        _ => addr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    fn request(peer: &str, forwarded_for: &[&str]) -> HttpRequest {
+        let mut req = TestRequest::default().peer_addr(peer.parse::<SocketAddr>().unwrap());
+        for value in forwarded_for {
+            req = req.append_header(("X-Forwarded-For", *value));
+        }
+        req.to_http_request()
+    }
+
+    fn ip(addr: &str) -> IpAddr {
+        addr.parse().unwrap()
+    }
+
+    #[test]
+    fn forwarded_for_is_ignored_from_untrusted_peers() {
+        let req = request("203.0.113.7:5000", &["198.51.100.1"]);
+        assert_eq!(client_ip(&req, &[ip("10.0.0.1")]), Some(ip("203.0.113.7")));
+        assert_eq!(client_ip(&req, &[]), Some(ip("203.0.113.7")));
+    }
+
+    #[test]
+    fn rightmost_untrusted_hop_is_the_client() {
+        let trusted = [ip("10.0.0.1"), ip("10.0.0.2")];
+        // İstemcinin kendi yazdığı en soldaki adres atlanır
+        let req = request("10.0.0.1:5000", &["1.2.3.4, 198.51.100.9", "10.0.0.2"]);
+        assert_eq!(client_ip(&req, &trusted), Some(ip("198.51.100.9")));
+    }
+
+    #[test]
+    fn falls_back_to_the_peer_when_every_hop_is_trusted_or_invalid() {
+        let trusted = [ip("10.0.0.1"), ip("10.0.0.2")];
+        let req = request("10.0.0.1:5000", &["not-an-ip, 10.0.0.2"]);
+        assert_eq!(client_ip(&req, &trusted), Some(ip("10.0.0.1")));
+    }
+
+    #[test]
+    fn ports_and_ipv4_mapped_addresses_are_normalized() {
+        let trusted = [ip("10.0.0.1")];
+        let req = request("[::ffff:10.0.0.1]:5000", &["[2001:db8::1]:443, 198.51.100.9:8080"]);
+        assert_eq!(client_ip(&req, &trusted), Some(ip("198.51.100.9")));
+        let req = request("10.0.0.1:5000", &["[2001:db8::1]:443"]);
+        assert_eq!(client_ip(&req, &trusted), Some(ip("2001:db8::1")));
+    }
+}
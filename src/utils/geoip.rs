@@ -0,0 +1,100 @@
+use std::net::IpAddr;
+
+use tracing::{info, warn};
+use maxminddb::{geoip2, Reader};
+
+use crate::{config::AppConfig, error::AppError};
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Bir IP adresinin yaklaşık konumu
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoLocation {
+    /// ISO 3166-1 alpha-2 ülke kodu
+    pub country: Option<String>,
+    pub city: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// İki koordinat arasındaki büyük daire mesafesi (km)
+pub fn haversine_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2) + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Çevrimdışı MaxMind City veritabanı üzerinden IP → konum çözümlemesi.
+/// Veritabanı yapılandırılmamışsa tüm sorgular `None` döner.
+pub struct GeoIpResolver {
+    reader: Option<Reader<Vec<u8>>>,
+}
+
+impl GeoIpResolver {
+    pub fn disabled() -> Self {
+        Self { reader: None }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.reader.is_some()
+    }
+
+    pub fn from_config(config: &AppConfig) -> Result<Self, AppError> {
+        let Some(path) = config.geoip_db_path.as_deref().filter(|p| !p.is_empty()) else {
+            warn!("GEOIP_DB_PATH is not set, location based login checks are disabled");
+            return Ok(Self::disabled());
+        };
+        let reader = Reader::open_readfile(path)
+            .map_err(|e| AppError::internal_error(&format!("GeoIP database could not be opened: {}", e)))?;
+        info!("GeoIP database loaded: {} ({})", path, reader.metadata.database_type);
+        Ok(Self { reader: Some(reader) })
+    }
+
+    pub fn lookup(&self, ip: &str) -> Option<GeoLocation> {
+        let reader = self.reader.as_ref()?;
+        let ip: IpAddr = ip.parse().ok()?;
+        let city: geoip2::City = reader.lookup(ip).ok()?;
+
+        let location = city.location?;
+        Some(GeoLocation {
+            country: city.country.and_then(|c| c.iso_code).map(str::to_string),
+            city: city
+                .city
+                .and_then(|c| c.names)
+                .and_then(|names| names.get("en").map(|n| n.to_string())),
+            latitude: location.latitude?,
+            longitude: location.longitude?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn haversine_is_zero_for_the_same_point() {
+        assert_eq!(haversine_km(41.0082, 28.9784, 41.0082, 28.9784), 0.0);
+    }
+
+    #[test]
+    fn haversine_matches_known_distances() {
+        // İstanbul - Ankara ≈ 350 km, Londra - New York ≈ 5570 km
+        let istanbul_ankara = haversine_km(41.0082, 28.9784, 39.9334, 32.8597);
+        assert!((istanbul_ankara - 350.0).abs() < 5.0, "{}", istanbul_ankara);
+        let london_new_york = haversine_km(51.5074, -0.1278, 40.7128, -74.0060);
+        assert!((london_new_york - 5570.0).abs() < 10.0, "{}", london_new_york);
+    }
+
+    #[test]
+    fn haversine_is_symmetric_and_handles_the_antimeridian() {
+        let a = haversine_km(10.0, 179.5, 10.0, -179.5);
+        let b = haversine_km(10.0, -179.5, 10.0, 179.5);
+        assert!((a - b).abs() < 1e-9);
+        assert!(a < 120.0, "{}", a);
+        // Antipodal noktalar arası yarım çevre
+        let antipodal = haversine_km(0.0, 0.0, 0.0, 180.0);
+        assert!((antipodal - std::f64::consts::PI * EARTH_RADIUS_KM).abs() < 1e-6);
+    }
+}
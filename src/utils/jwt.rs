@@ -1,35 +1,45 @@
-use std::default;
+use crate::{
+    config::AppConfig,
+    error::{AppError, JwtTokenError},
+    models::dto::auth::{Claims, TokenType},
+};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 
-use crate::{error::AppError, models::dto::auth::Claims};
-use actix_web::http::header::TryIntoHeaderValue;
-use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
-use uuid::Uuid;
-
-pub fn create_jwt(user_id: i32, user_role: String, secret: &str) -> Result<String, AppError> {
-    let expiration = Utc::now() + Duration::hours(24);
-    let iat = Utc::now().timestamp() as usize;
-
-    let claim = Claims {
-        user_id,
-        role: user_role,
-        exp: expiration.timestamp() as usize,
-        iat,
-    };
+/// İptal edilen token'ların `jti` değerleri bu önekle Redis'te tutulur
+pub fn revoked_jti_key(jti: &str) -> String {
+    format!("revoked_jti:{}", jti)
+}
 
-    let header = Header::default();
+pub fn encode_claims(claims: &Claims, secret: &str) -> Result<String, AppError> {
+    let header = Header::new(Algorithm::HS256);
     let encoding_key = EncodingKey::from_secret(secret.as_bytes());
 
-    encode(&header, &claim, &encoding_key).map_err(|e| AppError::JwtError((e)))
+    encode(&header, claims, &encoding_key).map_err(AppError::JwtError)
 }
 
-pub fn decode_jwt(token: &str, secret: &str) -> Result<Claims, AppError> {
-    let decoding_key = DecodingKey::from_secret(secret.as_bytes());
-    let mut validation = Validation::default();
+pub fn decode_jwt(
+    token: &str,
+    expected_type: TokenType,
+    config: &AppConfig,
+) -> Result<Claims, AppError> {
+    let decoding_key = DecodingKey::from_secret(config.jwt_secret.as_bytes());
+    let mut validation = Validation::new(Algorithm::HS256);
     validation.validate_exp = true;
-    validation.validate_nbf = false;
+    validation.validate_nbf = true;
+    validation.set_issuer(&[config.jwt_issuer.as_str()]);
+    validation.set_audience(&[config.jwt_audience.as_str()]);
+    validation.set_required_spec_claims(&["exp", "nbf", "iss", "aud", "sub"]);
 
-    decode::<Claims>(token, &decoding_key, &validation)
+    let claims = decode::<Claims>(token, &decoding_key, &validation)
         .map(|data| data.claims)
-        .map_err(|e| AppError::JwtError((e)))
+        .map_err(AppError::from_jwt_error)?;
+
+    if claims.token_type != expected_type {
+        return Err(AppError::Unauthorized("Invalid token type".to_string()));
+    }
+    if claims.jti.is_empty() {
+        return Err(AppError::token_invalid());
+    }
+
+    Ok(claims)
 }
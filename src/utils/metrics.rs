@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use deadpool_redis::Pool as RedisPool;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use sqlx::PgPool;
+
+use crate::error::AppError;
+
+/// HTTP ve yapay zekâ sağlayıcı süreleri için kova sınırları (saniye)
+const HTTP_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+const AI_BUCKETS: &[f64] = &[0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0];
+
+/// Kimlik doğrulama sonuçları; `auth_events_total{event=...}` etiketi
+#[derive(Debug, Clone, Copy)]
+pub enum AuthEvent {
+    LoginSuccess,
+    LoginFailure,
+    MagicLinkLogin,
+    /// Kara listedeki (iptal edilmiş) bir token ile yapılan istek
+    BlacklistedToken,
+}
+
+impl AuthEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthEvent::LoginSuccess => "login_success",
+            AuthEvent::LoginFailure => "login_failure",
+            AuthEvent::MagicLinkLogin => "magic_link_login",
+            AuthEvent::BlacklistedToken => "blacklisted_token",
+        }
+    }
+}
+
+/// Uygulamanın Prometheus metrikleri. Örnek başına tek bir kayıt defteri tutulur;
+/// havuz göstergeleri her okumada güncellenir.
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    db_pool_connections: IntGaugeVec,
+    redis_pool_connections: IntGaugeVec,
+    auth_events: IntCounterVec,
+    audit_write_failures: IntCounterVec,
+    ai_request_duration: HistogramVec,
+    ai_tokens: IntCounterVec,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self, AppError> {
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests by route and status"),
+            &["method", "route", "status"],
+        )?;
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP request latency by route and status")
+                .buckets(HTTP_BUCKETS.to_vec()),
+            &["method", "route", "status"],
+        )?;
+        let db_pool_connections = IntGaugeVec::new(
+            Opts::new("db_pool_connections", "PostgreSQL pool connections by state"),
+            &["state"],
+        )?;
+        let redis_pool_connections = IntGaugeVec::new(
+            Opts::new("redis_pool_connections", "Redis pool connections by state"),
+            &["state"],
+        )?;
+        let auth_events = IntCounterVec::new(
+            Opts::new("auth_events_total", "Authentication outcomes"),
+            &["event"],
+        )?;
+        let audit_write_failures = IntCounterVec::new(
+            Opts::new("audit_write_failures_total", "Audit events that could not be written and were dropped"),
+            &["action"],
+        )?;
+        let ai_request_duration = HistogramVec::new(
+            HistogramOpts::new("ai_provider_request_duration_seconds", "AI provider request latency")
+                .buckets(AI_BUCKETS.to_vec()),
+            &["provider", "model", "outcome"],
+        )?;
+        let ai_tokens = IntCounterVec::new(
+            Opts::new("ai_provider_tokens_total", "Tokens consumed from AI providers"),
+            &["provider", "model", "kind"],
+        )?;
+
+        let registry = Registry::new_custom(Some("ann_ai".to_string()), None)?;
+        registry.register(Box::new(http_requests.clone()))?;
+        registry.register(Box::new(http_request_duration.clone()))?;
+        registry.register(Box::new(db_pool_connections.clone()))?;
+        registry.register(Box::new(redis_pool_connections.clone()))?;
+        registry.register(Box::new(auth_events.clone()))?;
+        registry.register(Box::new(audit_write_failures.clone()))?;
+        registry.register(Box::new(ai_request_duration.clone()))?;
+        registry.register(Box::new(ai_tokens.clone()))?;
+
+        Ok(Self {
+            registry,
+            http_requests,
+            http_request_duration,
+            db_pool_connections,
+            redis_pool_connections,
+            auth_events,
+            audit_write_failures,
+            ai_request_duration,
+            ai_tokens,
+        })
+    }
+
+    /// `route` eşleşen kalıptır (ör. `/api/organizations/{id}`); kardinaliteyi sınırlamak için ham yol kullanılmaz
+    pub fn observe_http(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let status = status.to_string();
+        let labels = [method, route, status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_request_duration
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+    }
+
+    pub fn auth_event(&self, event: AuthEvent) {
+        self.auth_events.with_label_values(&[event.as_str()]).inc();
+    }
+
+    /// Yazılamayıp düşürülen denetim kayıtları; sıfırdan büyükse alarm üretilmelidir
+    pub fn audit_write_failed(&self, action: &str) {
+        self.audit_write_failures.with_label_values(&[action]).inc();
+    }
+
+    /// Sağlayıcı çağrısı başına bir kez çağrılır; başarısız çağrılarda token sayıları 0 verilebilir
+    pub fn observe_ai_request(
+        &self,
+        provider: &str,
+        model: &str,
+        success: bool,
+        elapsed: Duration,
+        prompt_tokens: u64,
+        completion_tokens: u64,
+    ) {
+        let outcome = if success { "success" } else { "error" };
+        self.ai_request_duration
+            .with_label_values(&[provider, model, outcome])
+            .observe(elapsed.as_secs_f64());
+        self.ai_tokens.with_label_values(&[provider, model, "prompt"]).inc_by(prompt_tokens);
+        self.ai_tokens
+            .with_label_values(&[provider, model, "completion"])
+            .inc_by(completion_tokens);
+    }
+
+    /// Havuz göstergelerini günceller ve tüm metrikleri Prometheus metin formatında döner
+    pub fn render(&self, db: &PgPool, redis_pool: &RedisPool) -> Result<String, AppError> {
+        let db_size = i64::from(db.size());
+        let db_idle = db.num_idle() as i64;
+        self.db_pool_connections.with_label_values(&["idle"]).set(db_idle);
+        self.db_pool_connections.with_label_values(&["in_use"]).set(db_size - db_idle);
+        self.db_pool_connections
+            .with_label_values(&["max"])
+            .set(i64::from(db.options().get_max_connections()));
+
+        let redis = redis_pool.status();
+        self.redis_pool_connections.with_label_values(&["idle"]).set(redis.available as i64);
+        self.redis_pool_connections
+            .with_label_values(&["in_use"])
+            .set(redis.size.saturating_sub(redis.available) as i64);
+        self.redis_pool_connections.with_label_values(&["max"]).set(redis.max_size as i64);
+        // Havuzdan bağlantı bekleyen istek sayısı
+        self.redis_pool_connections.with_label_values(&["waiting"]).set(redis.waiting as i64);
+
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        String::from_utf8(buffer).map_err(|e| AppError::internal_error(&e.to_string()))
+    }
+}
@@ -1,6 +1,18 @@
 pub mod jwt;
 pub mod password;
 pub mod validation;
-pub mod uudi_convert_32byte;
-pub mod get_token_from_header;
-pub mod sha256;
\ No newline at end of file
+pub mod client_info;
+pub mod otp;
+pub mod phone;
+pub mod sha256;
+pub mod rate_limit;
+pub mod breached_password;
+pub mod password_policy;
+pub mod signed_url;
+pub mod geoip;
+pub mod auth_cookie;
+pub mod shutdown;
+pub mod metrics;
+pub mod telemetry;
+pub mod system_log;
+pub mod traced_redis;
@@ -0,0 +1,51 @@
+use rand::{rngs::OsRng, Rng};
+
+use crate::utils::sha256::sha256_hash;
+
+/// `digits` haneli, baştaki sıfırları korunan rastgele sayısal kod üretir
+pub fn generate_numeric_code(digits: u32) -> String {
+    let upper = 10u32.pow(digits);
+    let code = OsRng.gen_range(0..upper);
+    format!("{:0width$}", code, width = digits as usize)
+}
+
+/// Kodlar Redis'te düz metin değil hash olarak saklanır
+pub fn hash_code(code: &str) -> String {
+    sha256_hash(code)
+}
+
+/// Girilen kodu saklanan hash ile sabit zamanda karşılaştırır
+pub fn code_matches(code: &str, stored_hash: &str) -> bool {
+    constant_time_eq(hash_code(code).as_bytes(), stored_hash.as_bytes())
+}
+
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut result = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        result |= x ^ y;
+    }
+    result == 0
+}
+
+/// URL'lerde kullanılabilecek 256 bitlik rastgele token (hex)
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Karıştırılabilen karakterler (0/O, 1/I) çıkarılmış alfabeyle `XXXX-XXXX-XXXX-XXXX` biçiminde davet kodu
+pub fn generate_invite_code() -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+    (0..4)
+        .map(|_| {
+            (0..4)
+                .map(|_| ALPHABET[OsRng.gen_range(0..ALPHABET.len())] as char)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
@@ -1,26 +1,161 @@
-use actix_web::{body::MessageBody, App};
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
-    Argon2,
+    Algorithm, Argon2, Params, Version,
 };
 
-use crate::{error::AppError, result::AppResult};
+use crate::{config::AppConfig, error::AppError, result::AppResult};
 
-pub fn hash_password(password: &str) -> AppResult<String> {
+/// Dağıtıma göre ayarlanabilen Argon2id maliyet parametreleri ve isteğe bağlı sunucu tarafı pepper
+#[derive(Debug, Clone)]
+pub struct PasswordHashing {
+    params: Params,
+    pepper: Option<Vec<u8>>,
+}
+
+/// Doğrulama sonucu; `needs_rehash` hash'in güncel parametrelerle yeniden üretilmesi gerektiğini belirtir
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasswordVerification {
+    pub valid: bool,
+    pub needs_rehash: bool,
+}
+
+impl PasswordHashing {
+    pub fn from_config(config: &AppConfig) -> Result<Self, AppError> {
+        let params = Params::new(
+            config.argon2_memory_kib,
+            config.argon2_iterations,
+            config.argon2_parallelism,
+            None,
+        )
+        .map_err(|e| AppError::Argon2Error(format!("Invalid Argon2 parameters: {}", e)))?;
+
+        Ok(Self {
+            params,
+            pepper: config
+                .password_pepper
+                .as_ref()
+                .filter(|p| !p.is_empty())
+                .map(|p| p.as_bytes().to_vec()),
+        })
+    }
+
+    fn argon2(&self, with_pepper: bool) -> AppResult<Argon2<'_>> {
+        match (&self.pepper, with_pepper) {
+            (Some(pepper), true) => Argon2::new_with_secret(pepper, Algorithm::Argon2id, Version::V0x13, self.params.clone())
+                .map_err(|e| AppError::Argon2Error(e.to_string())),
+            _ => Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())),
+        }
+    }
+
+    /// Hash güncel algoritma ve parametrelerle mi üretilmiş?
+    fn is_current(&self, parsed: &PasswordHash<'_>) -> bool {
+        if parsed.algorithm != Algorithm::Argon2id.ident() || parsed.version != Some(Version::V0x13.into()) {
+            return false;
+        }
+        match Params::try_from(parsed) {
+            Ok(params) => {
+                params.m_cost() == self.params.m_cost()
+                    && params.t_cost() == self.params.t_cost()
+                    && params.p_cost() == self.params.p_cost()
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+pub fn hash_password(password: &str, hashing: &PasswordHashing) -> AppResult<String> {
     let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    argon2
+    hashing
+        .argon2(true)?
         .hash_password(password.as_bytes(), &salt)
-        .map_err(|e| AppError::Argon2Error((e.to_string())))
+        .map_err(|e| AppError::Argon2Error(e.to_string()))
         .map(|hash| hash.to_string())
 }
 
-pub fn verify_password(password: &str, hashed_password: &str) -> Result<bool, AppError> {
-    let parsed_hash = argon2::password_hash::PasswordHash::new(hashed_password)
-        .map_err(|e| AppError::Argon2Error((e.to_string())))?;
+pub fn verify_password(password: &str, hashed_password: &str, hashing: &PasswordHashing) -> Result<bool, AppError> {
+    verify_password_with_rehash(password, hashed_password, hashing).map(|v| v.valid)
+}
+
+/// Parolayı doğrular ve hash'in yenilenmesi gerekip gerekmediğini bildirir.
+///
+/// Desteklenen eski biçimler: farklı parametrelerle üretilmiş Argon2 hash'leri,
+/// pepper eklenmeden önce üretilmiş hash'ler ve dışarıdan aktarılan bcrypt (`$2a$`, `$2b$`, `$2y$`) hash'leri.
+pub fn verify_password_with_rehash(
+    password: &str,
+    hashed_password: &str,
+    hashing: &PasswordHashing,
+) -> Result<PasswordVerification, AppError> {
+    if hashed_password.starts_with("$2") {
+        let valid = bcrypt::verify(password, hashed_password)
+            .map_err(|e| AppError::Argon2Error(format!("bcrypt: {}", e)))?;
+        return Ok(PasswordVerification { valid, needs_rehash: valid });
+    }
+
+    let parsed_hash = PasswordHash::new(hashed_password)
+        .map_err(|e| AppError::Argon2Error(e.to_string()))?;
+    let current = hashing.is_current(&parsed_hash);
+
+    if hashing.argon2(true)?.verify_password(password.as_bytes(), &parsed_hash).is_ok() {
+        return Ok(PasswordVerification { valid: true, needs_rehash: !current });
+    }
+
+    // Pepper devreye alınmadan önce üretilmiş hash'ler pepper'sız doğrulanır ve yenilenir
+    if hashing.pepper.is_some() && hashing.argon2(false)?.verify_password(password.as_bytes(), &parsed_hash).is_ok() {
+        return Ok(PasswordVerification { valid: true, needs_rehash: true });
+    }
+
+    Ok(PasswordVerification { valid: false, needs_rehash: false })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashing(memory_kib: u32, pepper: Option<&str>) -> PasswordHashing {
+        PasswordHashing {
+            params: Params::new(memory_kib, 1, 1, None).unwrap(),
+            pepper: pepper.map(|p| p.as_bytes().to_vec()),
+        }
+    }
+
+    #[test]
+    fn peppered_hash_round_trips_only_with_the_same_pepper() {
+        let peppered = hashing(64, Some("pepper-one"));
+        let hash = hash_password("correct horse", &peppered).unwrap();
+
+        let verification = verify_password_with_rehash("correct horse", &hash, &peppered).unwrap();
+        assert_eq!(verification, PasswordVerification { valid: true, needs_rehash: false });
+        assert!(!verify_password("wrong horse", &hash, &peppered).unwrap());
+        assert!(!verify_password("correct horse", &hash, &hashing(64, Some("pepper-two"))).unwrap());
+        assert!(!verify_password("correct horse", &hash, &hashing(64, None)).unwrap());
+    }
+
+    #[test]
+    fn hash_from_before_the_pepper_is_accepted_and_rehashed() {
+        let hash = hash_password("correct horse", &hashing(64, None)).unwrap();
+        let verification = verify_password_with_rehash("correct horse", &hash, &hashing(64, Some("pepper"))).unwrap();
+        assert_eq!(verification, PasswordVerification { valid: true, needs_rehash: true });
+    }
+
+    #[test]
+    fn hash_with_old_parameters_needs_rehash() {
+        let hash = hash_password("correct horse", &hashing(64, None)).unwrap();
+        let verification = verify_password_with_rehash("correct horse", &hash, &hashing(128, None)).unwrap();
+        assert_eq!(verification, PasswordVerification { valid: true, needs_rehash: true });
+    }
+
+    #[test]
+    fn bcrypt_hash_is_verified_and_migrated_to_argon2() {
+        let current = hashing(64, Some("pepper"));
+        let hash = bcrypt::hash("correct horse", 4).unwrap();
+
+        let verification = verify_password_with_rehash("correct horse", &hash, &current).unwrap();
+        assert_eq!(verification, PasswordVerification { valid: true, needs_rehash: true });
+        let verification = verify_password_with_rehash("wrong horse", &hash, &current).unwrap();
+        assert_eq!(verification, PasswordVerification { valid: false, needs_rehash: false });
 
-    let argon2 = Argon2::default();
-    Ok(argon2
-        .verify_password(password.as_bytes(), &parsed_hash)
-        .is_ok())
+        let rehashed = hash_password("correct horse", &current).unwrap();
+        assert!(rehashed.starts_with("$argon2id$"));
+        assert!(!verify_password_with_rehash("correct horse", &rehashed, &current).unwrap().needs_rehash);
+    }
 }
@@ -0,0 +1,309 @@
+use std::{borrow::Cow, path::PathBuf};
+
+use actix_web::web;
+use tracing::error;
+use validator::{ValidationError, ValidationErrors};
+
+use crate::{config::AppConfig, error::AppError, utils::breached_password};
+
+/// Sık kullanılan parola ve kelimeler; güç skorunda sözlük eşleşmesi sayılır
+const COMMON_WORDS: &[&str] = &[
+    "password", "parola", "sifre", "şifre", "qwerty", "azerty", "letmein", "welcome", "admin",
+    "iloveyou", "monkey", "dragon", "master", "sunshine", "princess", "football", "baseball",
+    "galatasaray", "fenerbahce", "besiktas", "trabzonspor", "istanbul", "ankara", "turkiye",
+    "login", "secret", "shadow", "superman", "batman", "trustno1", "hello", "freedom",
+];
+
+const KEYBOARD_ROWS: &[&str] = &["qwertyuiop", "asdfghjkl", "zxcvbnm", "1234567890", "qwertzuiop"];
+
+/// Kayıt, parola sıfırlama ve parola değişikliğinde uygulanan kurallar
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    pub min_score: u8,
+    pub breached_passwords_dir: Option<PathBuf>,
+}
+
+impl PasswordPolicy {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            min_length: config.password_min_length,
+            require_lowercase: config.password_require_lowercase,
+            require_uppercase: config.password_require_uppercase,
+            require_digit: config.password_require_digit,
+            require_symbol: config.password_require_symbol,
+            min_score: config.password_min_score.min(4),
+            breached_passwords_dir: config.breached_passwords_dir.as_ref().map(PathBuf::from),
+        }
+    }
+
+    /// Tüm kuralları uygular; her ihlal `password` alanında ayrı bir hata olarak döner.
+    /// `user_inputs` kullanıcı adı, e-posta gibi parolada geçmemesi gereken değerlerdir.
+    pub async fn check(&self, password: &str, user_inputs: &[&str]) -> Result<(), AppError> {
+        let mut violations = self.violations(password, user_inputs);
+
+        if violations.is_empty() {
+            if let Some(dir) = self.breached_passwords_dir.clone() {
+                let candidate = password.to_string();
+                let breached = web::block(move || breached_password::is_breached(&dir, &candidate))
+                    .await
+                    .map_err(|e| AppError::internal_error(&e.to_string()))?
+                    .unwrap_or_else(|e| {
+                        error!("Breached password list could not be read: {}", e);
+                        false
+                    });
+                if breached {
+                    violations.push(violation("password_breached", "password appears in a known data breach".to_string()));
+                }
+            }
+        }
+
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let mut errors = ValidationErrors::new();
+        for v in violations {
+            errors.add("password", v);
+        }
+        Err(AppError::InvalidFields(errors))
+    }
+
+    fn violations(&self, password: &str, user_inputs: &[&str]) -> Vec<ValidationError> {
+        let mut violations = Vec::new();
+
+        if password.chars().count() < self.min_length {
+            let mut too_short = violation(
+                "password_too_short",
+                format!("password must be at least {} characters", self.min_length),
+            );
+            too_short.add_param(Cow::from("min"), &self.min_length);
+            violations.push(too_short);
+        }
+        if self.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
+            violations.push(violation("password_lowercase", "password must contain a lowercase letter".to_string()));
+        }
+        if self.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
+            violations.push(violation("password_uppercase", "password must contain an uppercase letter".to_string()));
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            violations.push(violation("password_digit", "password must contain a digit".to_string()));
+        }
+        if self.require_symbol && !password.chars().any(|c| !c.is_alphanumeric()) {
+            violations.push(violation("password_symbol", "password must contain a symbol".to_string()));
+        }
+
+        let lowered = password.to_lowercase();
+        for input in expand_user_inputs(user_inputs) {
+            if lowered.contains(&input) {
+                violations.push(violation(
+                    "password_user_input",
+                    "password must not contain your username or email".to_string(),
+                ));
+                break;
+            }
+        }
+
+        let score = strength_score(password, user_inputs);
+        if score < self.min_score {
+            let mut too_weak = violation(
+                "password_too_weak",
+                format!("password is too weak (strength {} of 4, at least {} required)", score, self.min_score),
+            );
+            too_weak.add_param(Cow::from("score"), &score);
+            too_weak.add_param(Cow::from("min_score"), &self.min_score);
+            violations.push(too_weak);
+        }
+
+        violations
+    }
+}
+
+fn violation(code: &'static str, message: String) -> ValidationError {
+    ValidationError::new(code).with_message(Cow::from(message))
+}
+
+/// E-posta adreslerinin yerel kısmını da ayrı bir girdi olarak ekler; 3 karakterden kısa girdiler yok sayılır
+fn expand_user_inputs(user_inputs: &[&str]) -> Vec<String> {
+    let mut expanded = Vec::new();
+    for input in user_inputs {
+        let lowered = input.trim().to_lowercase();
+        if let Some((local, _)) = lowered.split_once('@') {
+            expanded.push(local.to_string());
+        }
+        expanded.push(lowered);
+    }
+    expanded.retain(|i| i.chars().count() >= 3);
+    expanded
+}
+
+/// zxcvbn tarzı 0-4 güç skoru.
+///
+/// Tahmini tahmin sayısı karakter havuzundan hesaplanır; tekrarlar, ardışık
+/// diziler, klavye desenleri, yaygın kelimeler ve kullanıcı bilgileri entropiyi düşürür.
+/// Eşikler zxcvbn ile aynıdır: 10^3, 10^6, 10^8, 10^10 tahmin.
+pub fn strength_score(password: &str, user_inputs: &[&str]) -> u8 {
+    let lowered = password.to_lowercase();
+    if COMMON_WORDS.contains(&lowered.as_str()) {
+        return 0;
+    }
+
+    let chars: Vec<char> = password.chars().collect();
+    let pool = charset_size(&chars) as f64;
+    let char_bits = pool.log2();
+    let lowered_chars: Vec<char> = lowered.chars().collect();
+
+    // Her karakter için tahmin bitleri; tekrarların devamı 0.25, ardışık dizilerin devamı 1 bit sayılır
+    let mut bits: Vec<f64> = lowered_chars
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            if i == 0 {
+                return char_bits;
+            }
+            match (*c as i64 - lowered_chars[i - 1] as i64).abs() {
+                0 => 0.25,
+                1 => 1.0,
+                _ => char_bits,
+            }
+        })
+        .collect();
+
+    // Sözlük, klavye ve kullanıcı bilgisi eşleşmeleri: eşleşen aralık yaklaşık 7 bit sayılır
+    let mut patterns: Vec<String> = COMMON_WORDS.iter().map(|w| w.to_string()).collect();
+    for row in KEYBOARD_ROWS {
+        for window in row.as_bytes().windows(4) {
+            patterns.push(String::from_utf8_lossy(window).into_owned());
+        }
+    }
+    patterns.extend(expand_user_inputs(user_inputs));
+
+    for pattern in patterns {
+        let pattern_chars: Vec<char> = pattern.chars().collect();
+        if pattern_chars.len() < 3 || pattern_chars.len() > lowered_chars.len() {
+            continue;
+        }
+        for start in 0..=(lowered_chars.len() - pattern_chars.len()) {
+            if lowered_chars[start..start + pattern_chars.len()] == pattern_chars[..] {
+                let end = start + pattern_chars.len();
+                for (offset, b) in bits[start..end].iter_mut().enumerate() {
+                    *b = if offset == 0 { b.min(7.0) } else { 0.0 };
+                }
+            }
+        }
+    }
+
+    let total_bits: f64 = bits.iter().sum();
+    let guesses_log10 = total_bits * std::f64::consts::LOG10_2;
+    match guesses_log10 {
+        g if g < 3.0 => 0,
+        g if g < 6.0 => 1,
+        g if g < 8.0 => 2,
+        g if g < 10.0 => 3,
+        _ => 4,
+    }
+}
+
+fn charset_size(chars: &[char]) -> u32 {
+    let mut size = 0;
+    if chars.iter().any(|c| c.is_ascii_lowercase()) {
+        size += 26;
+    }
+    if chars.iter().any(|c| c.is_ascii_uppercase()) {
+        size += 26;
+    }
+    if chars.iter().any(|c| c.is_ascii_digit()) {
+        size += 10;
+    }
+    if chars.iter().any(|c| c.is_ascii_punctuation() || *c == ' ') {
+        size += 33;
+    }
+    if chars.iter().any(|c| !c.is_ascii()) {
+        size += 100;
+    }
+    size.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 10,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            require_symbol: false,
+            min_score: 3,
+            breached_passwords_dir: None,
+        }
+    }
+
+    fn codes(violations: &[ValidationError]) -> Vec<&str> {
+        violations.iter().map(|v| v.code.as_ref()).collect()
+    }
+
+    #[test]
+    fn accepts_a_long_random_password() {
+        assert!(policy().violations("vT9#qLm2!xRw7@pZ", &["ayse", "ayse@example.com"]).is_empty());
+    }
+
+    #[test]
+    fn counts_characters_not_bytes_for_min_length() {
+        let violations = policy().violations("çğıöşüÇĞİ", &[]);
+        let too_short = violations.iter().find(|v| v.code == "password_too_short").expect("too short");
+        assert_eq!(too_short.params["min"], 10);
+        assert!(!codes(&policy().violations("çğıöşüÇĞİÖ9x#", &[])).contains(&"password_too_short"));
+    }
+
+    #[test]
+    fn rejects_common_words_sequences_and_keyboard_walks() {
+        assert_eq!(strength_score("password", &[]), 0);
+        assert!(strength_score("aaaaaaaaaaaa", &[]) < 2);
+        assert!(strength_score("abcdefghijkl", &[]) < 2);
+        assert!(strength_score("qwertyuiop", &[]) < 3);
+        assert_eq!(strength_score("vT9#qLm2!xRw7@pZ", &[]), 4);
+
+        let violations = policy().violations("qwertyuiop12", &[]);
+        let too_weak = violations.iter().find(|v| v.code == "password_too_weak").expect("too weak");
+        assert_eq!(too_weak.params["min_score"], 3);
+    }
+
+    #[test]
+    fn rejects_passwords_containing_user_inputs() {
+        let violations = policy().violations("xX-mehmetyilmaz-9!", &["MehmetYilmaz", "m.y@example.com"]);
+        assert!(codes(&violations).contains(&"password_user_input"), "{:?}", violations);
+
+        // E-postanın yerel kısmı da ayrı girdi sayılır
+        let violations = policy().violations("Kq8!zeynep.kaya#2", &["zk", "zeynep.kaya@example.com"]);
+        assert!(codes(&violations).contains(&"password_user_input"), "{:?}", violations);
+
+        // 3 karakterden kısa girdiler yok sayılır
+        let violations = policy().violations("vT9#qLm2!xRw7@pZ", &["vT", "qL"]);
+        assert!(violations.is_empty(), "{:?}", violations);
+    }
+
+    #[test]
+    fn reports_each_character_class_separately() {
+        let strict = PasswordPolicy {
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: true,
+            min_score: 0,
+            ..policy()
+        };
+        assert_eq!(
+            codes(&strict.violations("                ", &[])),
+            vec!["password_lowercase", "password_uppercase", "password_digit"]
+        );
+        assert_eq!(
+            codes(&strict.violations("abcdefghijklmnop", &[])),
+            vec!["password_uppercase", "password_digit", "password_symbol"]
+        );
+    }
+}
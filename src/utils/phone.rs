@@ -0,0 +1,81 @@
+use crate::error::AppError;
+
+/// Telefon numarasını E.164 biçimine (`+905321234567`) dönüştürür.
+///
+/// Boşluk, tire, nokta ve parantezler atılır; `00` öneki `+` kabul edilir,
+/// ülke kodu içermeyen numaralara `default_country_code` eklenir.
+pub fn normalize_e164(input: &str, default_country_code: &str) -> Result<String, AppError> {
+    let trimmed = input.trim();
+    // `+` yalnızca en başta kabul edilir; "12+34" gibi girdiler reddedilir
+    let (has_plus, rest) = match trimmed.strip_prefix('+') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+    let mut digits: String = rest
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '.' | '(' | ')'))
+        .collect();
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(AppError::validation_error("phone_number must contain only digits"));
+    }
+
+    if !has_plus {
+        if let Some(rest) = digits.strip_prefix("00") {
+            digits = rest.to_string();
+        } else {
+            // Ulusal biçim: baştaki trunk "0" atılır ve varsayılan ülke kodu eklenir
+            let national = digits.trim_start_matches('0');
+            digits = format!("{}{}", default_country_code, national);
+        }
+    }
+
+    let normalized = format!("+{}", digits);
+    if !is_valid_e164(&normalized) {
+        return Err(AppError::validation_error("phone_number is not a valid E.164 number"));
+    }
+    Ok(normalized)
+}
+
+/// `+` ile başlayan, ilk hanesi 0 olmayan 8-15 haneli numaralar geçerlidir
+pub fn is_valid_e164(number: &str) -> bool {
+    let Some(digits) = number.strip_prefix('+') else {
+        return false;
+    };
+    (8..=15).contains(&digits.len())
+        && !digits.starts_with('0')
+        && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Loglarda ve yanıtlarda numaranın yalnızca son dört hanesini gösterir
+pub fn mask(number: &str) -> String {
+    let visible = number.len().saturating_sub(4);
+    format!("{}{}", "*".repeat(visible), &number[visible..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_international_and_national_formats() {
+        assert_eq!(normalize_e164("+90 532 123 45 67", "90").unwrap(), "+905321234567");
+        assert_eq!(normalize_e164("0090 (532) 123-45-67", "90").unwrap(), "+905321234567");
+        assert_eq!(normalize_e164("0532 123 45 67", "90").unwrap(), "+905321234567");
+        assert_eq!(normalize_e164("  +1.415.555.2671 ", "90").unwrap(), "+14155552671");
+    }
+
+    #[test]
+    fn rejects_plus_outside_the_leading_position() {
+        assert!(normalize_e164("12+34567890", "90").is_err());
+        assert!(normalize_e164("++905321234567", "90").is_err());
+    }
+
+    #[test]
+    fn rejects_letters_and_out_of_range_lengths() {
+        assert!(normalize_e164("+90 532 ABC 45 67", "90").is_err());
+        assert!(normalize_e164("", "90").is_err());
+        assert!(normalize_e164("+1234567", "90").is_err());
+        assert!(normalize_e164("+1234567890123456", "90").is_err());
+    }
+}
@@ -0,0 +1,35 @@
+use deadpool_redis::redis;
+
+use crate::{error::AppError, utils::traced_redis::TracedConnection};
+
+/// Sayacı artırır ve ilk istekte süresini başlatır; tek adımda çalıştığı için
+/// ikisinin arasında bağlantı kopsa da süresiz bir sayaç kalmaz
+const INCR_WITH_WINDOW_SCRIPT: &str = r#"
+local count = redis.call('INCR', KEYS[1])
+if redis.call('TTL', KEYS[1]) < 0 then
+    redis.call('EXPIRE', KEYS[1], ARGV[1])
+end
+return count
+"#;
+
+/// Sabit pencereli sayaç: `key` için `window_secs` içinde `limit`'ten fazla
+/// istek yapılmışsa `AppError::TooManyRequests` döner.
+pub async fn enforce(
+    conn: &mut TracedConnection,
+    key: &str,
+    limit: u32,
+    window_secs: i64,
+    message: &str,
+) -> Result<(), AppError> {
+    let count: u32 = redis::cmd("EVAL")
+        .arg(INCR_WITH_WINDOW_SCRIPT)
+        .arg(1)
+        .arg(key)
+        .arg(window_secs)
+        .query_async(conn)
+        .await?;
+    if count > limit {
+        return Err(AppError::TooManyRequests(message.to_string()));
+    }
+    Ok(())
+}
@@ -1,7 +1,15 @@
- use sha2::{Sha256, Digest};
+ use hmac::{Hmac, Mac};
+use sha2::{Sha256, Digest};
 
 pub fn sha256_hash(token: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(token.as_bytes());
     format!("{:x}", hasher.finalize())
-}
\ No newline at end of file
+}
+
+/// Anahtarlı (HMAC-SHA256) özet; anahtarı bilmeyen biri olası değerleri deneyerek eşleştiremez
+pub fn keyed_hash(value: &str, secret: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(value.as_bytes());
+    format!("{:x}", mac.finalize().into_bytes())
+}
@@ -0,0 +1,66 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::sync::{mpsc, watch};
+
+/// Kapanış sinyali ve arka plan işlerinin takibi. Her uzun ömürlü görev (arka plan
+/// işleri, akış yanıtları) `subscribe` ile bir dinleyici alır; `shutdown` sinyali
+/// yayınlar ve tüm dinleyiciler bırakılana kadar (en fazla verilen süre kadar) bekler.
+pub struct Shutdown {
+    trigger: watch::Sender<bool>,
+    /// Dinleyicilerin tuttuğu göndericinin kaynağı; kapanışta bırakılır
+    guard: Mutex<Option<mpsc::Sender<()>>>,
+    finished: tokio::sync::Mutex<mpsc::Receiver<()>>,
+}
+
+pub struct ShutdownListener {
+    receiver: watch::Receiver<bool>,
+    _guard: Option<mpsc::Sender<()>>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        let (trigger, _) = watch::channel(false);
+        let (guard, finished) = mpsc::channel(1);
+        Self {
+            trigger,
+            guard: Mutex::new(Some(guard)),
+            finished: tokio::sync::Mutex::new(finished),
+        }
+    }
+}
+
+impl Shutdown {
+    pub fn subscribe(&self) -> ShutdownListener {
+        ShutdownListener {
+            receiver: self.trigger.subscribe(),
+            _guard: self.guard.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        }
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        *self.trigger.borrow()
+    }
+
+    /// Dinleyicilere kapanışı bildirir; birden fazla çağrılabilir
+    pub fn trigger(&self) {
+        self.trigger.send_replace(true);
+    }
+
+    /// Sinyali yayınlar ve görevlerin bitmesini bekler; süre dolarsa false döner
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.trigger();
+        self.guard.lock().unwrap_or_else(|e| e.into_inner()).take();
+
+        let mut finished = self.finished.lock().await;
+        // Tüm göndericiler bırakıldığında `recv` None döner
+        tokio::time::timeout(timeout, finished.recv()).await.is_ok()
+    }
+}
+
+impl ShutdownListener {
+    /// Kapanış başlayana kadar bekler
+    pub async fn recv(&mut self) {
+        let _ = self.receiver.wait_for(|triggered| *triggered).await;
+    }
+}
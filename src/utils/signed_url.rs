@@ -0,0 +1,34 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{error::AppError, utils::otp::constant_time_eq};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// `path` ve son geçerlilik zamanı için HMAC-SHA256 imzası (hex)
+pub fn sign(path: &str, expires: i64, secret: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(path.as_bytes());
+    mac.update(b"\n");
+    mac.update(expires.to_string().as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// `?expires=...&sig=...` sorgu parametreleriyle imzalı yol üretir
+pub fn signed_path(path: &str, expires: i64, secret: &str) -> String {
+    format!("{}?expires={}&sig={}", path, expires, sign(path, expires, secret))
+}
+
+pub fn verify(path: &str, expires: i64, signature: &str, secret: &str) -> Result<(), AppError> {
+    if expires < chrono::Utc::now().timestamp() {
+        return Err(AppError::Forbidden("Download link has expired".to_string()));
+    }
+    if !constant_time_eq(sign(path, expires, secret).as_bytes(), signature.as_bytes()) {
+        return Err(AppError::Forbidden("Invalid download link signature".to_string()));
+    }
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+
+use chrono::Utc;
+use serde_json::{Map, Value};
+use tokio::sync::mpsc;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+use crate::models::system_log::NewSystemLog;
+
+/// Yazıcıya giden kuyruğun kapasitesi; dolarsa yeni kayıtlar düşürülür, istekler beklemez
+const QUEUE_CAPACITY: usize = 4096;
+/// Yazıcının veritabanı işlemleri bu span içinde çalışır; içinde oluşan olaylar tekrar
+/// kuyruğa alınmaz (yazma hatası yeni bir yazma denemesi doğurmasın diye)
+pub const WRITER_SPAN: &str = "system_log_writer";
+
+pub type SystemLogReceiver = mpsc::Receiver<NewSystemLog>;
+
+/// WARN ve ERROR olaylarını `system_log` tablosuna yazılmak üzere kuyruğa alan katman.
+/// Olay anında veritabanına gidilmez; kayıtlar `services::system_log` yazıcısına gönderilir.
+pub struct SystemLogLayer {
+    sender: mpsc::Sender<NewSystemLog>,
+}
+
+impl SystemLogLayer {
+    pub fn new() -> (Self, SystemLogReceiver) {
+        let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+        (Self { sender }, receiver)
+    }
+}
+
+/// Kök istek span'inin log satırlarına taşınan alanları
+#[derive(Debug, Default)]
+struct RequestFields {
+    request_id: Option<String>,
+    user_id: Option<i32>,
+}
+
+impl Visit for RequestFields {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        if field.name() == "user_id" {
+            self.user_id = i32::try_from(value).ok();
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "user_id" {
+            self.user_id = i32::try_from(value).ok();
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "request_id" {
+            self.request_id = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "request_id" {
+            self.request_id = Some(format!("{:?}", value));
+        }
+    }
+}
+
+/// Olayın mesajını ve diğer alanlarını ayırır
+#[derive(Default)]
+struct EventFields {
+    message: String,
+    fields: Map<String, Value>,
+}
+
+impl Visit for EventFields {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.fields.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.fields.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.fields.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.fields.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields.insert(field.name().to_string(), Value::from(value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let value = format!("{:?}", value);
+        if field.name() == "message" {
+            self.message = value;
+        } else {
+            self.fields.insert(field.name().to_string(), Value::from(value));
+        }
+    }
+}
+
+impl<S> Layer<S> for SystemLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let fields = attrs.metadata().fields();
+        if fields.field("request_id").is_none() && fields.field("user_id").is_none() {
+            return;
+        }
+        let mut request = RequestFields::default();
+        attrs.record(&mut request);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(request);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        // `user_id` kimlik doğrulandıktan sonra kök span'e yazılır
+        if let Some(span) = ctx.span(id) {
+            if let Some(request) = span.extensions_mut().get_mut::<RequestFields>() {
+                values.record(request);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = match *metadata.level() {
+            Level::ERROR => "error",
+            Level::WARN => "warn",
+            _ => return,
+        };
+
+        let mut request_id = None;
+        let mut user_id = None;
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope {
+                if span.name() == WRITER_SPAN {
+                    return;
+                }
+                if let Some(request) = span.extensions().get::<RequestFields>() {
+                    request_id = request_id.or_else(|| request.request_id.clone());
+                    user_id = user_id.or(request.user_id);
+                }
+            }
+        }
+
+        let mut visitor = EventFields::default();
+        event.record(&mut visitor);
+        let record = NewSystemLog {
+            occurred_at: Utc::now(),
+            level,
+            target: metadata.target().chars().take(255).collect(),
+            message: visitor.message,
+            fields: Value::Object(visitor.fields),
+            request_id,
+            user_id,
+        };
+        // Kuyruk doluysa ya da yazıcı durduysa kayıt yalnızca stdout'ta kalır
+        let _ = self.sender.try_send(record);
+    }
+}
@@ -0,0 +1,124 @@
+use std::fs::File;
+
+use opentelemetry::{global, trace::TraceContextExt, KeyValue};
+use opentelemetry_http::HeaderInjector;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    runtime,
+    trace::{self as sdktrace, Sampler, Tracer, TracerProvider},
+    Resource,
+};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+use crate::{
+    config::AppConfig,
+    error::AppError,
+    utils::system_log::{SystemLogLayer, SystemLogReceiver},
+};
+
+/// `tracing`'i yapılandırır: her zaman fmt çıktısı (`log_format`'a göre metin ya da JSON),
+/// WARN ve üstü olaylar için `system_log` katmanı, exporter seçiliyse ek olarak OpenTelemetry
+/// katmanı. W3C `traceparent` yayıcısı her durumda kurulur. `log` kullanan bağımlılıkların
+/// kayıtları da `tracing-log` köprüsüyle aynı çıktıya düşer.
+///
+/// Dönen alıcı, veritabanı hazır olduğunda `services::system_log::spawn_writer`'a verilmelidir.
+pub fn init(config: &AppConfig) -> Result<SystemLogReceiver, AppError> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let fmt_layer = match config.log_format.as_str() {
+        // Span listesi, iç span'lerde (ör. veritabanı) yazılan satırlara da kök span'in
+        // request_id/user_id/route alanlarını taşır
+        "json" => tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(false)
+            .with_span_list(true)
+            .boxed(),
+        _ => tracing_subscriber::fmt::layer().with_target(true).boxed(),
+    };
+    let (system_log_layer, system_log_receiver) = SystemLogLayer::new();
+    let otel_layer = tracer(config)?.map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer));
+
+    tracing_subscriber::registry()
+        .with(EnvFilter::from_default_env())
+        .with(fmt_layer)
+        .with(system_log_layer)
+        .with(otel_layer)
+        .init();
+    Ok(system_log_receiver)
+}
+
+fn trace_config(config: &AppConfig) -> sdktrace::Config {
+    sdktrace::config()
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(config.otel_sample_ratio))))
+        .with_resource(Resource::new(vec![KeyValue::new(
+            "service.name",
+            config.otel_service_name.clone(),
+        )]))
+}
+
+fn tracer(config: &AppConfig) -> Result<Option<Tracer>, AppError> {
+    let provider = match config.otel_exporter.as_str() {
+        "none" => return Ok(None),
+        "otlp" => {
+            let endpoint = config.otel_endpoint.clone().unwrap_or_default();
+            let tracer = opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(opentelemetry_otlp::new_exporter().http().with_endpoint(endpoint))
+                .with_trace_config(trace_config(config))
+                .install_batch(runtime::Tokio)
+                .map_err(|e| AppError::internal_error(&format!("OTLP exporter could not be started: {}", e)))?;
+            return Ok(Some(tracer));
+        }
+        "stdout" => TracerProvider::builder()
+            .with_batch_exporter(opentelemetry_stdout::SpanExporter::default(), runtime::Tokio)
+            .with_config(trace_config(config))
+            .build(),
+        "file" => {
+            let file = File::create(&config.otel_file_path).map_err(|e| {
+                AppError::internal_error(&format!("trace file {} could not be opened: {}", config.otel_file_path, e))
+            })?;
+            let exporter = opentelemetry_stdout::SpanExporter::builder().with_writer(file).build();
+            TracerProvider::builder()
+                .with_batch_exporter(exporter, runtime::Tokio)
+                .with_config(trace_config(config))
+                .build()
+        }
+        other => return Err(AppError::internal_error(&format!("Unknown trace exporter: {}", other))),
+    };
+
+    let tracer = opentelemetry::trace::TracerProvider::tracer(&provider, config.otel_service_name.clone());
+    global::set_tracer_provider(provider);
+    Ok(Some(tracer))
+}
+
+/// Kuyruktaki span'leri gönderir; kapanışta çağrılmalıdır
+pub fn shutdown() {
+    global::shutdown_tracer_provider();
+}
+
+/// Geçerli span'in trace id'si; exporter kapalıysa ya da span yoksa `None`
+pub fn current_trace_id() -> Option<String> {
+    let context = Span::current().context();
+    let span_context = context.span().span_context().clone();
+    span_context.is_valid().then(|| span_context.trace_id().to_string())
+}
+
+/// Giden HTTP isteğine geçerli span'in `traceparent` başlığını ekler
+pub fn inject_trace_headers(headers: &mut reqwest::header::HeaderMap) {
+    let context = Span::current().context();
+    global::get_text_map_propagator(|propagator| propagator.inject_context(&context, &mut HeaderInjector(headers)));
+}
+
+/// Yapay zekâ sağlayıcı çağrılarını saran span; token sayıları çağrı bitince `record` ile yazılır
+pub fn ai_provider_span(provider: &str, model: &str) -> Span {
+    tracing::info_span!(
+        "ai_provider.request",
+        ai.provider = provider,
+        ai.model = model,
+        ai.prompt_tokens = tracing::field::Empty,
+        ai.completion_tokens = tracing::field::Empty,
+    )
+}
@@ -0,0 +1,44 @@
+use deadpool_redis::{
+    redis::{aio::ConnectionLike, Arg, Cmd, Pipeline, RedisFuture, Value},
+    Connection,
+};
+use tracing::{info_span, Instrument};
+
+/// Havuz bağlantısını saran ve her Redis komutu için bir span açan bağlantı.
+/// `AsyncCommands` ve `redis::cmd(...).query_async` ile olduğu gibi kullanılır.
+pub struct TracedConnection(Connection);
+
+impl TracedConnection {
+    pub fn new(conn: Connection) -> Self {
+        Self(conn)
+    }
+}
+
+/// Komut adı (GET, SET, INCR...); anahtar ve değerler span'e yazılmaz
+fn command_name(cmd: &Cmd) -> String {
+    match cmd.args_iter().next() {
+        Some(Arg::Simple(name)) => String::from_utf8_lossy(name).to_uppercase(),
+        _ => "UNKNOWN".to_string(),
+    }
+}
+
+impl ConnectionLike for TracedConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        let span = info_span!("redis.command", db.system = "redis", db.operation = %command_name(cmd));
+        Box::pin(self.0.req_packed_command(cmd).instrument(span))
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        let span = info_span!("redis.pipeline", db.system = "redis", db.commands = count);
+        Box::pin(self.0.req_packed_commands(cmd, offset, count).instrument(span))
+    }
+
+    fn get_db(&self) -> i64 {
+        self.0.get_db()
+    }
+}
@@ -1 +1,42 @@
+use bigdecimal::BigDecimal;
+use validator::ValidationError;
 
+/// `RegisterRequest` / `UserUpdate` için telefon numarası doğrulaması.
+/// Biçim serbesttir; kayıt sırasında E.164'e normalize edilir.
+pub fn validate_phone_number(phone: &str) -> Result<(), ValidationError> {
+    let digits = phone.chars().filter(|c| c.is_ascii_digit()).count();
+    let allowed = phone
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, ' ' | '-' | '.' | '(' | ')' | '+'));
+
+    if !allowed || !(7..=15).contains(&digits) {
+        return Err(ValidationError::new("phone_number"));
+    }
+    Ok(())
+}
+
+/// `numeric(12,2)` token sütunlarına sığan en büyük değer
+const MAX_TOKEN_AMOUNT: i64 = 9_999_999_999;
+/// Yöneticinin tek seferde yükleyebileceği en fazla token
+const MAX_CREDIT_AMOUNT: i64 = 1_000_000;
+
+/// En fazla iki ondalık basamak; fazlası sessizce yuvarlanmak yerine reddedilir
+fn has_token_scale(value: &BigDecimal) -> bool {
+    value.normalized().as_bigint_and_exponent().1 <= 2
+}
+
+/// Organizasyon havuzuna yüklenen miktar: 0'dan büyük, en fazla 1.000.000 ve iki ondalık basamak
+pub fn validate_credit_amount(amount: &BigDecimal) -> Result<(), ValidationError> {
+    if !has_token_scale(amount) || *amount <= BigDecimal::from(0) || *amount > BigDecimal::from(MAX_CREDIT_AMOUNT) {
+        return Err(ValidationError::new("token_amount"));
+    }
+    Ok(())
+}
+
+/// Üye harcama limiti: negatif olamaz, `numeric(12,2)`'ye sığmalı ve iki ondalık basamak
+pub fn validate_spending_cap(cap: &BigDecimal) -> Result<(), ValidationError> {
+    if !has_token_scale(cap) || *cap < BigDecimal::from(0) || *cap > BigDecimal::from(MAX_TOKEN_AMOUNT) {
+        return Err(ValidationError::new("token_amount"));
+    }
+    Ok(())
+}